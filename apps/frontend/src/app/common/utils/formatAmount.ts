@@ -2,3 +2,30 @@ export const formatAmount = (amount: string) =>
   Intl.NumberFormat("en-US", {
     currency: "USD",
   }).format(BigInt(amount));
+
+// short scale, with q for quadrillion, the way tiny spl names compact leaves
+const COMPACT_AMOUNT_SUFFIXES = ["K", "M", "B", "T", "q", "Q"];
+
+// truncates rather than rounds, matching the leaf names minted on chain
+export const formatCompactAmount = (amount: string) => {
+  const value = BigInt(amount);
+  if (value < 1_000n) {
+    return value.toString();
+  }
+
+  let unit = 1_000n;
+  let suffixIndex = 0;
+  while (
+    suffixIndex + 1 < COMPACT_AMOUNT_SUFFIXES.length &&
+    value / unit >= 1_000n
+  ) {
+    unit *= 1_000n;
+    suffixIndex += 1;
+  }
+
+  const whole = value / unit;
+  const tenths = (value % unit) / (unit / 10n);
+  const suffix = COMPACT_AMOUNT_SUFFIXES[suffixIndex];
+
+  return tenths === 0n ? `${whole}${suffix}` : `${whole}.${tenths}${suffix}`;
+};
@@ -26,6 +26,7 @@ import {
 import { getAssetAmount } from "../../swr-hooks/utils/getAssetAmount";
 import { getAssetCollectionId } from "../../swr-hooks/utils/getAssetCollectionId";
 import { TREE_ADDRESS_3 } from "../constants/treeAddresses";
+import { getLeafFormats } from "./getLeafFormats";
 
 export const buildCombineTinySplTx = async ({
   assets,
@@ -80,14 +81,32 @@ export const buildCombineTinySplTx = async ({
     tinySplProgram.programId
   );
 
-  const sourceTrees = assets.map((asset) => asset.compression.tree);
-  const sourceTreeAuthorities = sourceTrees.map(
-    (tree) =>
-      PublicKey.findProgramAddressSync(
-        [new PublicKey(tree).toBuffer()],
-        BUBBLEGUM_PROGRAM_ID
-      )[0]
+  // every input is verified in the format it was minted in, the combined leaf
+  // is minted in the current one
+  const { leafFormat, getAssetLeafFormat, initLeafFormatIxs } =
+    await getLeafFormats({
+      tinySplProgram,
+      collectionId,
+      tinySplAuthority,
+      payer: signer,
+    });
+  const assetLeafFormats = assets.map((asset) =>
+    getAssetLeafFormat(asset).toBase58()
   );
+  // formats other than the current one are passed once, inputs refer to them
+  // by index with 0 being `sourceLeafFormat`
+  const extraLeafFormats = [
+    ...new Set(
+      assetLeafFormats.filter(
+        (assetLeafFormat) => assetLeafFormat !== leafFormat.toBase58()
+      )
+    ),
+  ];
+
+  // every source tree is passed once, inputs refer to it by index
+  const sourceTrees = [
+    ...new Set(assets.map((asset) => asset.compression.tree)),
+  ];
 
   const [destinationTreeAuthority] = PublicKey.findProgramAddressSync(
     [TREE_ADDRESS_3.toBuffer()],
@@ -117,25 +136,25 @@ export const buildCombineTinySplTx = async ({
         .slice(0, assetProof.proof.length - (canopyDepth ? canopyDepth : 0));
     })
   );
-  const proofPathEndIndexesExcluded = proofPaths.reduce((acc, curr) => {
-    const prevEndIndex = acc.at(-1) ?? 0;
-    const nextEndIndex = prevEndIndex + curr.length;
-    acc.push(nextEndIndex);
-
-    return acc;
-  }, new Array<number>());
-
-  const flattenedProofPaths = proofPaths.flat();
   const ix = await tinySplProgram.methods
     .combine(
-      assets.map((asset) => new BN(getAssetAmount(asset))),
-      assets.map((asset) => new PublicKey(asset.id)),
-      assetProofs.map((assetProof) => [
-        ...new PublicKey(assetProof.root.trim()).toBytes(),
-      ]),
-      assets.map((asset) => new BN(asset.compression.leaf_id)),
-      assets.map((asset) => asset.compression.leaf_id),
-      proofPathEndIndexesExcluded
+      {
+        inputs: assets.map((asset, i) => ({
+          amount: new BN(getAssetAmount(asset)),
+          assetId: new PublicKey(asset.id),
+          root: [...new PublicKey(assetProofs[i].root.trim()).toBytes()],
+          nonce: new BN(asset.compression.leaf_id),
+          index: asset.compression.leaf_id,
+          treeIndex: sourceTrees.indexOf(asset.compression.tree),
+          leafFormatIndex: extraLeafFormats.indexOf(assetLeafFormats[i]) + 1,
+          proofLength: proofPaths[i].length,
+        })),
+        treeCount: sourceTrees.length,
+        leafFormatCount: extraLeafFormats.length,
+      },
+      // mint into `destinationMerkleTree` without keeping any delegate
+      null,
+      false
     )
     .accounts({
       leafOwner: signer,
@@ -143,6 +162,8 @@ export const buildCombineTinySplTx = async ({
       compressionProgram: COMPRESSION_PROGRAM_ID,
       collectionMint: collectionId,
       tinySplAuthority,
+      sourceLeafFormat: leafFormat,
+      leafFormat,
       destinationMerkleTree: TREE_ADDRESS_3,
       systemProgram: SystemProgram.programId,
       authority: signer,
@@ -157,19 +178,27 @@ export const buildCombineTinySplTx = async ({
       treeCreatorOrDelegate: tinySplAuthority,
     })
     .remainingAccounts([
-      ...sourceTrees.map((tree) => ({
-        pubkey: new PublicKey(tree),
-        isSigner: false,
-        isWritable: true,
-      })),
-
-      ...sourceTreeAuthorities.map((treeAuthority) => ({
-        pubkey: treeAuthority,
+      ...sourceTrees.flatMap((tree) => [
+        {
+          pubkey: new PublicKey(tree),
+          isSigner: false,
+          isWritable: true,
+        },
+        {
+          pubkey: PublicKey.findProgramAddressSync(
+            [new PublicKey(tree).toBuffer()],
+            BUBBLEGUM_PROGRAM_ID
+          )[0],
+          isSigner: false,
+          isWritable: true,
+        },
+      ]),
+      ...extraLeafFormats.map((extraLeafFormat) => ({
+        pubkey: new PublicKey(extraLeafFormat),
         isSigner: false,
-        isWritable: true,
+        isWritable: false,
       })),
-
-      ...flattenedProofPaths,
+      ...proofPaths.flat(),
     ])
     .instruction();
 
@@ -182,6 +211,7 @@ export const buildCombineTinySplTx = async ({
       ComputeBudgetProgram.setComputeUnitLimit({
         units: 14_000_000,
       }),
+      ...initLeafFormatIxs,
       ix,
     ],
     payer: signer,
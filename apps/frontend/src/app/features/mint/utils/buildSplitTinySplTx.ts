@@ -25,6 +25,7 @@ import {
 
 import { getAssetCollectionId } from "../../swr-hooks/utils/getAssetCollectionId";
 import { TREE_ADDRESS_3 } from "../constants/treeAddresses";
+import { getLeafFormats } from "./getLeafFormats";
 
 export const buildSplitTinySplTx = async ({
   asset,
@@ -75,6 +76,15 @@ export const buildSplitTinySplTx = async ({
     ],
     tinySplProgram.programId
   );
+  // the source leaf is verified in the format it was minted in, the new
+  // leaves are minted in the current one
+  const { leafFormat, getAssetLeafFormat, initLeafFormatIxs } =
+    await getLeafFormats({
+      tinySplProgram,
+      collectionId,
+      tinySplAuthority,
+      payer: signer,
+    });
   const assetProof = await connection.getAssetProof(new PublicKey(asset.id));
 
   const [sourceTreeAuthority] = PublicKey.findProgramAddressSync(
@@ -106,7 +116,12 @@ export const buildSplitTinySplTx = async ({
       [...new PublicKey(assetProof.root.trim()).toBytes()],
       new BN(asset.compression.leaf_id),
       asset.compression.leaf_id,
-      destinationAmounts.map((amount) => new BN(amount))
+      {
+        amounts: destinationAmounts.map((amount) => new BN(amount)),
+        treeIndexes: [],
+        extraTreeCount: 0,
+        preserveDelegate: false,
+      }
     )
     .accounts({
       leafOwner: signer,
@@ -114,6 +129,8 @@ export const buildSplitTinySplTx = async ({
       compressionProgram: COMPRESSION_PROGRAM_ID,
       collectionMint: collectionId,
       tinySplAuthority,
+      sourceLeafFormat: getAssetLeafFormat(asset),
+      leafFormat,
       sourceMerkleTree: assetProof.tree_id,
       destinationMerkleTree: TREE_ADDRESS_3,
       systemProgram: SystemProgram.programId,
@@ -141,6 +158,7 @@ export const buildSplitTinySplTx = async ({
       ComputeBudgetProgram.setComputeUnitLimit({
         units: 14_000_000,
       }),
+      ...initLeafFormatIxs,
       ix,
     ],
     payer: signer,
@@ -0,0 +1,116 @@
+import { IdlAccounts, Program } from "@coral-xyz/anchor";
+import {
+  PublicKey,
+  SystemProgram,
+  TransactionInstruction,
+} from "@solana/web3.js";
+import { LEAF_FORMAT_SEED, TinySpl } from "@tiny-spl/contracts";
+
+import { MPL_TOKEN_METADATA_PROGRAM_ID } from "@/app/common/constants";
+import {
+  formatAmount,
+  formatCompactAmount,
+} from "@/app/common/utils/formatAmount";
+import { ReadApiAsset } from "@/app/common/utils/WrapperConnection";
+
+import { getAssetAmount } from "../../swr-hooks/utils/getAssetAmount";
+
+type LeafFormat = IdlAccounts<TinySpl>["leafFormat"];
+
+// the name a leaf of `amount` gets when it is minted in `leafFormat`
+const getLeafName = (leafFormat: LeafFormat, amount: string) => {
+  const formattedAmount =
+    "compact" in leafFormat.nameStyle
+      ? formatCompactAmount(amount)
+      : formatAmount(amount);
+
+  return `${formattedAmount} ${leafFormat.symbol.replaceAll("\0", "")}`;
+};
+
+export const getLeafFormats = async ({
+  tinySplProgram,
+  collectionId,
+  tinySplAuthority,
+  payer,
+}: {
+  tinySplProgram: Program<TinySpl>;
+  collectionId: string;
+  tinySplAuthority: PublicKey;
+  payer: PublicKey;
+}) => {
+  const { leafFormatVersion } =
+    await tinySplProgram.account.tinySplAuthority.fetch(tinySplAuthority);
+  const leafFormats = [...Array(leafFormatVersion + 1).keys()].map(
+    (version) =>
+      PublicKey.findProgramAddressSync(
+        [
+          Buffer.from(LEAF_FORMAT_SEED),
+          new PublicKey(collectionId).toBuffer(),
+          Buffer.from([version]),
+        ],
+        tinySplProgram.programId
+      )[0]
+  );
+  const leafFormatAccounts =
+    await tinySplProgram.account.leafFormat.fetchMultiple(leafFormats);
+
+  // mints created before leaf formats existed have no version 0 record until
+  // someone pays for it. newer versions need it, so it is the only format
+  // their leaves can be in and the record is created in the same transaction
+  const initLeafFormatIxs: TransactionInstruction[] = [];
+  if (!leafFormatAccounts[0]) {
+    const [collectionMetadata] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("metadata"),
+        MPL_TOKEN_METADATA_PROGRAM_ID.toBuffer(),
+        new PublicKey(collectionId).toBuffer(),
+      ],
+      MPL_TOKEN_METADATA_PROGRAM_ID
+    );
+    initLeafFormatIxs.push(
+      await tinySplProgram.methods
+        .initLeafFormat()
+        .accounts({
+          payer,
+          collectionMint: collectionId,
+          collectionMetadata,
+          tinySplAuthority,
+          leafFormat: leafFormats[0],
+          systemProgram: SystemProgram.programId,
+        })
+        .instruction()
+    );
+  }
+
+  // leaves keep the format they were minted in, so the one whose name and
+  // uri reproduce the leaf is the one it verifies against
+  const getAssetLeafFormat = (asset: ReadApiAsset) => {
+    if (initLeafFormatIxs.length) {
+      return leafFormats[0];
+    }
+
+    const amount = getAssetAmount(asset);
+    const version = leafFormatAccounts.findIndex(
+      (leafFormatAccount) =>
+        !!leafFormatAccount &&
+        leafFormatAccount.symbol === asset.content.metadata?.symbol &&
+        getLeafName(leafFormatAccount, amount) ===
+          asset.content.metadata?.name &&
+        leafFormatAccount.uriTemplate
+          .replaceAll("{collection_mint}", collectionId)
+          .replaceAll("{amount}", amount)
+          .replaceAll("\0", "") === asset.content.json_uri
+    );
+    if (version === -1) {
+      throw new Error(`Leaf format of ${asset.id} not found`);
+    }
+
+    return leafFormats[version];
+  };
+
+  return {
+    leafFormat: leafFormats[leafFormatVersion],
+    getAssetLeafFormat,
+    initLeafFormatIxs,
+  };
+};
@@ -83,7 +83,7 @@ const idl = {
         },
         {
           name: "authority",
-          isMut: false,
+          isMut: true,
           isSigner: true,
         },
       ],
@@ -91,6 +91,31 @@ const idl = {
     },
     {
       name: "createMint",
+      docs: [
+        "Creates a tiny spl mint.",
+        "",
+        "Leaves are always bubblegum v1 leaves in a token metadata collection.",
+        "Bubblegum v2 leaves in mpl core collections need a newer solana sdk",
+        "than anchor 0.29 builds against, so mints cannot choose them until the",
+        "program moves to a newer toolchain.",
+        "",
+        "Mints cannot freeze individual leaves. Bubblegum v1 has no frozen",
+        "leaves, so a freeze could only be checked by tiny spl and the owner",
+        "could still move the leaf with a direct bubblegum `transfer`.",
+        "",
+        "Mints cannot have a permanent delegate that claws leaves back.",
+        "Bubblegum v1 leaves have no permanent delegate, and owners can clear",
+        "any delegation tiny spl sets with a direct bubblegum `transfer` or",
+        "`delegate`, so tiny spl could not guarantee that a leaf can be seized.",
+        "",
+        "Leaf metadata inputs are not snapshotted into the tiny spl authority",
+        "for zero-copy reads. They live in versioned `LeafFormat` accounts, and",
+        "leaves minted in older versions need those to verify, so a snapshot",
+        "could only stand in for the current format and would have to be kept",
+        "in sync with it. Symbols, uri templates and creators have no fixed size",
+        "for a zero-copy layout either, and the token metadata read it was meant",
+        "to save is already gone from `mint_to`, `split` and `combine`.",
+      ],
       accounts: [
         {
           name: "metadata",
@@ -117,6 +142,11 @@ const idl = {
           isMut: true,
           isSigner: false,
         },
+        {
+          name: "leafFormat",
+          isMut: true,
+          isSigner: false,
+        },
         {
           name: "systemProgram",
           isMut: false,
@@ -145,7 +175,61 @@ const idl = {
             defined: "CreateMintMetadata",
           },
         },
+        {
+          name: "complianceConfig",
+          type: {
+            option: {
+              defined: "ComplianceConfig",
+            },
+          },
+        },
+      ],
+    },
+    {
+      name: "adoptCollection",
+      accounts: [
+        {
+          name: "updateAuthority",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "collectionMint",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "collectionMetadata",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "collectionAuthorityRecord",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "tinySplAuthority",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "leafFormat",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "tokenMetadataProgram",
+          isMut: false,
+          isSigner: false,
+        },
       ],
+      args: [],
     },
     {
       name: "mintTo",
@@ -160,6 +244,24 @@ const idl = {
           isMut: false,
           isSigner: false,
         },
+        {
+          name: "newLeafDelegate",
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: "newLeafOwnerAllowedWallet",
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: "newLeafOwnerDeniedWallet",
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
         {
           name: "merkleTree",
           isMut: true,
@@ -195,11 +297,22 @@ const idl = {
           isMut: false,
           isSigner: false,
         },
+        {
+          name: "collectionAuthorityRecord",
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
         {
           name: "tinySplAuthority",
           isMut: true,
           isSigner: false,
         },
+        {
+          name: "leafFormat",
+          isMut: false,
+          isSigner: false,
+        },
         {
           name: "logWrapper",
           isMut: false,
@@ -240,33 +353,71 @@ const idl = {
       ],
     },
     {
-      name: "split",
+      name: "updateCollectionMetadata",
       accounts: [
         {
-          name: "authority",
+          name: "metadataAuthority",
           isMut: true,
           isSigner: true,
         },
         {
-          name: "treeCreatorOrDelegate",
+          name: "collectionMint",
           isMut: false,
           isSigner: false,
         },
         {
-          name: "leafOwner",
+          name: "collectionMetadata",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "editionAccount",
           isMut: false,
           isSigner: false,
         },
         {
-          name: "leafDelegate",
+          name: "tinySplAuthority",
           isMut: false,
           isSigner: false,
         },
         {
-          name: "newLeafOwner",
+          name: "leafFormat",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "sysvarInstructions",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "tokenMetadataProgram",
           isMut: false,
           isSigner: false,
         },
+      ],
+      args: [
+        {
+          name: "updateCollectionMetadataArgs",
+          type: {
+            defined: "UpdateCollectionMetadataArgs",
+          },
+        },
+      ],
+    },
+    {
+      name: "lockCollectionMetadata",
+      accounts: [
+        {
+          name: "metadataAuthority",
+          isMut: true,
+          isSigner: true,
+        },
         {
           name: "collectionMint",
           isMut: false,
@@ -283,84 +434,149 @@ const idl = {
           isSigner: false,
         },
         {
-          name: "bubblegumSigner",
+          name: "tinySplAuthority",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "systemProgram",
           isMut: false,
           isSigner: false,
         },
         {
-          name: "tinySplAuthority",
+          name: "sysvarInstructions",
           isMut: false,
           isSigner: false,
         },
         {
-          name: "treeAuthority",
-          isMut: true,
+          name: "tokenMetadataProgram",
+          isMut: false,
           isSigner: false,
         },
+      ],
+      args: [],
+    },
+    {
+      name: "transferMetadataAuthority",
+      accounts: [
         {
-          name: "merkleTree",
+          name: "metadataAuthority",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "collectionMint",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "tinySplAuthority",
           isMut: true,
           isSigner: false,
         },
+      ],
+      args: [
         {
-          name: "logWrapper",
+          name: "newMetadataAuthority",
+          type: {
+            option: "publicKey",
+          },
+        },
+      ],
+    },
+    {
+      name: "initLeafFormat",
+      accounts: [
+        {
+          name: "payer",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "collectionMint",
           isMut: false,
           isSigner: false,
         },
         {
-          name: "compressionProgram",
+          name: "collectionMetadata",
           isMut: false,
           isSigner: false,
         },
         {
-          name: "tokenMetadataProgram",
+          name: "tinySplAuthority",
           isMut: false,
           isSigner: false,
         },
+        {
+          name: "leafFormat",
+          isMut: true,
+          isSigner: false,
+        },
         {
           name: "systemProgram",
           isMut: false,
           isSigner: false,
         },
+      ],
+      args: [],
+    },
+    {
+      name: "setLeafFormat",
+      accounts: [
         {
-          name: "mplBubblegumProgram",
+          name: "metadataAuthority",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "collectionMint",
           isMut: false,
           isSigner: false,
         },
-      ],
-      args: [
         {
-          name: "sourceAmount",
-          type: "u64",
+          name: "tinySplAuthority",
+          isMut: true,
+          isSigner: false,
         },
         {
-          name: "assetId",
-          type: "publicKey",
+          name: "currentLeafFormat",
+          isMut: false,
+          isSigner: false,
         },
         {
-          name: "root",
-          type: {
-            array: ["u8", 32],
-          },
+          name: "leafFormat",
+          isMut: true,
+          isSigner: false,
         },
         {
-          name: "nonce",
-          type: "u64",
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
         },
+      ],
+      args: [
         {
-          name: "index",
-          type: "u32",
+          name: "version",
+          type: "u8",
         },
         {
-          name: "amounts",
+          name: "symbol",
+          type: "string",
+        },
+        {
+          name: "uriTemplate",
+          type: "string",
+        },
+        {
+          name: "nameStyle",
           type: {
-            vec: "u64",
+            defined: "LeafNameStyle",
           },
         },
       ],
     },
     {
-      name: "combine",
+      name: "migrateLeaf",
       accounts: [
         {
           name: "authority",
@@ -382,11 +598,6 @@ const idl = {
           isMut: false,
           isSigner: false,
         },
-        {
-          name: "newLeafOwner",
-          isMut: false,
-          isSigner: false,
-        },
         {
           name: "collectionMint",
           isMut: false,
@@ -407,18 +618,44 @@ const idl = {
           isMut: false,
           isSigner: false,
         },
+        {
+          name: "collectionAuthorityRecord",
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
         {
           name: "tinySplAuthority",
           isMut: false,
           isSigner: false,
         },
         {
-          name: "treeAuthority",
+          name: "sourceLeafFormat",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "leafFormat",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "sourceTreeAuthority",
           isMut: true,
           isSigner: false,
         },
         {
-          name: "merkleTree",
+          name: "destinationTreeAuthority",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "sourceMerkleTree",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "destinationMerkleTree",
           isMut: true,
           isSigner: false,
         },
@@ -450,273 +687,3676 @@ const idl = {
       ],
       args: [
         {
-          name: "amounts",
-          type: {
-            vec: "u64",
-          },
+          name: "amount",
+          type: "u64",
         },
         {
-          name: "assetIds",
-          type: {
-            vec: "publicKey",
-          },
+          name: "assetId",
+          type: "publicKey",
         },
         {
-          name: "roots",
+          name: "root",
           type: {
-            vec: {
-              array: ["u8", 32],
-            },
+            array: ["u8", 32],
           },
         },
         {
-          name: "nonces",
-          type: {
-            vec: "u64",
-          },
-        },
-        {
-          name: "indexes",
-          type: {
-            vec: "u32",
-          },
+          name: "nonce",
+          type: "u64",
         },
         {
-          name: "proofPathEndIndexesExclusive",
-          type: {
-            vec: "u32",
-          },
+          name: "index",
+          type: "u32",
         },
       ],
     },
-  ],
-  accounts: [
-    {
-      name: "LoggingMetadata",
-      type: {
-        kind: "struct",
-        fields: [
-          {
-            name: "authority",
-            type: "publicKey",
-          },
-        ],
-      },
-    },
     {
-      name: "TinySplAuthority",
-      type: {
-        kind: "struct",
-        fields: [
-          {
-            name: "isVerifiedTinySplMint",
-            type: "bool",
-          },
-          {
-            name: "currentSupply",
-            type: "u64",
-          },
-          {
-            name: "mintAuthority",
-            type: {
-              option: "publicKey",
-            },
-          },
-        ],
-      },
+      name: "createTwinMint",
+      accounts: [
+        {
+          name: "mintAuthority",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "collectionMint",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "tinySplAuthority",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "twinMint",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "tokenProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [],
     },
-  ],
-  types: [
     {
-      name: "CreateMintMetadata",
-      type: {
-        kind: "struct",
-        fields: [
-          {
-            name: "name",
-            type: "string",
-          },
-          {
-            name: "symbol",
-            type: "string",
-          },
-          {
-            name: "uri",
-            type: "string",
+      name: "decompress",
+      accounts: [
+        {
+          name: "authority",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "leafOwner",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "leafDelegate",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "collectionMint",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "tinySplAuthority",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "sourceLeafFormat",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "sourceTreeAuthority",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "sourceMerkleTree",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "twinMint",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "destinationTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "tokenProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "logWrapper",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "compressionProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "mplBubblegumProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "amount",
+          type: "u64",
+        },
+        {
+          name: "assetId",
+          type: "publicKey",
+        },
+        {
+          name: "root",
+          type: {
+            array: ["u8", 32],
           },
-        ],
-      },
-    },
-  ],
-  errors: [
-    {
-      code: 6000,
-      name: "MintAccountNotEmpty",
-      msg: "Mint account is not empty",
-    },
-    {
-      code: 6001,
-      name: "MetadataAccountNotEmpty",
-      msg: "Metadata account is not empty",
-    },
-    {
-      code: 6002,
-      name: "MasterEditionAccountNotEmpty",
-      msg: "Master edition account is not empty",
-    },
-    {
-      code: 6003,
-      name: "LeafAuthorityMustSign",
-      msg: "Leaf authority must sign",
-    },
-    {
-      code: 6004,
-      name: "CollectionMismatch",
-      msg: "Passed in collection mint does not match the collection mint of token",
-    },
-    {
-      code: 6005,
-      name: "AssetIdMismatch",
-      msg: "Passed in asset id does not match the asset id derived from the merkle tree and index",
-    },
-    {
-      code: 6006,
-      name: "InvalidSplitAmounts",
-      msg: "Invalid split amounts supplied",
-    },
-    {
-      code: 6007,
-      name: "CannotCombineSameAsset",
-      msg: "Cannot combine more than 1 of the same asset",
-    },
-    {
-      code: 6008,
-      name: "InvalidCombineParameters",
-      msg: "Different number of parameters supplied for combining",
-    },
-    {
-      code: 6009,
-      name: "ExceededMaxMintSupply",
-      msg: "Exceeded max mint supply",
+        },
+        {
+          name: "nonce",
+          type: "u64",
+        },
+        {
+          name: "index",
+          type: "u32",
+        },
+      ],
     },
-  ],
-  metadata: {
-    address: "tsP1jf31M3iGNPmANP3ep3iWCMTxpMFLNbewWVWWbSo",
-  },
-};
-
-type TinySpl = {
-  version: "0.1.0";
-  name: "tiny_spl";
-  instructions: [
     {
-      name: "initMetadataAccount";
+      name: "compress",
       accounts: [
         {
-          name: "metadata";
+          name: "owner",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "sourceTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "twinMint",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "treeAuthority",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "newLeafOwner",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "newLeafDelegate",
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: "merkleTree",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "treeCreatorOrDelegate",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "collectionMint",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "collectionMetadata",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "editionAccount",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "bubblegumSigner",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "collectionAuthorityRecord",
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: "tinySplAuthority",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "leafFormat",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "tokenProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "logWrapper",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "compressionProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "tokenMetadataProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "mplBubblegumProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "amount",
+          type: "u64",
+        },
+      ],
+    },
+    {
+      name: "openCombineSession",
+      accounts: [
+        {
+          name: "owner",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "collectionMint",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "tinySplAuthority",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "combineSession",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [],
+    },
+    {
+      name: "absorb",
+      accounts: [
+        {
+          name: "owner",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "leafDelegate",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "ownerDeniedWallet",
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: "collectionMint",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "tinySplAuthority",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "sourceLeafFormat",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "combineSession",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "logWrapper",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "compressionProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "mplBubblegumProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "leafBatch",
+          type: {
+            defined: "LeafBatch",
+          },
+        },
+      ],
+    },
+    {
+      name: "closeCombineSession",
+      accounts: [
+        {
+          name: "authority",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "owner",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "newLeafOwner",
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: "newLeafOwnerAllowedWallet",
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: "newLeafOwnerDeniedWallet",
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: "combineSession",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "treeAuthority",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "merkleTree",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "treeCreatorOrDelegate",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "collectionMint",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "collectionMetadata",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "editionAccount",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "bubblegumSigner",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "collectionAuthorityRecord",
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: "tinySplAuthority",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "leafFormat",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "logWrapper",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "compressionProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "tokenMetadataProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "mplBubblegumProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [],
+    },
+    {
+      name: "openDistributionSession",
+      accounts: [
+        {
+          name: "owner",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "collectionMint",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "tinySplAuthority",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "distributionSession",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [],
+    },
+    {
+      name: "fundDistributionSession",
+      accounts: [
+        {
+          name: "owner",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "leafDelegate",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "ownerDeniedWallet",
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: "collectionMint",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "tinySplAuthority",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "sourceLeafFormat",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "distributionSession",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "logWrapper",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "compressionProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "mplBubblegumProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "leafBatch",
+          type: {
+            defined: "LeafBatch",
+          },
+        },
+      ],
+    },
+    {
+      name: "distribute",
+      accounts: [
+        {
+          name: "owner",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "ownerDeniedWallet",
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: "distributionSession",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "treeAuthority",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "merkleTree",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "treeCreatorOrDelegate",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "collectionMint",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "collectionMetadata",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "editionAccount",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "bubblegumSigner",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "collectionAuthorityRecord",
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: "tinySplAuthority",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "leafFormat",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "transferHook",
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: "transferHookProgram",
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: "logWrapper",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "compressionProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "tokenMetadataProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "mplBubblegumProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "amounts",
+          type: {
+            vec: "u64",
+          },
+        },
+      ],
+    },
+    {
+      name: "closeDistributionSession",
+      accounts: [
+        {
+          name: "owner",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "ownerAllowedWallet",
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: "ownerDeniedWallet",
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: "distributionSession",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "treeAuthority",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "merkleTree",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "treeCreatorOrDelegate",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "collectionMint",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "collectionMetadata",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "editionAccount",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "bubblegumSigner",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "collectionAuthorityRecord",
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: "tinySplAuthority",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "leafFormat",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "logWrapper",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "compressionProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "tokenMetadataProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "mplBubblegumProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [],
+    },
+    {
+      name: "allowWallet",
+      accounts: [
+        {
+          name: "complianceAuthority",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "collectionMint",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "tinySplAuthority",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "allowedWallet",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "wallet",
+          type: "publicKey",
+        },
+      ],
+    },
+    {
+      name: "disallowWallet",
+      accounts: [
+        {
+          name: "complianceAuthority",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "collectionMint",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "tinySplAuthority",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "allowedWallet",
+          isMut: true,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "wallet",
+          type: "publicKey",
+        },
+      ],
+    },
+    {
+      name: "denyWallet",
+      accounts: [
+        {
+          name: "complianceAuthority",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "collectionMint",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "tinySplAuthority",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "deniedWallet",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "wallet",
+          type: "publicKey",
+        },
+      ],
+    },
+    {
+      name: "undenyWallet",
+      accounts: [
+        {
+          name: "complianceAuthority",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "collectionMint",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "tinySplAuthority",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "deniedWallet",
+          isMut: true,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "wallet",
+          type: "publicKey",
+        },
+      ],
+    },
+    {
+      name: "setTransferHook",
+      accounts: [
+        {
+          name: "mintAuthority",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "collectionMint",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "tinySplAuthority",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "transferHook",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "programId",
+          type: {
+            option: "publicKey",
+          },
+        },
+        {
+          name: "extraAccounts",
+          type: {
+            vec: {
+              defined: "TransferHookAccount",
+            },
+          },
+        },
+      ],
+    },
+    {
+      name: "split",
+      accounts: [
+        {
+          name: "authority",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "treeCreatorOrDelegate",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "leafOwner",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "leafDelegate",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "newLeafOwner",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "newLeafDelegate",
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: "newLeafOwnerAllowedWallet",
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: "authorityDeniedWallet",
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: "leafOwnerDeniedWallet",
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: "newLeafOwnerDeniedWallet",
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: "collectionMint",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "collectionMetadata",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "editionAccount",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "bubblegumSigner",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "collectionAuthorityRecord",
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: "tinySplAuthority",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "sourceLeafFormat",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "leafFormat",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "sourceTreeAuthority",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "destinationTreeAuthority",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "sourceMerkleTree",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "destinationMerkleTree",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "transferHook",
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: "transferHookProgram",
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: "logWrapper",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "compressionProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "tokenMetadataProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "mplBubblegumProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "sourceAmount",
+          type: "u64",
+        },
+        {
+          name: "assetId",
+          type: "publicKey",
+        },
+        {
+          name: "root",
+          type: {
+            array: ["u8", 32],
+          },
+        },
+        {
+          name: "nonce",
+          type: "u64",
+        },
+        {
+          name: "index",
+          type: "u32",
+        },
+        {
+          name: "outputs",
+          type: {
+            defined: "SplitOutputs",
+          },
+        },
+      ],
+    },
+    {
+      name: "combine",
+      accounts: [
+        {
+          name: "authority",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "treeCreatorOrDelegate",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "leafOwner",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "leafDelegate",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "newLeafOwner",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "newLeafDelegate",
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: "newLeafOwnerAllowedWallet",
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: "authorityDeniedWallet",
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: "leafOwnerDeniedWallet",
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: "newLeafOwnerDeniedWallet",
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: "collectionMint",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "collectionMetadata",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "editionAccount",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "bubblegumSigner",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "collectionAuthorityRecord",
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: "tinySplAuthority",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "sourceLeafFormat",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "leafFormat",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "destinationTreeAuthority",
+          isMut: true,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: "destinationMerkleTree",
+          isMut: true,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: "transferHook",
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: "transferHookProgram",
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: "logWrapper",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "compressionProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "tokenMetadataProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "mplBubblegumProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "leafBatch",
+          type: {
+            defined: "LeafBatch",
+          },
+        },
+        {
+          name: "destinationTreeIndex",
+          type: {
+            option: "u8",
+          },
+        },
+        {
+          name: "preserveDelegate",
+          type: "bool",
+        },
+      ],
+    },
+    {
+      name: "migrateAccount",
+      docs: [
+        "Upgrades any tiny spl account to the current layout of its type.",
+        "",
+        "Mints created before collection metadata could be governed have no",
+        "metadata authority. Upgrading them makes their mint authority the",
+        "metadata authority, so they can use `update_collection_metadata`,",
+        "`lock_collection_metadata` and `set_leaf_format`.",
+        "",
+        "Logging metadata accounts created before layouts were versioned can",
+        "still be uploaded to, logged and closed, and are read at their old",
+        "buffer offset until they are upgraded.",
+      ],
+      accounts: [
+        {
+          name: "payer",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "account",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [],
+    },
+  ],
+  accounts: [
+    {
+      name: "AllowedWallet",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "collectionMint",
+            type: "publicKey",
+          },
+          {
+            name: "wallet",
+            type: "publicKey",
+          },
+          {
+            name: "layoutVersion",
+            type: "u8",
+          },
+        ],
+      },
+    },
+    {
+      name: "CombineSession",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "collectionMint",
+            type: "publicKey",
+          },
+          {
+            name: "owner",
+            type: "publicKey",
+          },
+          {
+            name: "totalAmount",
+            type: "u64",
+          },
+          {
+            name: "openedAt",
+            type: "i64",
+          },
+          {
+            name: "layoutVersion",
+            type: "u8",
+          },
+        ],
+      },
+    },
+    {
+      name: "DeniedWallet",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "collectionMint",
+            type: "publicKey",
+          },
+          {
+            name: "wallet",
+            type: "publicKey",
+          },
+          {
+            name: "layoutVersion",
+            type: "u8",
+          },
+        ],
+      },
+    },
+    {
+      name: "DistributionSession",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "collectionMint",
+            type: "publicKey",
+          },
+          {
+            name: "owner",
+            type: "publicKey",
+          },
+          {
+            name: "remainingAmount",
+            type: "u64",
+          },
+          {
+            name: "layoutVersion",
+            type: "u8",
+          },
+        ],
+      },
+    },
+    {
+      name: "LeafFormat",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "collectionMint",
+            type: "publicKey",
+          },
+          {
+            name: "version",
+            type: "u8",
+          },
+          {
+            name: "symbol",
+            type: "string",
+          },
+          {
+            name: "uriTemplate",
+            type: "string",
+          },
+          {
+            name: "nameStyle",
+            type: {
+              defined: "LeafNameStyle",
+            },
+          },
+          {
+            name: "sellerFeeBasisPoints",
+            type: "u16",
+          },
+          {
+            name: "primarySaleHappened",
+            type: "bool",
+          },
+          {
+            name: "isMutable",
+            type: "bool",
+          },
+          {
+            name: "additionalCreators",
+            type: {
+              vec: {
+                defined: "AdditionalCreator",
+              },
+            },
+          },
+          {
+            name: "layoutVersion",
+            type: "u8",
+          },
+        ],
+      },
+    },
+    {
+      name: "VersionedLoggingMetadata",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "authority",
+            type: "publicKey",
+          },
+          {
+            name: "layoutVersion",
+            type: "u8",
+          },
+        ],
+      },
+    },
+    {
+      name: "LoggingMetadata",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "authority",
+            type: "publicKey",
+          },
+        ],
+      },
+    },
+    {
+      name: "TinySplAuthority",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "isVerifiedTinySplMint",
+            type: "bool",
+          },
+          {
+            name: "currentSupply",
+            type: "u64",
+          },
+          {
+            name: "mintAuthority",
+            type: {
+              option: "publicKey",
+            },
+          },
+          {
+            name: "metadataAuthority",
+            type: {
+              option: "publicKey",
+            },
+          },
+          {
+            name: "leafFormatVersion",
+            type: "u8",
+          },
+          {
+            name: "collectionAuthorityRecord",
+            type: {
+              option: "publicKey",
+            },
+          },
+          {
+            name: "twinMint",
+            type: {
+              option: "publicKey",
+            },
+          },
+          {
+            name: "transferHookProgram",
+            type: {
+              option: "publicKey",
+            },
+          },
+          {
+            name: "complianceAuthority",
+            type: {
+              option: "publicKey",
+            },
+          },
+          {
+            name: "isAllowlistEnabled",
+            type: "bool",
+          },
+          {
+            name: "isDenylistEnabled",
+            type: "bool",
+          },
+          {
+            name: "layoutVersion",
+            type: "u8",
+          },
+        ],
+      },
+    },
+    {
+      name: "TransferHook",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "collectionMint",
+            type: "publicKey",
+          },
+          {
+            name: "programId",
+            type: "publicKey",
+          },
+          {
+            name: "extraAccounts",
+            type: {
+              vec: {
+                defined: "TransferHookAccount",
+              },
+            },
+          },
+          {
+            name: "layoutVersion",
+            type: "u8",
+          },
+        ],
+      },
+    },
+  ],
+  types: [
+    {
+      name: "ComplianceConfig",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "authority",
+            type: "publicKey",
+          },
+          {
+            name: "isAllowlistEnabled",
+            type: "bool",
+          },
+          {
+            name: "isDenylistEnabled",
+            type: "bool",
+          },
+        ],
+      },
+    },
+    {
+      name: "CreateMintMetadata",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "name",
+            type: "string",
+          },
+          {
+            name: "symbol",
+            type: "string",
+          },
+          {
+            name: "uri",
+            type: "string",
+          },
+          {
+            name: "sellerFeeBasisPoints",
+            type: "u16",
+          },
+          {
+            name: "primarySaleHappened",
+            type: "bool",
+          },
+          {
+            name: "isMutable",
+            type: "bool",
+          },
+          {
+            name: "additionalCreators",
+            type: {
+              vec: {
+                defined: "AdditionalCreator",
+              },
+            },
+          },
+        ],
+      },
+    },
+    {
+      name: "SplitOutputs",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "amounts",
+            type: {
+              vec: "u64",
+            },
+          },
+          {
+            name: "treeIndexes",
+            type: "bytes",
+          },
+          {
+            name: "extraTreeCount",
+            type: "u8",
+          },
+          {
+            name: "preserveDelegate",
+            type: "bool",
+          },
+        ],
+      },
+    },
+    {
+      name: "UpdateCollectionMetadataArgs",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "name",
+            type: {
+              option: "string",
+            },
+          },
+          {
+            name: "symbol",
+            type: {
+              option: "string",
+            },
+          },
+          {
+            name: "uri",
+            type: {
+              option: "string",
+            },
+          },
+        ],
+      },
+    },
+    {
+      name: "AdditionalCreator",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "address",
+            type: "publicKey",
+          },
+          {
+            name: "share",
+            type: "u8",
+          },
+        ],
+      },
+    },
+    {
+      name: "TransferHookAccount",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "pubkey",
+            type: "publicKey",
+          },
+          {
+            name: "isWritable",
+            type: "bool",
+          },
+        ],
+      },
+    },
+    {
+      name: "LeafInput",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "amount",
+            type: "u64",
+          },
+          {
+            name: "assetId",
+            type: "publicKey",
+          },
+          {
+            name: "root",
+            type: {
+              array: ["u8", 32],
+            },
+          },
+          {
+            name: "nonce",
+            type: "u64",
+          },
+          {
+            name: "index",
+            type: "u32",
+          },
+          {
+            name: "treeIndex",
+            type: "u8",
+          },
+          {
+            name: "leafFormatIndex",
+            type: "u8",
+          },
+          {
+            name: "proofLength",
+            type: "u8",
+          },
+        ],
+      },
+    },
+    {
+      name: "LeafBatch",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "inputs",
+            type: {
+              vec: {
+                defined: "LeafInput",
+              },
+            },
+          },
+          {
+            name: "treeCount",
+            type: "u8",
+          },
+          {
+            name: "leafFormatCount",
+            type: "u8",
+          },
+        ],
+      },
+    },
+    {
+      name: "TransferHookDestination",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "owner",
+            type: "publicKey",
+          },
+          {
+            name: "amount",
+            type: "u64",
+          },
+        ],
+      },
+    },
+    {
+      name: "TransferHookArgs",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "sourceOwner",
+            type: "publicKey",
+          },
+          {
+            name: "destinations",
+            type: {
+              vec: {
+                defined: "TransferHookDestination",
+              },
+            },
+          },
+        ],
+      },
+    },
+    {
+      name: "LeafNameStyle",
+      type: {
+        kind: "enum",
+        variants: [
+          {
+            name: "Grouped",
+          },
+          {
+            name: "Compact",
+          },
+        ],
+      },
+    },
+  ],
+  errors: [
+    {
+      code: 6000,
+      name: "MintAccountNotEmpty",
+      msg: "Mint account is not empty",
+    },
+    {
+      code: 6001,
+      name: "MetadataAccountNotEmpty",
+      msg: "Metadata account is not empty",
+    },
+    {
+      code: 6002,
+      name: "MasterEditionAccountNotEmpty",
+      msg: "Master edition account is not empty",
+    },
+    {
+      code: 6003,
+      name: "LeafAuthorityMustSign",
+      msg: "Leaf authority must sign",
+    },
+    {
+      code: 6004,
+      name: "CollectionMismatch",
+      msg: "Passed in collection mint does not match the collection mint of token",
+    },
+    {
+      code: 6005,
+      name: "AssetIdMismatch",
+      msg: "Passed in asset id does not match the asset id derived from the merkle tree and index",
+    },
+    {
+      code: 6006,
+      name: "InvalidSplitAmounts",
+      msg: "Invalid split amounts supplied",
+    },
+    {
+      code: 6007,
+      name: "CannotCombineSameAsset",
+      msg: "Cannot combine more than 1 of the same asset",
+    },
+    {
+      code: 6008,
+      name: "InvalidCombineParameters",
+      msg: "Different number of parameters supplied for combining",
+    },
+    {
+      code: 6009,
+      name: "ExceededMaxMintSupply",
+      msg: "Exceeded max mint supply",
+    },
+    {
+      code: 6010,
+      name: "ConflictingDelegateOptions",
+      msg: "Cannot both preserve the source delegate and set a new leaf delegate",
+    },
+    {
+      code: 6011,
+      name: "CannotPreserveDelegateOnTransfer",
+      msg: "Source delegate can only be preserved when the new leaf owner is the leaf owner",
+    },
+    {
+      code: 6012,
+      name: "TreeAuthorityMismatch",
+      msg: "Tree authority does not match the bubblegum tree config of its merkle tree",
+    },
+    {
+      code: 6013,
+      name: "InvalidTreeTable",
+      msg: "Tree table is missing merkle tree or tree authority accounts",
+    },
+    {
+      code: 6014,
+      name: "InvalidDestinationTreeIndex",
+      msg: "Destination tree index is out of range of the tree table",
+    },
+    {
+      code: 6015,
+      name: "InvalidLeafFormatVersion",
+      msg: "Leaf format version must follow the current leaf format version",
+    },
+    {
+      code: 6016,
+      name: "InvalidLeafFormat",
+      msg: "Leaf format symbol is too long or uri template cannot hold every amount",
+    },
+    {
+      code: 6017,
+      name: "LeafFormatAlreadyCurrent",
+      msg: "Leaf is already in the current leaf format",
+    },
+    {
+      code: 6018,
+      name: "InvalidRoyaltyConfig",
+      msg: "Invalid seller fee basis points or creator shares supplied",
+    },
+    {
+      code: 6019,
+      name: "TwinMintNotAllowed",
+      msg: "Twin mint cannot be created for this mint",
+    },
+    {
+      code: 6020,
+      name: "MissingTransferHookAccounts",
+      msg: "Transfer hook accounts are missing or do not match the registered hook",
+    },
+    {
+      code: 6021,
+      name: "TooManyTransferHookAccounts",
+      msg: "Transfer hook has too many extra accounts",
+    },
+    {
+      code: 6022,
+      name: "WalletNotAllowed",
+      msg: "Wallet is not on the allowlist of this mint",
+    },
+    {
+      code: 6023,
+      name: "WalletDenied",
+      msg: "Wallet is on the denylist of this mint",
+    },
+    {
+      code: 6024,
+      name: "MissingDeniedWalletAccount",
+      msg: "Denied wallet account is missing or invalid",
+    },
+    {
+      code: 6025,
+      name: "CombineSessionNotExpired",
+      msg: "Combine session can only be closed by its owner until it expires",
+    },
+    {
+      code: 6026,
+      name: "InvalidDistributionAccounts",
+      msg: "Recipient accounts do not match the distribution amounts",
+    },
+    {
+      code: 6027,
+      name: "ArithmeticOverflow",
+      msg: "Amount overflowed",
+    },
+    {
+      code: 6028,
+      name: "MissingRemainingAccounts",
+      msg: "Remaining accounts are missing",
+    },
+    {
+      code: 6029,
+      name: "UploadOutOfBounds",
+      msg: "Upload does not fit in the logging metadata account",
+    },
+    {
+      code: 6030,
+      name: "InvalidProofLayout",
+      msg: "Proof accounts do not match the proof lengths of the leaves",
+    },
+    {
+      code: 6031,
+      name: "InvalidCollectionMetadata",
+      msg: "Collection metadata is not the metadata account of the collection mint",
+    },
+    {
+      code: 6032,
+      name: "InvalidCollectionEdition",
+      msg: "Edition account is not the master edition of the collection mint",
+    },
+    {
+      code: 6033,
+      name: "InvalidBubblegumSigner",
+      msg: "Bubblegum signer is not the collection cpi signer of bubblegum",
+    },
+    {
+      code: 6034,
+      name: "InvalidMerkleTree",
+      msg: "Merkle tree is not owned by the account compression program",
+    },
+    {
+      code: 6035,
+      name: "UnsupportedAccountType",
+      msg: "Account is not a tiny spl account that can be migrated",
+    },
+    {
+      code: 6036,
+      name: "AccountLayoutAlreadyCurrent",
+      msg: "Account is already in the current layout",
+    },
+    {
+      code: 6037,
+      name: "InvalidMetadataPointer",
+      msg: "Token 2022 mint has no metadata pointer to the collection metadata",
+    },
+    {
+      code: 6038,
+      name: "TransferHookNotAllowed",
+      msg: "Transfer hook cannot be set on a mint with a twin mint",
+    },
+    {
+      code: 6039,
+      name: "InvalidDestinationTree",
+      msg: "Pass either a destination tree index or the destination tree accounts",
+    },
+    {
+      code: 6040,
+      name: "UnsizedCollection",
+      msg: "Only sized collections can be adopted",
+    },
+    {
+      code: 6041,
+      name: "CollectionMetadataNotGoverned",
+      msg: "Adopted collections keep their metadata under their update authority",
+    },
+    {
+      code: 6042,
+      name: "InvalidLeafFormatTable",
+      msg: "Leaf format table does not match the leaf batch",
+    },
+    {
+      code: 6043,
+      name: "CombineSessionRecipientNotAllowed",
+      msg: "Only the mint or compliance authority can mint an expired combine session to another wallet",
+    },
+  ],
+  metadata: {
+    address: "tsP1jf31M3iGNPmANP3ep3iWCMTxpMFLNbewWVWWbSo",
+  },
+};
+
+type TinySpl = {
+  version: "0.1.0";
+  name: "tiny_spl";
+  instructions: [
+    {
+      name: "initMetadataAccount";
+      accounts: [
+        {
+          name: "metadata";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "authority";
+          isMut: false;
+          isSigner: true;
+        },
+      ];
+      args: [
+        {
+          name: "totalMetadataBytes";
+          type: "u32";
+        },
+      ];
+    },
+    {
+      name: "uploadLoggingMetadata";
+      accounts: [
+        {
+          name: "metadata";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "authority";
+          isMut: false;
+          isSigner: true;
+        },
+      ];
+      args: [
+        {
+          name: "index";
+          type: "u32";
+        },
+        {
+          name: "bytes";
+          type: "bytes";
+        },
+      ];
+    },
+    {
+      name: "logMetadata";
+      accounts: [
+        {
+          name: "metadata";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "authority";
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: "noopProgram";
+          isMut: false;
+          isSigner: false;
+        },
+      ];
+      args: [];
+    },
+    {
+      name: "closeMetadataAccount";
+      accounts: [
+        {
+          name: "metadata";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "authority";
+          isMut: true;
+          isSigner: true;
+        },
+      ];
+      args: [];
+    },
+    {
+      name: "createMint";
+      docs: [
+        "Creates a tiny spl mint.",
+        "",
+        "Leaves are always bubblegum v1 leaves in a token metadata collection.",
+        "Bubblegum v2 leaves in mpl core collections need a newer solana sdk",
+        "than anchor 0.29 builds against, so mints cannot choose them until the",
+        "program moves to a newer toolchain.",
+        "",
+        "Mints cannot freeze individual leaves. Bubblegum v1 has no frozen",
+        "leaves, so a freeze could only be checked by tiny spl and the owner",
+        "could still move the leaf with a direct bubblegum `transfer`.",
+        "",
+        "Mints cannot have a permanent delegate that claws leaves back.",
+        "Bubblegum v1 leaves have no permanent delegate, and owners can clear",
+        "any delegation tiny spl sets with a direct bubblegum `transfer` or",
+        "`delegate`, so tiny spl could not guarantee that a leaf can be seized.",
+        "",
+        "Leaf metadata inputs are not snapshotted into the tiny spl authority",
+        "for zero-copy reads. They live in versioned `LeafFormat` accounts, and",
+        "leaves minted in older versions need those to verify, so a snapshot",
+        "could only stand in for the current format and would have to be kept",
+        "in sync with it. Symbols, uri templates and creators have no fixed size",
+        "for a zero-copy layout either, and the token metadata read it was meant",
+        "to save is already gone from `mint_to`, `split` and `combine`.",
+      ];
+      accounts: [
+        {
+          name: "metadata";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "masterEdition";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "mint";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "mintAuthority";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "tinySplAuthority";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "leafFormat";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "sysvarInstructions";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "splTokenProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "mplTokenMetadataProgram";
+          isMut: false;
+          isSigner: false;
+        },
+      ];
+      args: [
+        {
+          name: "createMintMetadata";
+          type: {
+            defined: "CreateMintMetadata";
+          };
+        },
+        {
+          name: "complianceConfig";
+          type: {
+            option: {
+              defined: "ComplianceConfig";
+            };
+          };
+        },
+      ];
+    },
+    {
+      name: "adoptCollection";
+      accounts: [
+        {
+          name: "updateAuthority";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "collectionMint";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "collectionMetadata";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "collectionAuthorityRecord";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "tinySplAuthority";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "leafFormat";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "tokenMetadataProgram";
+          isMut: false;
+          isSigner: false;
+        },
+      ];
+      args: [];
+    },
+    {
+      name: "mintTo";
+      accounts: [
+        {
+          name: "treeAuthority";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "newLeafOwner";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "newLeafDelegate";
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
+        },
+        {
+          name: "newLeafOwnerAllowedWallet";
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
+        },
+        {
+          name: "newLeafOwnerDeniedWallet";
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
+        },
+        {
+          name: "merkleTree";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "mintAuthority";
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: "treeCreatorOrDelegate";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "collectionMint";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "collectionMetadata";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "editionAccount";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "bubblegumSigner";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "collectionAuthorityRecord";
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
+        },
+        {
+          name: "tinySplAuthority";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "leafFormat";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "logWrapper";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "compressionProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "tokenMetadataProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "mplBubblegumProgram";
+          isMut: false;
+          isSigner: false;
+        },
+      ];
+      args: [
+        {
+          name: "amount";
+          type: "u64";
+        },
+        {
+          name: "maxSupply";
+          type: {
+            option: "u64";
+          };
+        },
+      ];
+    },
+    {
+      name: "updateCollectionMetadata";
+      accounts: [
+        {
+          name: "metadataAuthority";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "collectionMint";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "collectionMetadata";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "editionAccount";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "tinySplAuthority";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "leafFormat";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "sysvarInstructions";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "tokenMetadataProgram";
+          isMut: false;
+          isSigner: false;
+        },
+      ];
+      args: [
+        {
+          name: "updateCollectionMetadataArgs";
+          type: {
+            defined: "UpdateCollectionMetadataArgs";
+          };
+        },
+      ];
+    },
+    {
+      name: "lockCollectionMetadata";
+      accounts: [
+        {
+          name: "metadataAuthority";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "collectionMint";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "collectionMetadata";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "editionAccount";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "tinySplAuthority";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "sysvarInstructions";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "tokenMetadataProgram";
+          isMut: false;
+          isSigner: false;
+        },
+      ];
+      args: [];
+    },
+    {
+      name: "transferMetadataAuthority";
+      accounts: [
+        {
+          name: "metadataAuthority";
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: "collectionMint";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "tinySplAuthority";
+          isMut: true;
+          isSigner: false;
+        },
+      ];
+      args: [
+        {
+          name: "newMetadataAuthority";
+          type: {
+            option: "publicKey";
+          };
+        },
+      ];
+    },
+    {
+      name: "initLeafFormat";
+      accounts: [
+        {
+          name: "payer";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "collectionMint";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "collectionMetadata";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "tinySplAuthority";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "leafFormat";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        },
+      ];
+      args: [];
+    },
+    {
+      name: "setLeafFormat";
+      accounts: [
+        {
+          name: "metadataAuthority";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "collectionMint";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "tinySplAuthority";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "currentLeafFormat";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "leafFormat";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        },
+      ];
+      args: [
+        {
+          name: "version";
+          type: "u8";
+        },
+        {
+          name: "symbol";
+          type: "string";
+        },
+        {
+          name: "uriTemplate";
+          type: "string";
+        },
+        {
+          name: "nameStyle";
+          type: {
+            defined: "LeafNameStyle";
+          };
+        },
+      ];
+    },
+    {
+      name: "migrateLeaf";
+      accounts: [
+        {
+          name: "authority";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "treeCreatorOrDelegate";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "leafOwner";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "leafDelegate";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "collectionMint";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "collectionMetadata";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "editionAccount";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "bubblegumSigner";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "collectionAuthorityRecord";
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
+        },
+        {
+          name: "tinySplAuthority";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "sourceLeafFormat";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "leafFormat";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "sourceTreeAuthority";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "destinationTreeAuthority";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "sourceMerkleTree";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "destinationMerkleTree";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "logWrapper";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "compressionProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "tokenMetadataProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "mplBubblegumProgram";
+          isMut: false;
+          isSigner: false;
+        },
+      ];
+      args: [
+        {
+          name: "amount";
+          type: "u64";
+        },
+        {
+          name: "assetId";
+          type: "publicKey";
+        },
+        {
+          name: "root";
+          type: {
+            array: ["u8", 32];
+          };
+        },
+        {
+          name: "nonce";
+          type: "u64";
+        },
+        {
+          name: "index";
+          type: "u32";
+        },
+      ];
+    },
+    {
+      name: "createTwinMint";
+      accounts: [
+        {
+          name: "mintAuthority";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "collectionMint";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "tinySplAuthority";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "twinMint";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "tokenProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        },
+      ];
+      args: [];
+    },
+    {
+      name: "decompress";
+      accounts: [
+        {
+          name: "authority";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "leafOwner";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "leafDelegate";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "collectionMint";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "tinySplAuthority";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "sourceLeafFormat";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "sourceTreeAuthority";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "sourceMerkleTree";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "twinMint";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "destinationTokenAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "tokenProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "logWrapper";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "compressionProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "mplBubblegumProgram";
+          isMut: false;
+          isSigner: false;
+        },
+      ];
+      args: [
+        {
+          name: "amount";
+          type: "u64";
+        },
+        {
+          name: "assetId";
+          type: "publicKey";
+        },
+        {
+          name: "root";
+          type: {
+            array: ["u8", 32];
+          };
+        },
+        {
+          name: "nonce";
+          type: "u64";
+        },
+        {
+          name: "index";
+          type: "u32";
+        },
+      ];
+    },
+    {
+      name: "compress";
+      accounts: [
+        {
+          name: "owner";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "sourceTokenAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "twinMint";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "treeAuthority";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "newLeafOwner";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "newLeafDelegate";
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
+        },
+        {
+          name: "merkleTree";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "treeCreatorOrDelegate";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "collectionMint";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "collectionMetadata";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "editionAccount";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "bubblegumSigner";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "collectionAuthorityRecord";
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
+        },
+        {
+          name: "tinySplAuthority";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "leafFormat";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "tokenProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "logWrapper";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "compressionProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "tokenMetadataProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "mplBubblegumProgram";
+          isMut: false;
+          isSigner: false;
+        },
+      ];
+      args: [
+        {
+          name: "amount";
+          type: "u64";
+        },
+      ];
+    },
+    {
+      name: "openCombineSession";
+      accounts: [
+        {
+          name: "owner";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "collectionMint";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "tinySplAuthority";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "combineSession";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        },
+      ];
+      args: [];
+    },
+    {
+      name: "absorb";
+      accounts: [
+        {
+          name: "owner";
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: "leafDelegate";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "ownerDeniedWallet";
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
+        },
+        {
+          name: "collectionMint";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "tinySplAuthority";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "sourceLeafFormat";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "combineSession";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "logWrapper";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "compressionProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "mplBubblegumProgram";
+          isMut: false;
+          isSigner: false;
+        },
+      ];
+      args: [
+        {
+          name: "leafBatch";
+          type: {
+            defined: "LeafBatch";
+          };
+        },
+      ];
+    },
+    {
+      name: "closeCombineSession";
+      accounts: [
+        {
+          name: "authority";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "owner";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "newLeafOwner";
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
+        },
+        {
+          name: "newLeafOwnerAllowedWallet";
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
+        },
+        {
+          name: "newLeafOwnerDeniedWallet";
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
+        },
+        {
+          name: "combineSession";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "treeAuthority";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "merkleTree";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "treeCreatorOrDelegate";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "collectionMint";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "collectionMetadata";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "editionAccount";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "bubblegumSigner";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "collectionAuthorityRecord";
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
+        },
+        {
+          name: "tinySplAuthority";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "leafFormat";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "logWrapper";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "compressionProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "tokenMetadataProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "mplBubblegumProgram";
+          isMut: false;
+          isSigner: false;
+        },
+      ];
+      args: [];
+    },
+    {
+      name: "openDistributionSession";
+      accounts: [
+        {
+          name: "owner";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "collectionMint";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "tinySplAuthority";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "distributionSession";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        },
+      ];
+      args: [];
+    },
+    {
+      name: "fundDistributionSession";
+      accounts: [
+        {
+          name: "owner";
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: "leafDelegate";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "ownerDeniedWallet";
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
+        },
+        {
+          name: "collectionMint";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "tinySplAuthority";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "sourceLeafFormat";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "distributionSession";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "logWrapper";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "compressionProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "mplBubblegumProgram";
+          isMut: false;
+          isSigner: false;
+        },
+      ];
+      args: [
+        {
+          name: "leafBatch";
+          type: {
+            defined: "LeafBatch";
+          };
+        },
+      ];
+    },
+    {
+      name: "distribute";
+      accounts: [
+        {
+          name: "owner";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "ownerDeniedWallet";
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
+        },
+        {
+          name: "distributionSession";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "treeAuthority";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "merkleTree";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "treeCreatorOrDelegate";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "collectionMint";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "collectionMetadata";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "editionAccount";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "bubblegumSigner";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "collectionAuthorityRecord";
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
+        },
+        {
+          name: "tinySplAuthority";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "leafFormat";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "transferHook";
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
+        },
+        {
+          name: "transferHookProgram";
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
+        },
+        {
+          name: "logWrapper";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "compressionProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "tokenMetadataProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "mplBubblegumProgram";
+          isMut: false;
+          isSigner: false;
+        },
+      ];
+      args: [
+        {
+          name: "amounts";
+          type: {
+            vec: "u64";
+          };
+        },
+      ];
+    },
+    {
+      name: "closeDistributionSession";
+      accounts: [
+        {
+          name: "owner";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "ownerAllowedWallet";
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
+        },
+        {
+          name: "ownerDeniedWallet";
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
+        },
+        {
+          name: "distributionSession";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "treeAuthority";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "merkleTree";
           isMut: true;
           isSigner: false;
         },
         {
-          name: "authority";
+          name: "treeCreatorOrDelegate";
           isMut: false;
-          isSigner: true;
+          isSigner: false;
         },
-      ];
-      args: [
         {
-          name: "totalMetadataBytes";
-          type: "u32";
+          name: "collectionMint";
+          isMut: false;
+          isSigner: false;
         },
-      ];
-    },
-    {
-      name: "uploadLoggingMetadata";
-      accounts: [
         {
-          name: "metadata";
+          name: "collectionMetadata";
           isMut: true;
           isSigner: false;
         },
         {
-          name: "authority";
+          name: "editionAccount";
           isMut: false;
-          isSigner: true;
+          isSigner: false;
         },
-      ];
-      args: [
         {
-          name: "index";
-          type: "u32";
+          name: "bubblegumSigner";
+          isMut: false;
+          isSigner: false;
         },
         {
-          name: "bytes";
-          type: "bytes";
+          name: "collectionAuthorityRecord";
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
         },
-      ];
-    },
-    {
-      name: "logMetadata";
-      accounts: [
         {
-          name: "metadata";
+          name: "tinySplAuthority";
           isMut: false;
           isSigner: false;
         },
         {
-          name: "authority";
+          name: "leafFormat";
           isMut: false;
-          isSigner: true;
+          isSigner: false;
         },
         {
-          name: "noopProgram";
+          name: "logWrapper";
           isMut: false;
           isSigner: false;
         },
-      ];
-      args: [];
-    },
-    {
-      name: "closeMetadataAccount";
-      accounts: [
         {
-          name: "metadata";
-          isMut: true;
+          name: "compressionProgram";
+          isMut: false;
           isSigner: false;
         },
         {
-          name: "authority";
+          name: "tokenMetadataProgram";
           isMut: false;
-          isSigner: true;
+          isSigner: false;
         },
-      ];
-      args: [];
-    },
-    {
-      name: "createMint";
-      accounts: [
         {
-          name: "metadata";
-          isMut: true;
+          name: "systemProgram";
+          isMut: false;
           isSigner: false;
         },
         {
-          name: "masterEdition";
-          isMut: true;
+          name: "mplBubblegumProgram";
+          isMut: false;
           isSigner: false;
         },
+      ];
+      args: [];
+    },
+    {
+      name: "allowWallet";
+      accounts: [
         {
-          name: "mint";
+          name: "complianceAuthority";
           isMut: true;
           isSigner: true;
         },
         {
-          name: "mintAuthority";
-          isMut: true;
-          isSigner: true;
+          name: "collectionMint";
+          isMut: false;
+          isSigner: false;
         },
         {
           name: "tinySplAuthority";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "allowedWallet";
           isMut: true;
           isSigner: false;
         },
@@ -725,119 +4365,154 @@ type TinySpl = {
           isMut: false;
           isSigner: false;
         },
+      ];
+      args: [
         {
-          name: "sysvarInstructions";
+          name: "wallet";
+          type: "publicKey";
+        },
+      ];
+    },
+    {
+      name: "disallowWallet";
+      accounts: [
+        {
+          name: "complianceAuthority";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "collectionMint";
           isMut: false;
           isSigner: false;
         },
         {
-          name: "splTokenProgram";
+          name: "tinySplAuthority";
           isMut: false;
           isSigner: false;
         },
         {
-          name: "mplTokenMetadataProgram";
-          isMut: false;
+          name: "allowedWallet";
+          isMut: true;
           isSigner: false;
         },
       ];
       args: [
         {
-          name: "createMintMetadata";
-          type: {
-            defined: "CreateMintMetadata";
-          };
+          name: "wallet";
+          type: "publicKey";
         },
       ];
     },
     {
-      name: "mintTo";
+      name: "denyWallet";
       accounts: [
         {
-          name: "treeAuthority";
+          name: "complianceAuthority";
           isMut: true;
-          isSigner: false;
+          isSigner: true;
         },
         {
-          name: "newLeafOwner";
+          name: "collectionMint";
           isMut: false;
           isSigner: false;
         },
         {
-          name: "merkleTree";
-          isMut: true;
+          name: "tinySplAuthority";
+          isMut: false;
           isSigner: false;
         },
         {
-          name: "mintAuthority";
-          isMut: false;
-          isSigner: true;
+          name: "deniedWallet";
+          isMut: true;
+          isSigner: false;
         },
         {
-          name: "treeCreatorOrDelegate";
+          name: "systemProgram";
           isMut: false;
           isSigner: false;
         },
+      ];
+      args: [
         {
-          name: "collectionMint";
-          isMut: false;
-          isSigner: false;
+          name: "wallet";
+          type: "publicKey";
         },
+      ];
+    },
+    {
+      name: "undenyWallet";
+      accounts: [
         {
-          name: "collectionMetadata";
+          name: "complianceAuthority";
           isMut: true;
-          isSigner: false;
+          isSigner: true;
         },
         {
-          name: "editionAccount";
+          name: "collectionMint";
           isMut: false;
           isSigner: false;
         },
         {
-          name: "bubblegumSigner";
+          name: "tinySplAuthority";
           isMut: false;
           isSigner: false;
         },
         {
-          name: "tinySplAuthority";
+          name: "deniedWallet";
           isMut: true;
           isSigner: false;
         },
+      ];
+      args: [
         {
-          name: "logWrapper";
-          isMut: false;
-          isSigner: false;
+          name: "wallet";
+          type: "publicKey";
         },
+      ];
+    },
+    {
+      name: "setTransferHook";
+      accounts: [
         {
-          name: "compressionProgram";
+          name: "mintAuthority";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "collectionMint";
           isMut: false;
           isSigner: false;
         },
         {
-          name: "tokenMetadataProgram";
-          isMut: false;
+          name: "tinySplAuthority";
+          isMut: true;
           isSigner: false;
         },
         {
-          name: "systemProgram";
-          isMut: false;
+          name: "transferHook";
+          isMut: true;
           isSigner: false;
         },
         {
-          name: "mplBubblegumProgram";
+          name: "systemProgram";
           isMut: false;
           isSigner: false;
         },
       ];
       args: [
         {
-          name: "amount";
-          type: "u64";
+          name: "programId";
+          type: {
+            option: "publicKey";
+          };
         },
         {
-          name: "maxSupply";
+          name: "extraAccounts";
           type: {
-            option: "u64";
+            vec: {
+              defined: "TransferHookAccount";
+            };
           };
         },
       ];
@@ -870,6 +4545,36 @@ type TinySpl = {
           isMut: false;
           isSigner: false;
         },
+        {
+          name: "newLeafDelegate";
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
+        },
+        {
+          name: "newLeafOwnerAllowedWallet";
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
+        },
+        {
+          name: "authorityDeniedWallet";
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
+        },
+        {
+          name: "leafOwnerDeniedWallet";
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
+        },
+        {
+          name: "newLeafOwnerDeniedWallet";
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
+        },
         {
           name: "collectionMint";
           isMut: false;
@@ -890,21 +4595,59 @@ type TinySpl = {
           isMut: false;
           isSigner: false;
         },
+        {
+          name: "collectionAuthorityRecord";
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
+        },
         {
           name: "tinySplAuthority";
           isMut: false;
           isSigner: false;
         },
         {
-          name: "treeAuthority";
+          name: "sourceLeafFormat";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "leafFormat";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "sourceTreeAuthority";
           isMut: true;
           isSigner: false;
         },
         {
-          name: "merkleTree";
+          name: "destinationTreeAuthority";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "sourceMerkleTree";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "destinationMerkleTree";
           isMut: true;
           isSigner: false;
         },
+        {
+          name: "transferHook";
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
+        },
+        {
+          name: "transferHookProgram";
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
+        },
         {
           name: "logWrapper";
           isMut: false;
@@ -955,9 +4698,9 @@ type TinySpl = {
           type: "u32";
         },
         {
-          name: "amounts";
+          name: "outputs";
           type: {
-            vec: "u64";
+            defined: "SplitOutputs";
           };
         },
       ];
@@ -971,24 +4714,54 @@ type TinySpl = {
           isSigner: true;
         },
         {
-          name: "treeCreatorOrDelegate";
+          name: "treeCreatorOrDelegate";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "leafOwner";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "leafDelegate";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "newLeafOwner";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "newLeafDelegate";
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
+        },
+        {
+          name: "newLeafOwnerAllowedWallet";
           isMut: false;
           isSigner: false;
+          isOptional: true;
         },
         {
-          name: "leafOwner";
+          name: "authorityDeniedWallet";
           isMut: false;
           isSigner: false;
+          isOptional: true;
         },
         {
-          name: "leafDelegate";
+          name: "leafOwnerDeniedWallet";
           isMut: false;
           isSigner: false;
+          isOptional: true;
         },
         {
-          name: "newLeafOwner";
+          name: "newLeafOwnerDeniedWallet";
           isMut: false;
           isSigner: false;
+          isOptional: true;
         },
         {
           name: "collectionMint";
@@ -1010,20 +4783,50 @@ type TinySpl = {
           isMut: false;
           isSigner: false;
         },
+        {
+          name: "collectionAuthorityRecord";
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
+        },
         {
           name: "tinySplAuthority";
           isMut: false;
           isSigner: false;
         },
         {
-          name: "treeAuthority";
+          name: "sourceLeafFormat";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "leafFormat";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "destinationTreeAuthority";
           isMut: true;
           isSigner: false;
+          isOptional: true;
         },
         {
-          name: "merkleTree";
+          name: "destinationMerkleTree";
           isMut: true;
           isSigner: false;
+          isOptional: true;
+        },
+        {
+          name: "transferHook";
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
+        },
+        {
+          name: "transferHookProgram";
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
         },
         {
           name: "logWrapper";
@@ -1053,49 +4856,332 @@ type TinySpl = {
       ];
       args: [
         {
-          name: "amounts";
+          name: "leafBatch";
           type: {
-            vec: "u64";
+            defined: "LeafBatch";
           };
         },
         {
-          name: "assetIds";
+          name: "destinationTreeIndex";
           type: {
-            vec: "publicKey";
+            option: "u8";
           };
         },
         {
-          name: "roots";
-          type: {
-            vec: {
-              array: ["u8", 32];
+          name: "preserveDelegate";
+          type: "bool";
+        },
+      ];
+    },
+    {
+      name: "migrateAccount";
+      docs: [
+        "Upgrades any tiny spl account to the current layout of its type.",
+        "",
+        "Mints created before collection metadata could be governed have no",
+        "metadata authority. Upgrading them makes their mint authority the",
+        "metadata authority, so they can use `update_collection_metadata`,",
+        "`lock_collection_metadata` and `set_leaf_format`.",
+        "",
+        "Logging metadata accounts created before layouts were versioned can",
+        "still be uploaded to, logged and closed, and are read at their old",
+        "buffer offset until they are upgraded.",
+      ];
+      accounts: [
+        {
+          name: "payer";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "account";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        },
+      ];
+      args: [];
+    },
+  ];
+  accounts: [
+    {
+      name: "allowedWallet";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "collectionMint";
+            type: "publicKey";
+          },
+          {
+            name: "wallet";
+            type: "publicKey";
+          },
+          {
+            name: "layoutVersion";
+            type: "u8";
+          },
+        ];
+      };
+    },
+    {
+      name: "combineSession";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "collectionMint";
+            type: "publicKey";
+          },
+          {
+            name: "owner";
+            type: "publicKey";
+          },
+          {
+            name: "totalAmount";
+            type: "u64";
+          },
+          {
+            name: "openedAt";
+            type: "i64";
+          },
+          {
+            name: "layoutVersion";
+            type: "u8";
+          },
+        ];
+      };
+    },
+    {
+      name: "deniedWallet";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "collectionMint";
+            type: "publicKey";
+          },
+          {
+            name: "wallet";
+            type: "publicKey";
+          },
+          {
+            name: "layoutVersion";
+            type: "u8";
+          },
+        ];
+      };
+    },
+    {
+      name: "distributionSession";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "collectionMint";
+            type: "publicKey";
+          },
+          {
+            name: "owner";
+            type: "publicKey";
+          },
+          {
+            name: "remainingAmount";
+            type: "u64";
+          },
+          {
+            name: "layoutVersion";
+            type: "u8";
+          },
+        ];
+      };
+    },
+    {
+      name: "leafFormat";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "collectionMint";
+            type: "publicKey";
+          },
+          {
+            name: "version";
+            type: "u8";
+          },
+          {
+            name: "symbol";
+            type: "string";
+          },
+          {
+            name: "uriTemplate";
+            type: "string";
+          },
+          {
+            name: "nameStyle";
+            type: {
+              defined: "LeafNameStyle";
+            };
+          },
+          {
+            name: "sellerFeeBasisPoints";
+            type: "u16";
+          },
+          {
+            name: "primarySaleHappened";
+            type: "bool";
+          },
+          {
+            name: "isMutable";
+            type: "bool";
+          },
+          {
+            name: "additionalCreators";
+            type: {
+              vec: {
+                defined: "AdditionalCreator";
+              };
+            };
+          },
+          {
+            name: "layoutVersion";
+            type: "u8";
+          },
+        ];
+      };
+    },
+    {
+      name: "versionedLoggingMetadata";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "authority";
+            type: "publicKey";
+          },
+          {
+            name: "layoutVersion";
+            type: "u8";
+          },
+        ];
+      };
+    },
+    {
+      name: "loggingMetadata";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "authority";
+            type: "publicKey";
+          },
+        ];
+      };
+    },
+    {
+      name: "tinySplAuthority";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "isVerifiedTinySplMint";
+            type: "bool";
+          },
+          {
+            name: "currentSupply";
+            type: "u64";
+          },
+          {
+            name: "mintAuthority";
+            type: {
+              option: "publicKey";
+            };
+          },
+          {
+            name: "metadataAuthority";
+            type: {
+              option: "publicKey";
+            };
+          },
+          {
+            name: "leafFormatVersion";
+            type: "u8";
+          },
+          {
+            name: "collectionAuthorityRecord";
+            type: {
+              option: "publicKey";
+            };
+          },
+          {
+            name: "twinMint";
+            type: {
+              option: "publicKey";
+            };
+          },
+          {
+            name: "transferHookProgram";
+            type: {
+              option: "publicKey";
+            };
+          },
+          {
+            name: "complianceAuthority";
+            type: {
+              option: "publicKey";
+            };
+          },
+          {
+            name: "isAllowlistEnabled";
+            type: "bool";
+          },
+          {
+            name: "isDenylistEnabled";
+            type: "bool";
+          },
+          {
+            name: "layoutVersion";
+            type: "u8";
+          },
+        ];
+      };
+    },
+    {
+      name: "transferHook";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "collectionMint";
+            type: "publicKey";
+          },
+          {
+            name: "programId";
+            type: "publicKey";
+          },
+          {
+            name: "extraAccounts";
+            type: {
+              vec: {
+                defined: "TransferHookAccount";
+              };
             };
-          };
-        },
-        {
-          name: "nonces";
-          type: {
-            vec: "u64";
-          };
-        },
-        {
-          name: "indexes";
-          type: {
-            vec: "u32";
-          };
-        },
-        {
-          name: "proofPathEndIndexesExclusive";
-          type: {
-            vec: "u32";
-          };
-        },
-      ];
+          },
+          {
+            name: "layoutVersion";
+            type: "u8";
+          },
+        ];
+      };
     },
   ];
-  accounts: [
+  types: [
     {
-      name: "loggingMetadata";
+      name: "ComplianceConfig";
       type: {
         kind: "struct";
         fields: [
@@ -1103,49 +5189,253 @@ type TinySpl = {
             name: "authority";
             type: "publicKey";
           },
+          {
+            name: "isAllowlistEnabled";
+            type: "bool";
+          },
+          {
+            name: "isDenylistEnabled";
+            type: "bool";
+          },
         ];
       };
     },
     {
-      name: "tinySplAuthority";
+      name: "CreateMintMetadata";
       type: {
         kind: "struct";
         fields: [
           {
-            name: "isVerifiedTinySplMint";
+            name: "name";
+            type: "string";
+          },
+          {
+            name: "symbol";
+            type: "string";
+          },
+          {
+            name: "uri";
+            type: "string";
+          },
+          {
+            name: "sellerFeeBasisPoints";
+            type: "u16";
+          },
+          {
+            name: "primarySaleHappened";
             type: "bool";
           },
           {
-            name: "currentSupply";
-            type: "u64";
+            name: "isMutable";
+            type: "bool";
           },
           {
-            name: "mintAuthority";
+            name: "additionalCreators";
             type: {
-              option: "publicKey";
+              vec: {
+                defined: "AdditionalCreator";
+              };
             };
           },
         ];
       };
     },
-  ];
-  types: [
     {
-      name: "CreateMintMetadata";
+      name: "SplitOutputs";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "amounts";
+            type: {
+              vec: "u64";
+            };
+          },
+          {
+            name: "treeIndexes";
+            type: "bytes";
+          },
+          {
+            name: "extraTreeCount";
+            type: "u8";
+          },
+          {
+            name: "preserveDelegate";
+            type: "bool";
+          },
+        ];
+      };
+    },
+    {
+      name: "UpdateCollectionMetadataArgs";
       type: {
         kind: "struct";
         fields: [
           {
             name: "name";
-            type: "string";
+            type: {
+              option: "string";
+            };
           },
           {
             name: "symbol";
-            type: "string";
+            type: {
+              option: "string";
+            };
           },
           {
             name: "uri";
-            type: "string";
+            type: {
+              option: "string";
+            };
+          },
+        ];
+      };
+    },
+    {
+      name: "AdditionalCreator";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "address";
+            type: "publicKey";
+          },
+          {
+            name: "share";
+            type: "u8";
+          },
+        ];
+      };
+    },
+    {
+      name: "TransferHookAccount";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "pubkey";
+            type: "publicKey";
+          },
+          {
+            name: "isWritable";
+            type: "bool";
+          },
+        ];
+      };
+    },
+    {
+      name: "LeafInput";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "amount";
+            type: "u64";
+          },
+          {
+            name: "assetId";
+            type: "publicKey";
+          },
+          {
+            name: "root";
+            type: {
+              array: ["u8", 32];
+            };
+          },
+          {
+            name: "nonce";
+            type: "u64";
+          },
+          {
+            name: "index";
+            type: "u32";
+          },
+          {
+            name: "treeIndex";
+            type: "u8";
+          },
+          {
+            name: "leafFormatIndex";
+            type: "u8";
+          },
+          {
+            name: "proofLength";
+            type: "u8";
+          },
+        ];
+      };
+    },
+    {
+      name: "LeafBatch";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "inputs";
+            type: {
+              vec: {
+                defined: "LeafInput";
+              };
+            };
+          },
+          {
+            name: "treeCount";
+            type: "u8";
+          },
+          {
+            name: "leafFormatCount";
+            type: "u8";
+          },
+        ];
+      };
+    },
+    {
+      name: "TransferHookDestination";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "owner";
+            type: "publicKey";
+          },
+          {
+            name: "amount";
+            type: "u64";
+          },
+        ];
+      };
+    },
+    {
+      name: "TransferHookArgs";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "sourceOwner";
+            type: "publicKey";
+          },
+          {
+            name: "destinations";
+            type: {
+              vec: {
+                defined: "TransferHookDestination";
+              };
+            };
+          },
+        ];
+      };
+    },
+    {
+      name: "LeafNameStyle";
+      type: {
+        kind: "enum";
+        variants: [
+          {
+            name: "Grouped";
+          },
+          {
+            name: "Compact";
           },
         ];
       };
@@ -1202,6 +5492,176 @@ type TinySpl = {
       name: "ExceededMaxMintSupply";
       msg: "Exceeded max mint supply";
     },
+    {
+      code: 6010;
+      name: "ConflictingDelegateOptions";
+      msg: "Cannot both preserve the source delegate and set a new leaf delegate";
+    },
+    {
+      code: 6011;
+      name: "CannotPreserveDelegateOnTransfer";
+      msg: "Source delegate can only be preserved when the new leaf owner is the leaf owner";
+    },
+    {
+      code: 6012;
+      name: "TreeAuthorityMismatch";
+      msg: "Tree authority does not match the bubblegum tree config of its merkle tree";
+    },
+    {
+      code: 6013;
+      name: "InvalidTreeTable";
+      msg: "Tree table is missing merkle tree or tree authority accounts";
+    },
+    {
+      code: 6014;
+      name: "InvalidDestinationTreeIndex";
+      msg: "Destination tree index is out of range of the tree table";
+    },
+    {
+      code: 6015;
+      name: "InvalidLeafFormatVersion";
+      msg: "Leaf format version must follow the current leaf format version";
+    },
+    {
+      code: 6016;
+      name: "InvalidLeafFormat";
+      msg: "Leaf format symbol is too long or uri template cannot hold every amount";
+    },
+    {
+      code: 6017;
+      name: "LeafFormatAlreadyCurrent";
+      msg: "Leaf is already in the current leaf format";
+    },
+    {
+      code: 6018;
+      name: "InvalidRoyaltyConfig";
+      msg: "Invalid seller fee basis points or creator shares supplied";
+    },
+    {
+      code: 6019;
+      name: "TwinMintNotAllowed";
+      msg: "Twin mint cannot be created for this mint";
+    },
+    {
+      code: 6020;
+      name: "MissingTransferHookAccounts";
+      msg: "Transfer hook accounts are missing or do not match the registered hook";
+    },
+    {
+      code: 6021;
+      name: "TooManyTransferHookAccounts";
+      msg: "Transfer hook has too many extra accounts";
+    },
+    {
+      code: 6022;
+      name: "WalletNotAllowed";
+      msg: "Wallet is not on the allowlist of this mint";
+    },
+    {
+      code: 6023;
+      name: "WalletDenied";
+      msg: "Wallet is on the denylist of this mint";
+    },
+    {
+      code: 6024;
+      name: "MissingDeniedWalletAccount";
+      msg: "Denied wallet account is missing or invalid";
+    },
+    {
+      code: 6025;
+      name: "CombineSessionNotExpired";
+      msg: "Combine session can only be closed by its owner until it expires";
+    },
+    {
+      code: 6026;
+      name: "InvalidDistributionAccounts";
+      msg: "Recipient accounts do not match the distribution amounts";
+    },
+    {
+      code: 6027;
+      name: "ArithmeticOverflow";
+      msg: "Amount overflowed";
+    },
+    {
+      code: 6028;
+      name: "MissingRemainingAccounts";
+      msg: "Remaining accounts are missing";
+    },
+    {
+      code: 6029;
+      name: "UploadOutOfBounds";
+      msg: "Upload does not fit in the logging metadata account";
+    },
+    {
+      code: 6030;
+      name: "InvalidProofLayout";
+      msg: "Proof accounts do not match the proof lengths of the leaves";
+    },
+    {
+      code: 6031;
+      name: "InvalidCollectionMetadata";
+      msg: "Collection metadata is not the metadata account of the collection mint";
+    },
+    {
+      code: 6032;
+      name: "InvalidCollectionEdition";
+      msg: "Edition account is not the master edition of the collection mint";
+    },
+    {
+      code: 6033;
+      name: "InvalidBubblegumSigner";
+      msg: "Bubblegum signer is not the collection cpi signer of bubblegum";
+    },
+    {
+      code: 6034;
+      name: "InvalidMerkleTree";
+      msg: "Merkle tree is not owned by the account compression program";
+    },
+    {
+      code: 6035;
+      name: "UnsupportedAccountType";
+      msg: "Account is not a tiny spl account that can be migrated";
+    },
+    {
+      code: 6036;
+      name: "AccountLayoutAlreadyCurrent";
+      msg: "Account is already in the current layout";
+    },
+    {
+      code: 6037;
+      name: "InvalidMetadataPointer";
+      msg: "Token 2022 mint has no metadata pointer to the collection metadata";
+    },
+    {
+      code: 6038;
+      name: "TransferHookNotAllowed";
+      msg: "Transfer hook cannot be set on a mint with a twin mint";
+    },
+    {
+      code: 6039;
+      name: "InvalidDestinationTree";
+      msg: "Pass either a destination tree index or the destination tree accounts";
+    },
+    {
+      code: 6040;
+      name: "UnsizedCollection";
+      msg: "Only sized collections can be adopted";
+    },
+    {
+      code: 6041;
+      name: "CollectionMetadataNotGoverned";
+      msg: "Adopted collections keep their metadata under their update authority";
+    },
+    {
+      code: 6042;
+      name: "InvalidLeafFormatTable";
+      msg: "Leaf format table does not match the leaf batch";
+    },
+    {
+      code: 6043;
+      name: "CombineSessionRecipientNotAllowed";
+      msg: "Only the mint or compliance authority can mint an expired combine session to another wallet";
+    },
   ];
 };
 
@@ -7,6 +7,8 @@ import {
   ComputeBudgetProgram,
   Keypair,
   PublicKey,
+  SystemProgram,
+  TransactionInstruction,
   TransactionMessage,
   VersionedTransaction,
 } from "@solana/web3.js";
@@ -80,6 +82,15 @@ async function verifyHCaptchaToken(token: string) {
 const DEEZ_NUTS_AUTHORITY = Keypair.fromSecretKey(
   Uint8Array.from(JSON.parse(process.env.DEEZ_NUTS_AUTHORITY!))
 );
+const DEEZ_NUTS_COLLECTION_MINT = new PublicKey(
+  "DEEZyno8D9RCCghEWkTNarZrCW7HvvWE9z64tiqvQKpH"
+);
+const DEEZ_NUTS_COLLECTION_METADATA = new PublicKey(
+  "2GcCdocF2A2HdYMHPow6YJQxp4MbTjdSmS2eypAe4C6E"
+);
+const DEEZ_NUTS_TINY_SPL_AUTHORITY = new PublicKey(
+  "EzGv9FqDepT6winVNWGiLVrTKjPD5KgB2jTiHhmFR4X6"
+);
 
 async function buildMintToTransaction(
   publicKey: string,
@@ -87,13 +98,46 @@ async function buildMintToTransaction(
   blockhash: string
 ) {
   const program = getProgram();
+  // leaves are minted in the current leaf format of the mint
+  const { leafFormatVersion } = await program.account.tinySplAuthority.fetch(
+    DEEZ_NUTS_TINY_SPL_AUTHORITY
+  );
+  const [leafFormat] = PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("leaf_format"),
+      DEEZ_NUTS_COLLECTION_MINT.toBuffer(),
+      Buffer.from([leafFormatVersion]),
+    ],
+    program.programId
+  );
+
+  // the mint predates leaf formats, so its version 0 record may not exist yet.
+  // anyone can create it, the minter pays for it along with the fees
+  const initLeafFormatIxs: TransactionInstruction[] = [];
+  if (!(await program.account.leafFormat.fetchNullable(leafFormat))) {
+    initLeafFormatIxs.push(
+      await program.methods
+        .initLeafFormat()
+        .accounts({
+          payer: publicKey,
+          collectionMint: DEEZ_NUTS_COLLECTION_MINT,
+          collectionMetadata: DEEZ_NUTS_COLLECTION_METADATA,
+          tinySplAuthority: DEEZ_NUTS_TINY_SPL_AUTHORITY,
+          leafFormat,
+          systemProgram: SystemProgram.programId,
+        })
+        .instruction()
+    );
+  }
+
   const ix = await program.methods
     .mintTo(new BN(amount), new BN(21_000_000))
     .accounts({
       bubblegumSigner: "4ewWZC5gT6TGpm5LZNDs9wVonfUT2q5PP5sc9kVbwMAK",
-      collectionMetadata: "2GcCdocF2A2HdYMHPow6YJQxp4MbTjdSmS2eypAe4C6E",
-      collectionMint: "DEEZyno8D9RCCghEWkTNarZrCW7HvvWE9z64tiqvQKpH",
-      tinySplAuthority: "EzGv9FqDepT6winVNWGiLVrTKjPD5KgB2jTiHhmFR4X6",
+      collectionMetadata: DEEZ_NUTS_COLLECTION_METADATA,
+      collectionMint: DEEZ_NUTS_COLLECTION_MINT,
+      tinySplAuthority: DEEZ_NUTS_TINY_SPL_AUTHORITY,
+      leafFormat,
       compressionProgram: "cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS91L8KbNCK",
       editionAccount: "HURxPdTRu9wBrb64hDMR9ZB3RqAgFQEQ2K6zjpbZZ7Lc",
       logWrapper: "noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV",
@@ -103,7 +147,7 @@ async function buildMintToTransaction(
       tokenMetadataProgram: "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s",
       treeAuthority: "3aVQP4jSj5ab4Br73bVL7d7c96bJGuHXuE4iaMXPzas3",
       newLeafOwner: publicKey,
-      treeCreatorOrDelegate: "EzGv9FqDepT6winVNWGiLVrTKjPD5KgB2jTiHhmFR4X6",
+      treeCreatorOrDelegate: DEEZ_NUTS_TINY_SPL_AUTHORITY,
     })
     .instruction();
 
@@ -114,6 +158,7 @@ async function buildMintToTransaction(
       ComputeBudgetProgram.setComputeUnitPrice({
         microLamports: 10_000,
       }),
+      ...initLeafFormatIxs,
       ix,
     ],
   }).compileToV0Message();
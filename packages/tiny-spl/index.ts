@@ -5,6 +5,7 @@ import { TinySpl } from "./target/types/tiny_spl";
 const PROGRAM_ID = new PublicKey("tsP1jf31M3iGNPmANP3ep3iWCMTxpMFLNbewWVWWbSo");
 
 const TINY_SPL_AUTHORITY_SEED = "tiny_spl";
+const LEAF_FORMAT_SEED = "leaf_format";
 
 export type { TinySpl };
-export { idl, PROGRAM_ID, TINY_SPL_AUTHORITY_SEED };
+export { idl, LEAF_FORMAT_SEED, PROGRAM_ID, TINY_SPL_AUTHORITY_SEED };
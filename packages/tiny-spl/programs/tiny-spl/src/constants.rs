@@ -1,4 +1,26 @@
 pub const TINY_SPL_AUTHORITY_SEED: &[u8] = b"tiny_spl";
+pub const LEAF_FORMAT_SEED: &[u8] = b"leaf_format";
+pub const TWIN_MINT_SEED: &[u8] = b"twin_mint";
+pub const TRANSFER_HOOK_SEED: &[u8] = b"transfer_hook";
+pub const ALLOWED_WALLET_SEED: &[u8] = b"allowed_wallet";
+pub const DENIED_WALLET_SEED: &[u8] = b"denied_wallet";
+pub const COMBINE_SESSION_SEED: &[u8] = b"combine_session";
+pub const DISTRIBUTION_SESSION_SEED: &[u8] = b"distribution_session";
+// seed of the pda bubblegum signs collection cpis with, derived from bubblegum
+pub const BUBBLEGUM_SIGNER_SEED: &[u8] = b"collection_cpi";
 
-pub const METADATA_BUFFER_START: usize = 8 // discriminator
+pub const DEFAULT_LEAF_URI_TEMPLATE: &str =
+    "https://metadata.tinys.pl/collection?id={collection_mint}&amount={amount}";
+pub const MAX_SYMBOL_LENGTH: usize = 10;
+pub const MAX_URI_LENGTH: usize = 200;
+pub const MAX_ADDITIONAL_CREATORS: usize = 4;
+pub const MAX_SELLER_FEE_BASIS_POINTS: u16 = 10_000;
+pub const MAX_TRANSFER_HOOK_EXTRA_ACCOUNTS: usize = 8;
+pub const COMBINE_SESSION_TIMEOUT_SECONDS: i64 = 24 * 60 * 60;
+
+// sha256("global:execute_transfer_hook")[..8], so hooks can be written in anchor
+pub const TRANSFER_HOOK_DISCRIMINATOR: [u8; 8] = [120, 157, 67, 141, 88, 144, 143, 220];
+
+pub const LEGACY_METADATA_BUFFER_START: usize = 8 // discriminator
     + 32; // authority
+pub const METADATA_BUFFER_START: usize = LEGACY_METADATA_BUFFER_START + 1; // layout version
@@ -22,4 +22,72 @@ pub enum TinySplError {
     InvalidCombineParameters,
     #[msg("Exceeded max mint supply")]
     ExceededMaxMintSupply,
+    #[msg("Cannot both preserve the source delegate and set a new leaf delegate")]
+    ConflictingDelegateOptions,
+    #[msg("Source delegate can only be preserved when the new leaf owner is the leaf owner")]
+    CannotPreserveDelegateOnTransfer,
+    #[msg("Tree authority does not match the bubblegum tree config of its merkle tree")]
+    TreeAuthorityMismatch,
+    #[msg("Tree table is missing merkle tree or tree authority accounts")]
+    InvalidTreeTable,
+    #[msg("Destination tree index is out of range of the tree table")]
+    InvalidDestinationTreeIndex,
+    #[msg("Leaf format version must follow the current leaf format version")]
+    InvalidLeafFormatVersion,
+    #[msg("Leaf format symbol is too long or uri template cannot hold every amount")]
+    InvalidLeafFormat,
+    #[msg("Leaf is already in the current leaf format")]
+    LeafFormatAlreadyCurrent,
+    #[msg("Invalid seller fee basis points or creator shares supplied")]
+    InvalidRoyaltyConfig,
+    #[msg("Twin mint cannot be created for this mint")]
+    TwinMintNotAllowed,
+    #[msg("Transfer hook accounts are missing or do not match the registered hook")]
+    MissingTransferHookAccounts,
+    #[msg("Transfer hook has too many extra accounts")]
+    TooManyTransferHookAccounts,
+    #[msg("Wallet is not on the allowlist of this mint")]
+    WalletNotAllowed,
+    #[msg("Wallet is on the denylist of this mint")]
+    WalletDenied,
+    #[msg("Denied wallet account is missing or invalid")]
+    MissingDeniedWalletAccount,
+    #[msg("Combine session can only be closed by its owner until it expires")]
+    CombineSessionNotExpired,
+    #[msg("Recipient accounts do not match the distribution amounts")]
+    InvalidDistributionAccounts,
+    #[msg("Amount overflowed")]
+    ArithmeticOverflow,
+    #[msg("Remaining accounts are missing")]
+    MissingRemainingAccounts,
+    #[msg("Upload does not fit in the logging metadata account")]
+    UploadOutOfBounds,
+    #[msg("Proof accounts do not match the proof lengths of the leaves")]
+    InvalidProofLayout,
+    #[msg("Collection metadata is not the metadata account of the collection mint")]
+    InvalidCollectionMetadata,
+    #[msg("Edition account is not the master edition of the collection mint")]
+    InvalidCollectionEdition,
+    #[msg("Bubblegum signer is not the collection cpi signer of bubblegum")]
+    InvalidBubblegumSigner,
+    #[msg("Merkle tree is not owned by the account compression program")]
+    InvalidMerkleTree,
+    #[msg("Account is not a tiny spl account that can be migrated")]
+    UnsupportedAccountType,
+    #[msg("Account is already in the current layout")]
+    AccountLayoutAlreadyCurrent,
+    #[msg("Token 2022 mint has no metadata pointer to the collection metadata")]
+    InvalidMetadataPointer,
+    #[msg("Transfer hook cannot be set on a mint with a twin mint")]
+    TransferHookNotAllowed,
+    #[msg("Pass either a destination tree index or the destination tree accounts")]
+    InvalidDestinationTree,
+    #[msg("Only sized collections can be adopted")]
+    UnsizedCollection,
+    #[msg("Adopted collections keep their metadata under their update authority")]
+    CollectionMetadataNotGoverned,
+    #[msg("Leaf format table does not match the leaf batch")]
+    InvalidLeafFormatTable,
+    #[msg("Only the mint or compliance authority can mint an expired combine session to another wallet")]
+    CombineSessionRecipientNotAllowed,
 }
@@ -5,13 +5,15 @@ mod program_wrappers;
 mod state;
 mod utils;
 
+#[cfg(all(feature = "custom-heap", any(test, target_os = "solana")))]
 use std::{alloc::Layout, mem::size_of, ptr::null_mut};
 
-use anchor_lang::{
-    prelude::*,
-    solana_program::entrypoint::{HEAP_LENGTH, HEAP_START_ADDRESS},
-};
+use anchor_lang::prelude::*;
+#[cfg(all(feature = "custom-heap", target_os = "solana"))]
+use anchor_lang::solana_program::entrypoint::{HEAP_LENGTH, HEAP_START_ADDRESS};
 use processor::*;
+use state::{LeafNameStyle, TransferHookAccount};
+use utils::LeafBatch;
 
 declare_id!("tsP1jf31M3iGNPmANP3ep3iWCMTxpMFLNbewWVWWbSo");
 
@@ -42,17 +44,168 @@ pub mod tiny_spl {
         processor::close_metadata_account(ctx)
     }
 
+    /// Creates a tiny spl mint.
+    ///
+    /// Leaves are always bubblegum v1 leaves in a token metadata collection.
+    /// Bubblegum v2 leaves in mpl core collections need a newer solana sdk
+    /// than anchor 0.29 builds against, so mints cannot choose them until the
+    /// program moves to a newer toolchain.
+    ///
+    /// Mints cannot freeze individual leaves. Bubblegum v1 has no frozen
+    /// leaves, so a freeze could only be checked by tiny spl and the owner
+    /// could still move the leaf with a direct bubblegum `transfer`.
+    ///
+    /// Mints cannot have a permanent delegate that claws leaves back.
+    /// Bubblegum v1 leaves have no permanent delegate, and owners can clear
+    /// any delegation tiny spl sets with a direct bubblegum `transfer` or
+    /// `delegate`, so tiny spl could not guarantee that a leaf can be seized.
+    ///
+    /// Leaf metadata inputs are not snapshotted into the tiny spl authority
+    /// for zero-copy reads. They live in versioned `LeafFormat` accounts, and
+    /// leaves minted in older versions need those to verify, so a snapshot
+    /// could only stand in for the current format and would have to be kept
+    /// in sync with it. Symbols, uri templates and creators have no fixed size
+    /// for a zero-copy layout either, and the token metadata read it was meant
+    /// to save is already gone from `mint_to`, `split` and `combine`.
     pub fn create_mint(
         ctx: Context<CreateMint>,
         create_mint_metadata: CreateMintMetadata,
+        compliance_config: Option<ComplianceConfig>,
     ) -> Result<()> {
-        processor::create_mint(ctx, create_mint_metadata)
+        processor::create_mint(ctx, create_mint_metadata, compliance_config)
+    }
+
+    pub fn adopt_collection(ctx: Context<AdoptCollection>) -> Result<()> {
+        processor::adopt_collection(ctx)
     }
 
     pub fn mint_to(ctx: Context<MintTo>, amount: u64, max_supply: Option<u64>) -> Result<()> {
         processor::mint_to(ctx, amount, max_supply)
     }
 
+    pub fn update_collection_metadata(
+        ctx: Context<UpdateCollectionMetadata>,
+        update_collection_metadata_args: UpdateCollectionMetadataArgs,
+    ) -> Result<()> {
+        processor::update_collection_metadata(ctx, update_collection_metadata_args)
+    }
+
+    pub fn lock_collection_metadata(ctx: Context<LockCollectionMetadata>) -> Result<()> {
+        processor::lock_collection_metadata(ctx)
+    }
+
+    pub fn transfer_metadata_authority(
+        ctx: Context<TransferMetadataAuthority>,
+        new_metadata_authority: Option<Pubkey>,
+    ) -> Result<()> {
+        processor::transfer_metadata_authority(ctx, new_metadata_authority)
+    }
+
+    pub fn init_leaf_format(ctx: Context<InitLeafFormat>) -> Result<()> {
+        processor::init_leaf_format(ctx)
+    }
+
+    pub fn set_leaf_format(
+        ctx: Context<SetLeafFormat>,
+        version: u8,
+        symbol: String,
+        uri_template: String,
+        name_style: LeafNameStyle,
+    ) -> Result<()> {
+        processor::set_leaf_format(ctx, version, symbol, uri_template, name_style)
+    }
+
+    pub fn migrate_leaf<'info>(
+        ctx: Context<'_, '_, '_, 'info, MigrateLeaf<'info>>,
+        amount: u64,
+        asset_id: Pubkey,
+        root: [u8; 32],
+        nonce: u64,
+        index: u32,
+    ) -> Result<()> {
+        processor::migrate_leaf(ctx, amount, asset_id, root, nonce, index)
+    }
+
+    pub fn create_twin_mint(ctx: Context<CreateTwinMint>) -> Result<()> {
+        processor::create_twin_mint(ctx)
+    }
+
+    pub fn decompress<'info>(
+        ctx: Context<'_, '_, '_, 'info, Decompress<'info>>,
+        amount: u64,
+        asset_id: Pubkey,
+        root: [u8; 32],
+        nonce: u64,
+        index: u32,
+    ) -> Result<()> {
+        processor::decompress(ctx, amount, asset_id, root, nonce, index)
+    }
+
+    pub fn compress(ctx: Context<Compress>, amount: u64) -> Result<()> {
+        processor::compress(ctx, amount)
+    }
+
+    pub fn open_combine_session(ctx: Context<OpenCombineSession>) -> Result<()> {
+        processor::open_combine_session(ctx)
+    }
+
+    pub fn absorb<'info>(
+        ctx: Context<'_, '_, '_, 'info, Absorb<'info>>,
+        leaf_batch: LeafBatch,
+    ) -> Result<()> {
+        processor::absorb(ctx, leaf_batch)
+    }
+
+    pub fn close_combine_session(ctx: Context<CloseCombineSession>) -> Result<()> {
+        processor::close_combine_session(ctx)
+    }
+
+    pub fn open_distribution_session(ctx: Context<OpenDistributionSession>) -> Result<()> {
+        processor::open_distribution_session(ctx)
+    }
+
+    pub fn fund_distribution_session<'info>(
+        ctx: Context<'_, '_, '_, 'info, FundDistributionSession<'info>>,
+        leaf_batch: LeafBatch,
+    ) -> Result<()> {
+        processor::fund_distribution_session(ctx, leaf_batch)
+    }
+
+    pub fn distribute<'info>(
+        ctx: Context<'_, '_, '_, 'info, Distribute<'info>>,
+        amounts: Vec<u64>,
+    ) -> Result<()> {
+        processor::distribute(ctx, amounts)
+    }
+
+    pub fn close_distribution_session(ctx: Context<CloseDistributionSession>) -> Result<()> {
+        processor::close_distribution_session(ctx)
+    }
+
+    pub fn allow_wallet(ctx: Context<AllowWallet>, wallet: Pubkey) -> Result<()> {
+        processor::allow_wallet(ctx, wallet)
+    }
+
+    pub fn disallow_wallet(ctx: Context<DisallowWallet>, wallet: Pubkey) -> Result<()> {
+        processor::disallow_wallet(ctx, wallet)
+    }
+
+    pub fn deny_wallet(ctx: Context<DenyWallet>, wallet: Pubkey) -> Result<()> {
+        processor::deny_wallet(ctx, wallet)
+    }
+
+    pub fn undeny_wallet(ctx: Context<UndenyWallet>, wallet: Pubkey) -> Result<()> {
+        processor::undeny_wallet(ctx, wallet)
+    }
+
+    pub fn set_transfer_hook(
+        ctx: Context<SetTransferHook>,
+        program_id: Option<Pubkey>,
+        extra_accounts: Vec<TransferHookAccount>,
+    ) -> Result<()> {
+        processor::set_transfer_hook(ctx, program_id, extra_accounts)
+    }
+
     pub fn split<'info>(
         ctx: Context<'_, '_, '_, 'info, Split<'info>>,
         source_amount: u64,
@@ -60,59 +213,67 @@ pub mod tiny_spl {
         root: [u8; 32],
         nonce: u64,
         index: u32,
-        amounts: Vec<u64>,
+        outputs: SplitOutputs,
     ) -> Result<()> {
-        processor::split(ctx, source_amount, asset_id, root, nonce, index, amounts)
+        processor::split(ctx, source_amount, asset_id, root, nonce, index, outputs)
     }
 
     pub fn combine<'info>(
         ctx: Context<'_, '_, '_, 'info, Combine<'info>>,
-        amounts: Vec<u64>,
-        asset_ids: Vec<Pubkey>,
-        roots: Vec<[u8; 32]>,
-        nonces: Vec<u64>,
-        indexes: Vec<u32>,
-        proof_path_end_indexes_exclusive: Vec<u32>,
+        leaf_batch: LeafBatch,
+        destination_tree_index: Option<u8>,
+        preserve_delegate: bool,
     ) -> Result<()> {
-        processor::combine(
-            ctx,
-            amounts,
-            asset_ids,
-            roots,
-            nonces,
-            indexes,
-            proof_path_end_indexes_exclusive,
-        )
+        processor::combine(ctx, leaf_batch, destination_tree_index, preserve_delegate)
+    }
+
+    /// Upgrades any tiny spl account to the current layout of its type.
+    ///
+    /// Mints created before collection metadata could be governed have no
+    /// metadata authority. Upgrading them makes their mint authority the
+    /// metadata authority, so they can use `update_collection_metadata`,
+    /// `lock_collection_metadata` and `set_leaf_format`.
+    ///
+    /// Logging metadata accounts created before layouts were versioned can
+    /// still be uploaded to, logged and closed, and are read at their old
+    /// buffer offset until they are upgraded.
+    pub fn migrate_account(ctx: Context<MigrateAccount>) -> Result<()> {
+        processor::migrate_account(ctx)
     }
 }
 
 #[derive(Accounts)]
 pub struct Initialize {}
 
-// same as default solana bump allocator
-// this is needed so I can mess with the heap
-// when I make logging cpis
+// same as default solana bump allocator, except that it grows up from the
+// heap start so it can use a larger heap frame when the program is built for
+// one. this is also needed so I can mess with the heap when I make cpis,
+// see utils::with_heap_checkpoint
+#[cfg(all(feature = "custom-heap", any(test, target_os = "solana")))]
 pub struct BumpAllocator {
     pub start: usize,
     pub len: usize,
 }
 
+#[cfg(all(feature = "custom-heap", any(test, target_os = "solana")))]
 impl BumpAllocator {
-    const RESERVED_MEM: usize = 1 * size_of::<*mut u8>();
+    const RESERVED_MEM: usize = size_of::<*mut u8>();
 
     /// Return heap position as of this call
-    pub unsafe fn pos(&self) -> usize {
+    pub(crate) unsafe fn pos(&self) -> usize {
         let pos_ptr = self.start as *mut usize;
         *pos_ptr
     }
 
-    /// Reset heap start cursor to position.
-    /// ### This is very unsafe, use wisely
-    pub unsafe fn move_cursor(&self, pos: usize) {
+    /// Reset heap cursor to position.
+    /// ### This is very unsafe, use `utils::with_heap_checkpoint` instead
+    pub(crate) unsafe fn move_cursor(&self, pos: usize) {
         let pos_ptr = self.start as *mut usize;
         *pos_ptr = pos;
     }
 }
+
+#[cfg(all(feature = "custom-heap", any(test, target_os = "solana")))]
 unsafe impl std::alloc::GlobalAlloc for BumpAllocator {
     #[inline]
     unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
@@ -121,15 +282,23 @@ unsafe impl std::alloc::GlobalAlloc for BumpAllocator {
         let mut pos = *pos_ptr;
         if pos == 0 {
             // First time, set starting position
-            pos = self.start + self.len;
+            pos = self.start + BumpAllocator::RESERVED_MEM;
         }
-        pos = pos.saturating_sub(layout.size());
-        pos &= !(layout.align().wrapping_sub(1));
-        if pos < self.start + BumpAllocator::RESERVED_MEM {
+        let align_mask = layout.align().wrapping_sub(1);
+        let Some(aligned) = pos.checked_add(align_mask).map(|pos| pos & !align_mask) else {
+            return null_mut();
+        };
+        let Some(end) = aligned.checked_add(layout.size()) else {
+            return null_mut();
+        };
+        // never hand out memory past the heap frame the program was built
+        // for, so running out fails as an allocation error instead of a
+        // fault on access
+        if end > self.start + self.len {
             return null_mut();
         }
-        *pos_ptr = pos;
-        pos as *mut u8
+        *pos_ptr = end;
+        aligned as *mut u8
     }
     #[inline]
     unsafe fn dealloc(&self, _: *mut u8, _: Layout) {
@@ -137,8 +306,27 @@ unsafe impl std::alloc::GlobalAlloc for BumpAllocator {
     }
 }
 
+#[cfg(all(feature = "custom-heap", target_os = "solana"))]
 #[global_allocator]
 static A: BumpAllocator = BumpAllocator {
     start: HEAP_START_ADDRESS as usize,
-    len: HEAP_LENGTH,
+    len: HEAP_FRAME_BYTES,
 };
+
+// the default heap frame every transaction gets
+#[cfg(all(
+    feature = "custom-heap",
+    not(feature = "large-heap-frame"),
+    target_os = "solana"
+))]
+const HEAP_FRAME_BYTES: usize = HEAP_LENGTH;
+
+// builds with `large-heap-frame` use the largest heap frame a transaction can
+// request, so every transaction sent to them must start with
+// `ComputeBudgetProgram.requestHeapFrame({ bytes: 256 * 1024 })`
+#[cfg(all(
+    feature = "custom-heap",
+    feature = "large-heap-frame",
+    target_os = "solana"
+))]
+const HEAP_FRAME_BYTES: usize = 256 * 1024;
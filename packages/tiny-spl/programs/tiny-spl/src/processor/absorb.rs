@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{COMBINE_SESSION_SEED, LEAF_FORMAT_SEED, TINY_SPL_AUTHORITY_SEED},
+    error::TinySplError,
+    program_wrappers::{MplBubblegum, Noop, SplCompression},
+    state::{CombineSession, LeafFormat, TinySplAuthority},
+    utils::{burn_leaf_batch, verify_wallet_not_denied, BurnLeafBatch, LeafBatch},
+};
+
+pub fn absorb<'info>(
+    ctx: Context<'_, '_, '_, 'info, Absorb<'info>>,
+    leaf_batch: LeafBatch,
+) -> Result<()> {
+    verify_wallet_not_denied(
+        &ctx.accounts.tiny_spl_authority,
+        &ctx.accounts.collection_mint.key(),
+        &ctx.accounts.owner.key(),
+        ctx.accounts.owner_denied_wallet.as_deref(),
+    )?;
+
+    let burn_cpi_context = CpiContext::new(
+        ctx.accounts.mpl_bubblegum_program.to_account_info(),
+        BurnLeafBatch {
+            leaf_owner: ctx.accounts.owner.to_account_info(),
+            leaf_delegate: ctx.accounts.leaf_delegate.to_account_info(),
+            collection_mint: ctx.accounts.collection_mint.to_account_info(),
+            log_wrapper: ctx.accounts.log_wrapper.to_account_info(),
+            compression_program: ctx.accounts.compression_program.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+        },
+    )
+    .with_remaining_accounts(ctx.remaining_accounts.to_vec());
+    let batch_amount = burn_leaf_batch(
+        &burn_cpi_context,
+        &ctx.accounts.tiny_spl_authority,
+        &ctx.accounts.source_leaf_format,
+        &leaf_batch,
+    )?;
+
+    let combine_session = &mut ctx.accounts.combine_session;
+    combine_session.total_amount = combine_session
+        .total_amount
+        .checked_add(batch_amount)
+        .ok_or(TinySplError::ArithmeticOverflow)?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct Absorb<'info> {
+    pub owner: Signer<'info>,
+    /// CHECK: This account is checked in instruction
+    pub leaf_delegate: UncheckedAccount<'info>,
+    /// CHECK: This account is checked in instruction
+    pub owner_denied_wallet: Option<UncheckedAccount<'info>>,
+    /// CHECK: checked in tiny spl authority seeds
+    pub collection_mint: UncheckedAccount<'info>,
+    #[account(
+        seeds = [
+            TINY_SPL_AUTHORITY_SEED,
+            collection_mint.key().as_ref(),
+        ],
+        bump,
+        constraint = tiny_spl_authority.is_verified_tiny_spl_mint
+    )]
+    pub tiny_spl_authority: Box<Account<'info, TinySplAuthority>>,
+    #[account(
+        seeds = [
+            LEAF_FORMAT_SEED,
+            collection_mint.key().as_ref(),
+            &[source_leaf_format.version],
+        ],
+        bump,
+    )]
+    pub source_leaf_format: Box<Account<'info, LeafFormat>>,
+    #[account(
+        mut,
+        seeds = [
+            COMBINE_SESSION_SEED,
+            collection_mint.key().as_ref(),
+            owner.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub combine_session: Account<'info, CombineSession>,
+    pub log_wrapper: Program<'info, Noop>,
+    pub compression_program: Program<'info, SplCompression>,
+    pub system_program: Program<'info, System>,
+    pub mpl_bubblegum_program: Program<'info, MplBubblegum>,
+}
@@ -0,0 +1,162 @@
+use anchor_lang::{prelude::*, solana_program};
+use anchor_spl::metadata::{mpl_token_metadata, Metadata};
+
+use crate::{
+    constants::{
+        DEFAULT_LEAF_URI_TEMPLATE, LEAF_FORMAT_SEED, MAX_ADDITIONAL_CREATORS,
+        TINY_SPL_AUTHORITY_SEED,
+    },
+    error::TinySplError,
+    state::{AdditionalCreator, LeafFormat, LeafNameStyle, TinySplAuthority, VersionedAccount},
+};
+
+pub fn adopt_collection(ctx: Context<AdoptCollection>) -> Result<()> {
+    let collection_metadata = mpl_token_metadata::accounts::Metadata::safe_deserialize(
+        ctx.accounts.collection_metadata.try_borrow_data()?.as_ref(),
+    )?;
+    // bubblegum keeps the size of sized collections up to date, which it
+    // cannot do for unsized ones
+    require!(
+        collection_metadata.collection_details.is_some(),
+        TinySplError::UnsizedCollection
+    );
+
+    // leaves pay royalties to the creators of the collection, next to tiny spl
+    let additional_creators: Vec<AdditionalCreator> = collection_metadata
+        .creators
+        .unwrap_or_default()
+        .into_iter()
+        .map(|creator| AdditionalCreator {
+            address: creator.address,
+            share: creator.share,
+        })
+        .collect();
+    require!(
+        additional_creators.len() <= MAX_ADDITIONAL_CREATORS,
+        TinySplError::InvalidRoyaltyConfig
+    );
+
+    let cpi_context = CpiContext::new(
+        ctx.accounts.token_metadata_program.to_account_info(),
+        ApproveTinySplCollectionAuthority {
+            collection_authority_record: ctx.accounts.collection_authority_record.to_account_info(),
+            new_collection_authority: ctx.accounts.tiny_spl_authority.to_account_info(),
+            update_authority: ctx.accounts.update_authority.to_account_info(),
+            payer: ctx.accounts.update_authority.to_account_info(),
+            metadata: ctx.accounts.collection_metadata.to_account_info(),
+            mint: ctx.accounts.collection_mint.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+        },
+    );
+    approve_tiny_spl_collection_authority(cpi_context)?;
+
+    let leaf_format = &mut ctx.accounts.leaf_format;
+    leaf_format.collection_mint = ctx.accounts.collection_mint.key();
+    leaf_format.version = 0;
+    leaf_format.symbol = collection_metadata.symbol.replace("\0", "");
+    leaf_format.uri_template = DEFAULT_LEAF_URI_TEMPLATE.to_string();
+    leaf_format.name_style = LeafNameStyle::Compact;
+    leaf_format.seller_fee_basis_points = collection_metadata.seller_fee_basis_points;
+    leaf_format.primary_sale_happened = false;
+    leaf_format.is_mutable = true;
+    leaf_format.additional_creators = additional_creators;
+    leaf_format.layout_version = LeafFormat::LAYOUT_VERSION;
+
+    // the collection keeps its update authority, so the metadata authority can
+    // only change the leaf format, not the collection metadata
+    let tiny_spl_authority = &mut ctx.accounts.tiny_spl_authority;
+    tiny_spl_authority.is_verified_tiny_spl_mint = true;
+    tiny_spl_authority.current_supply = 0;
+    tiny_spl_authority.mint_authority = Some(*ctx.accounts.update_authority.key);
+    tiny_spl_authority.metadata_authority = Some(*ctx.accounts.update_authority.key);
+    tiny_spl_authority.leaf_format_version = 0;
+    tiny_spl_authority.collection_authority_record =
+        Some(ctx.accounts.collection_authority_record.key());
+    tiny_spl_authority.twin_mint = None;
+    tiny_spl_authority.transfer_hook_program = None;
+    tiny_spl_authority.compliance_authority = None;
+    tiny_spl_authority.is_allowlist_enabled = false;
+    tiny_spl_authority.is_denylist_enabled = false;
+    tiny_spl_authority.layout_version = TinySplAuthority::LAYOUT_VERSION;
+
+    Ok(())
+}
+
+fn approve_tiny_spl_collection_authority<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, ApproveTinySplCollectionAuthority<'info>>,
+) -> Result<()> {
+    let ix = mpl_token_metadata::instructions::ApproveCollectionAuthority {
+        collection_authority_record: *ctx.accounts.collection_authority_record.key,
+        new_collection_authority: *ctx.accounts.new_collection_authority.key,
+        update_authority: *ctx.accounts.update_authority.key,
+        payer: *ctx.accounts.payer.key,
+        metadata: *ctx.accounts.metadata.key,
+        mint: *ctx.accounts.mint.key,
+        system_program: *ctx.accounts.system_program.key,
+        rent: None,
+    }
+    .instruction();
+
+    solana_program::program::invoke(&ix, &ToAccountInfos::to_account_infos(&ctx))
+        .map_err(Into::into)
+}
+
+#[derive(Accounts)]
+pub struct AdoptCollection<'info> {
+    #[account(mut)]
+    pub update_authority: Signer<'info>,
+    /// CHECK: checked in cpi to mpl token metadata
+    pub collection_mint: UncheckedAccount<'info>,
+    /// CHECK: checked in accounts constraint
+    #[account(
+        address = mpl_token_metadata::accounts::Metadata::find_pda(&collection_mint.key()).0,
+        owner = mpl_token_metadata::ID,
+    )]
+    pub collection_metadata: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: checked in cpi to mpl token metadata
+    pub collection_authority_record: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = update_authority,
+        space = TinySplAuthority::SPACE,
+        seeds = [
+            TINY_SPL_AUTHORITY_SEED,
+            collection_mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub tiny_spl_authority: Box<Account<'info, TinySplAuthority>>,
+    #[account(
+        init,
+        payer = update_authority,
+        space = LeafFormat::SPACE,
+        seeds = [
+            LEAF_FORMAT_SEED,
+            collection_mint.key().as_ref(),
+            &[0],
+        ],
+        bump,
+    )]
+    pub leaf_format: Box<Account<'info, LeafFormat>>,
+    pub system_program: Program<'info, System>,
+    pub token_metadata_program: Program<'info, Metadata>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveTinySplCollectionAuthority<'info> {
+    /// CHECK: checked in cpi to mpl token metadata
+    pub collection_authority_record: AccountInfo<'info>,
+    /// CHECK: checked in cpi to mpl token metadata
+    pub new_collection_authority: AccountInfo<'info>,
+    /// CHECK: checked in cpi to mpl token metadata
+    pub update_authority: AccountInfo<'info>,
+    /// CHECK: checked in cpi to mpl token metadata
+    pub payer: AccountInfo<'info>,
+    /// CHECK: checked in cpi to mpl token metadata
+    pub metadata: AccountInfo<'info>,
+    /// CHECK: checked in cpi to mpl token metadata
+    pub mint: AccountInfo<'info>,
+    /// CHECK: checked in cpi to mpl token metadata
+    pub system_program: AccountInfo<'info>,
+}
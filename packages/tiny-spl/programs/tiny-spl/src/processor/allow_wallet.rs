@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{ALLOWED_WALLET_SEED, TINY_SPL_AUTHORITY_SEED},
+    state::{AllowedWallet, TinySplAuthority, VersionedAccount},
+};
+
+pub fn allow_wallet(ctx: Context<AllowWallet>, wallet: Pubkey) -> Result<()> {
+    let allowed_wallet = &mut ctx.accounts.allowed_wallet;
+    allowed_wallet.collection_mint = ctx.accounts.collection_mint.key();
+    allowed_wallet.wallet = wallet;
+    allowed_wallet.layout_version = AllowedWallet::LAYOUT_VERSION;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey)]
+pub struct AllowWallet<'info> {
+    #[account(mut)]
+    pub compliance_authority: Signer<'info>,
+    /// CHECK: checked in tiny spl authority seeds
+    pub collection_mint: UncheckedAccount<'info>,
+    #[account(
+        seeds = [
+            TINY_SPL_AUTHORITY_SEED,
+            collection_mint.key().as_ref(),
+        ],
+        bump,
+        constraint = tiny_spl_authority.is_verified_tiny_spl_mint
+            && tiny_spl_authority.is_allowlist_enabled
+            && tiny_spl_authority.compliance_authority == Some(compliance_authority.key())
+    )]
+    pub tiny_spl_authority: Box<Account<'info, TinySplAuthority>>,
+    #[account(
+        init,
+        payer = compliance_authority,
+        space = AllowedWallet::SPACE,
+        seeds = [
+            ALLOWED_WALLET_SEED,
+            collection_mint.key().as_ref(),
+            wallet.as_ref(),
+        ],
+        bump,
+    )]
+    pub allowed_wallet: Account<'info, AllowedWallet>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,367 @@
+use anchor_lang::prelude::*;
+use anchor_spl::metadata::{mpl_token_metadata, Metadata};
+
+use crate::{
+    constants::{
+        BUBBLEGUM_SIGNER_SEED, COMBINE_SESSION_SEED, COMBINE_SESSION_TIMEOUT_SECONDS,
+        LEAF_FORMAT_SEED, TINY_SPL_AUTHORITY_SEED,
+    },
+    error::TinySplError,
+    program_wrappers::{MplBubblegum, Noop, SplCompression},
+    state::{CombineSession, LeafFormat, TinySplAuthority},
+    utils::{
+        get_tiny_spl_metadata, mint_tiny_spl_to_collection, verify_tree_authority,
+        verify_wallet_allowed, verify_wallet_not_denied, MintTinySplToCollection,
+    },
+};
+
+pub fn close_combine_session(ctx: Context<CloseCombineSession>) -> Result<()> {
+    let new_leaf_owner = get_combined_leaf_owner(
+        &ctx.accounts.combine_session,
+        &ctx.accounts.tiny_spl_authority,
+        &ctx.accounts.authority.key(),
+        ctx.accounts.new_leaf_owner.as_ref().map(|x| x.key()),
+        Clock::get()?.unix_timestamp,
+    )?;
+
+    let total_amount = ctx.accounts.combine_session.total_amount;
+    if total_amount == 0 {
+        return Ok(());
+    }
+
+    verify_tree_authority(&ctx.accounts.merkle_tree, &ctx.accounts.tree_authority)?;
+    // the owner may have been denied or disallowed since the leaves were
+    // absorbed, and the combined leaf is a new leaf like any other
+    verify_wallet_not_denied(
+        &ctx.accounts.tiny_spl_authority,
+        &ctx.accounts.collection_mint.key(),
+        &new_leaf_owner,
+        ctx.accounts.new_leaf_owner_denied_wallet.as_deref(),
+    )?;
+    verify_wallet_allowed(
+        &ctx.accounts.tiny_spl_authority,
+        &ctx.accounts.collection_mint.key(),
+        &new_leaf_owner,
+        ctx.accounts.new_leaf_owner_allowed_wallet.as_deref(),
+    )?;
+    let new_leaf_owner = ctx
+        .accounts
+        .new_leaf_owner
+        .as_ref()
+        .map_or(ctx.accounts.owner.to_account_info(), |x| {
+            x.to_account_info()
+        });
+
+    let mint_pubkey = ctx.accounts.collection_mint.key();
+    let tiny_spl_seeds: &[&[&[u8]]] = &[&[
+        TINY_SPL_AUTHORITY_SEED,
+        mint_pubkey.as_ref(),
+        &[ctx.bumps.tiny_spl_authority],
+    ]];
+    let mint_cpi_context = CpiContext::new_with_signer(
+        ctx.accounts.mpl_bubblegum_program.to_account_info(),
+        MintTinySplToCollection {
+            tree_config: ctx.accounts.tree_authority.to_account_info(),
+            new_leaf_owner: new_leaf_owner.clone(),
+            new_leaf_delegate: new_leaf_owner,
+            merkle_tree: ctx.accounts.merkle_tree.to_account_info(),
+            payer: ctx.accounts.authority.to_account_info(),
+            tree_creator_or_delegate: ctx.accounts.tree_creator_or_delegate.to_account_info(),
+            collection_mint: ctx.accounts.collection_mint.to_account_info(),
+            collection_metadata: ctx.accounts.collection_metadata.to_account_info(),
+            collection_edition: ctx.accounts.edition_account.to_account_info(),
+            bubblegum_signer: ctx.accounts.bubblegum_signer.to_account_info(),
+            tiny_spl_authority: ctx.accounts.tiny_spl_authority.to_account_info(),
+            collection_authority_record: ctx
+                .accounts
+                .collection_authority_record
+                .as_ref()
+                .map(|x| x.to_account_info()),
+            log_wrapper: ctx.accounts.log_wrapper.to_account_info(),
+            compression_program: ctx.accounts.compression_program.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            token_metadata_program: ctx.accounts.token_metadata_program.to_account_info(),
+        },
+        tiny_spl_seeds,
+    );
+    mint_tiny_spl_to_collection(
+        &mint_cpi_context,
+        get_tiny_spl_metadata(
+            &ctx.accounts.leaf_format,
+            total_amount,
+            ctx.accounts.collection_mint.key(),
+            ctx.accounts.tiny_spl_authority.key(),
+        ),
+    )?;
+
+    Ok(())
+}
+
+// the owner can close their session at any time, and anyone can close it to
+// the owner once it expires. the owner may have been denied or disallowed
+// since, so once it expires the mint or compliance authority can also mint
+// the combined leaf to another wallet, or the value would be stuck for good
+fn get_combined_leaf_owner(
+    combine_session: &CombineSession,
+    tiny_spl_authority: &TinySplAuthority,
+    authority: &Pubkey,
+    new_leaf_owner: Option<Pubkey>,
+    now: i64,
+) -> Result<Pubkey> {
+    if new_leaf_owner.is_none() && *authority == combine_session.owner {
+        return Ok(combine_session.owner);
+    }
+
+    let expires_at = combine_session
+        .opened_at
+        .saturating_add(COMBINE_SESSION_TIMEOUT_SECONDS);
+    require!(now >= expires_at, TinySplError::CombineSessionNotExpired);
+
+    match new_leaf_owner {
+        None => Ok(combine_session.owner),
+        Some(new_leaf_owner) => {
+            require!(
+                tiny_spl_authority.mint_authority == Some(*authority)
+                    || tiny_spl_authority.compliance_authority == Some(*authority),
+                TinySplError::CombineSessionRecipientNotAllowed
+            );
+            Ok(new_leaf_owner)
+        }
+    }
+}
+
+#[derive(Accounts)]
+pub struct CloseCombineSession<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(mut)]
+    /// CHECK: checked in combine session seeds
+    pub owner: UncheckedAccount<'info>,
+    /// CHECK: receives the combined leaf instead of the owner, checked in instruction
+    pub new_leaf_owner: Option<UncheckedAccount<'info>>,
+    /// CHECK: This account is checked in instruction
+    pub new_leaf_owner_allowed_wallet: Option<UncheckedAccount<'info>>,
+    /// CHECK: This account is checked in instruction
+    pub new_leaf_owner_denied_wallet: Option<UncheckedAccount<'info>>,
+    #[account(
+        mut,
+        close = owner,
+        seeds = [
+            COMBINE_SESSION_SEED,
+            collection_mint.key().as_ref(),
+            owner.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub combine_session: Account<'info, CombineSession>,
+    #[account(mut)]
+    /// CHECK: checked in cpi to bubblegum
+    pub tree_authority: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: checked in cpi to account compression
+    pub merkle_tree: UncheckedAccount<'info>,
+    /// CHECK: This account is checked in CPI
+    pub tree_creator_or_delegate: UncheckedAccount<'info>,
+    /// CHECK: checked in cpi to bubblegum
+    pub collection_mint: UncheckedAccount<'info>,
+    /// CHECK: checked in accounts constraint
+    #[account(
+        mut,
+        address = mpl_token_metadata::accounts::Metadata::find_pda(&collection_mint.key()).0
+            @ TinySplError::InvalidCollectionMetadata,
+    )]
+    pub collection_metadata: UncheckedAccount<'info>,
+    /// CHECK: checked in accounts constraint
+    #[account(
+        address = mpl_token_metadata::accounts::MasterEdition::find_pda(&collection_mint.key()).0
+            @ TinySplError::InvalidCollectionEdition,
+    )]
+    pub edition_account: UncheckedAccount<'info>,
+    /// CHECK: checked in accounts constraint
+    #[account(
+        address = Pubkey::find_program_address(&[BUBBLEGUM_SIGNER_SEED], &mpl_bubblegum::ID).0
+            @ TinySplError::InvalidBubblegumSigner,
+    )]
+    pub bubblegum_signer: UncheckedAccount<'info>,
+    /// CHECK: checked in cpi to bubblegum
+    #[account(
+        constraint = tiny_spl_authority.collection_authority_record
+            == Some(collection_authority_record.key())
+    )]
+    pub collection_authority_record: Option<UncheckedAccount<'info>>,
+    #[account(
+        seeds = [
+            TINY_SPL_AUTHORITY_SEED,
+            collection_mint.key().as_ref(),
+        ],
+        bump,
+        constraint = tiny_spl_authority.is_verified_tiny_spl_mint
+    )]
+    pub tiny_spl_authority: Box<Account<'info, TinySplAuthority>>,
+    #[account(
+        seeds = [
+            LEAF_FORMAT_SEED,
+            collection_mint.key().as_ref(),
+            &[tiny_spl_authority.leaf_format_version],
+        ],
+        bump,
+    )]
+    pub leaf_format: Box<Account<'info, LeafFormat>>,
+    pub log_wrapper: Program<'info, Noop>,
+    pub compression_program: Program<'info, SplCompression>,
+    pub token_metadata_program: Program<'info, Metadata>,
+    pub system_program: Program<'info, System>,
+    pub mpl_bubblegum_program: Program<'info, MplBubblegum>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OPENED_AT: i64 = 1_700_000_000;
+    const EXPIRES_AT: i64 = OPENED_AT + COMBINE_SESSION_TIMEOUT_SECONDS;
+
+    struct Fixture {
+        combine_session: CombineSession,
+        tiny_spl_authority: TinySplAuthority,
+        mint_authority: Pubkey,
+    }
+
+    fn fixture() -> Fixture {
+        let mint_authority = Pubkey::new_unique();
+        Fixture {
+            combine_session: CombineSession {
+                collection_mint: Pubkey::new_unique(),
+                owner: Pubkey::new_unique(),
+                total_amount: 1_000,
+                opened_at: OPENED_AT,
+                layout_version: 1,
+            },
+            tiny_spl_authority: TinySplAuthority {
+                is_verified_tiny_spl_mint: true,
+                current_supply: 1_000,
+                mint_authority: Some(mint_authority),
+                metadata_authority: Some(mint_authority),
+                leaf_format_version: 0,
+                collection_authority_record: None,
+                twin_mint: None,
+                transfer_hook_program: None,
+                compliance_authority: Some(Pubkey::new_unique()),
+                is_allowlist_enabled: false,
+                is_denylist_enabled: true,
+                layout_version: 1,
+            },
+            mint_authority,
+        }
+    }
+
+    fn assert_error(result: Result<Pubkey>, expected: TinySplError) {
+        match result.unwrap_err() {
+            Error::AnchorError(error) => {
+                assert_eq!(error.error_code_number, u32::from(expected))
+            }
+            error => panic!("unexpected error {error}"),
+        }
+    }
+
+    #[test]
+    fn owner_closes_to_themselves_before_expiry() {
+        let Fixture {
+            combine_session,
+            tiny_spl_authority,
+            ..
+        } = fixture();
+        let owner = combine_session.owner;
+
+        let new_leaf_owner = get_combined_leaf_owner(
+            &combine_session,
+            &tiny_spl_authority,
+            &owner,
+            None,
+            OPENED_AT,
+        )
+        .unwrap();
+
+        assert_eq!(new_leaf_owner, owner);
+    }
+
+    #[test]
+    fn others_close_to_the_owner_only_after_expiry() {
+        let Fixture {
+            combine_session,
+            tiny_spl_authority,
+            ..
+        } = fixture();
+        let stranger = Pubkey::new_unique();
+
+        let early = get_combined_leaf_owner(
+            &combine_session,
+            &tiny_spl_authority,
+            &stranger,
+            None,
+            EXPIRES_AT - 1,
+        );
+        let expired = get_combined_leaf_owner(
+            &combine_session,
+            &tiny_spl_authority,
+            &stranger,
+            None,
+            EXPIRES_AT,
+        );
+
+        assert_error(early, TinySplError::CombineSessionNotExpired);
+        assert_eq!(expired.unwrap(), combine_session.owner);
+    }
+
+    #[test]
+    fn expired_session_of_a_denied_owner_is_recovered_by_the_mint_authority() {
+        let Fixture {
+            combine_session,
+            tiny_spl_authority,
+            mint_authority,
+        } = fixture();
+        let compliance_authority = tiny_spl_authority.compliance_authority.unwrap();
+        let recipient = Pubkey::new_unique();
+
+        for authority in [mint_authority, compliance_authority] {
+            let new_leaf_owner = get_combined_leaf_owner(
+                &combine_session,
+                &tiny_spl_authority,
+                &authority,
+                Some(recipient),
+                EXPIRES_AT,
+            )
+            .unwrap();
+
+            assert_eq!(new_leaf_owner, recipient);
+        }
+    }
+
+    #[test]
+    fn recipient_is_only_picked_by_an_authority_after_expiry() {
+        let Fixture {
+            combine_session,
+            tiny_spl_authority,
+            mint_authority,
+        } = fixture();
+        let recipient = Some(Pubkey::new_unique());
+
+        let early = get_combined_leaf_owner(
+            &combine_session,
+            &tiny_spl_authority,
+            &mint_authority,
+            recipient,
+            EXPIRES_AT - 1,
+        );
+        let by_owner = get_combined_leaf_owner(
+            &combine_session,
+            &tiny_spl_authority,
+            &combine_session.owner,
+            recipient,
+            EXPIRES_AT,
+        );
+
+        assert_error(early, TinySplError::CombineSessionNotExpired);
+        assert_error(by_owner, TinySplError::CombineSessionRecipientNotAllowed);
+    }
+}
@@ -0,0 +1,162 @@
+use anchor_lang::prelude::*;
+use anchor_spl::metadata::{mpl_token_metadata, Metadata};
+
+use crate::{
+    constants::{
+        BUBBLEGUM_SIGNER_SEED, DISTRIBUTION_SESSION_SEED, LEAF_FORMAT_SEED, TINY_SPL_AUTHORITY_SEED,
+    },
+    error::TinySplError,
+    program_wrappers::{MplBubblegum, Noop, SplCompression},
+    state::{DistributionSession, LeafFormat, TinySplAuthority},
+    utils::{
+        get_tiny_spl_metadata, mint_tiny_spl_to_collection, verify_tree_authority,
+        verify_wallet_allowed, verify_wallet_not_denied, MintTinySplToCollection,
+    },
+};
+
+pub fn close_distribution_session(ctx: Context<CloseDistributionSession>) -> Result<()> {
+    // whatever was not distributed is refunded to the owner as a single leaf
+    let remaining_amount = ctx.accounts.distribution_session.remaining_amount;
+    if remaining_amount == 0 {
+        return Ok(());
+    }
+
+    verify_tree_authority(&ctx.accounts.merkle_tree, &ctx.accounts.tree_authority)?;
+    // the owner may have been denied or disallowed since the session was
+    // funded, and the refund is a new leaf like any other
+    verify_wallet_not_denied(
+        &ctx.accounts.tiny_spl_authority,
+        &ctx.accounts.collection_mint.key(),
+        &ctx.accounts.owner.key(),
+        ctx.accounts.owner_denied_wallet.as_deref(),
+    )?;
+    verify_wallet_allowed(
+        &ctx.accounts.tiny_spl_authority,
+        &ctx.accounts.collection_mint.key(),
+        &ctx.accounts.owner.key(),
+        ctx.accounts.owner_allowed_wallet.as_deref(),
+    )?;
+
+    let mint_pubkey = ctx.accounts.collection_mint.key();
+    let tiny_spl_seeds: &[&[&[u8]]] = &[&[
+        TINY_SPL_AUTHORITY_SEED,
+        mint_pubkey.as_ref(),
+        &[ctx.bumps.tiny_spl_authority],
+    ]];
+    let mint_cpi_context = CpiContext::new_with_signer(
+        ctx.accounts.mpl_bubblegum_program.to_account_info(),
+        MintTinySplToCollection {
+            tree_config: ctx.accounts.tree_authority.to_account_info(),
+            new_leaf_owner: ctx.accounts.owner.to_account_info(),
+            new_leaf_delegate: ctx.accounts.owner.to_account_info(),
+            merkle_tree: ctx.accounts.merkle_tree.to_account_info(),
+            payer: ctx.accounts.owner.to_account_info(),
+            tree_creator_or_delegate: ctx.accounts.tree_creator_or_delegate.to_account_info(),
+            collection_mint: ctx.accounts.collection_mint.to_account_info(),
+            collection_metadata: ctx.accounts.collection_metadata.to_account_info(),
+            collection_edition: ctx.accounts.edition_account.to_account_info(),
+            bubblegum_signer: ctx.accounts.bubblegum_signer.to_account_info(),
+            tiny_spl_authority: ctx.accounts.tiny_spl_authority.to_account_info(),
+            collection_authority_record: ctx
+                .accounts
+                .collection_authority_record
+                .as_ref()
+                .map(|x| x.to_account_info()),
+            log_wrapper: ctx.accounts.log_wrapper.to_account_info(),
+            compression_program: ctx.accounts.compression_program.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            token_metadata_program: ctx.accounts.token_metadata_program.to_account_info(),
+        },
+        tiny_spl_seeds,
+    );
+    mint_tiny_spl_to_collection(
+        &mint_cpi_context,
+        get_tiny_spl_metadata(
+            &ctx.accounts.leaf_format,
+            remaining_amount,
+            ctx.accounts.collection_mint.key(),
+            ctx.accounts.tiny_spl_authority.key(),
+        ),
+    )?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CloseDistributionSession<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    /// CHECK: This account is checked in instruction
+    pub owner_allowed_wallet: Option<UncheckedAccount<'info>>,
+    /// CHECK: This account is checked in instruction
+    pub owner_denied_wallet: Option<UncheckedAccount<'info>>,
+    #[account(
+        mut,
+        close = owner,
+        seeds = [
+            DISTRIBUTION_SESSION_SEED,
+            collection_mint.key().as_ref(),
+            owner.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub distribution_session: Account<'info, DistributionSession>,
+    #[account(mut)]
+    /// CHECK: checked in cpi to bubblegum
+    pub tree_authority: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: checked in cpi to account compression
+    pub merkle_tree: UncheckedAccount<'info>,
+    /// CHECK: This account is checked in CPI
+    pub tree_creator_or_delegate: UncheckedAccount<'info>,
+    /// CHECK: checked in cpi to bubblegum
+    pub collection_mint: UncheckedAccount<'info>,
+    /// CHECK: checked in accounts constraint
+    #[account(
+        mut,
+        address = mpl_token_metadata::accounts::Metadata::find_pda(&collection_mint.key()).0
+            @ TinySplError::InvalidCollectionMetadata,
+    )]
+    pub collection_metadata: UncheckedAccount<'info>,
+    /// CHECK: checked in accounts constraint
+    #[account(
+        address = mpl_token_metadata::accounts::MasterEdition::find_pda(&collection_mint.key()).0
+            @ TinySplError::InvalidCollectionEdition,
+    )]
+    pub edition_account: UncheckedAccount<'info>,
+    /// CHECK: checked in accounts constraint
+    #[account(
+        address = Pubkey::find_program_address(&[BUBBLEGUM_SIGNER_SEED], &mpl_bubblegum::ID).0
+            @ TinySplError::InvalidBubblegumSigner,
+    )]
+    pub bubblegum_signer: UncheckedAccount<'info>,
+    /// CHECK: checked in cpi to bubblegum
+    #[account(
+        constraint = tiny_spl_authority.collection_authority_record
+            == Some(collection_authority_record.key())
+    )]
+    pub collection_authority_record: Option<UncheckedAccount<'info>>,
+    #[account(
+        seeds = [
+            TINY_SPL_AUTHORITY_SEED,
+            collection_mint.key().as_ref(),
+        ],
+        bump,
+        constraint = tiny_spl_authority.is_verified_tiny_spl_mint
+    )]
+    pub tiny_spl_authority: Box<Account<'info, TinySplAuthority>>,
+    #[account(
+        seeds = [
+            LEAF_FORMAT_SEED,
+            collection_mint.key().as_ref(),
+            &[tiny_spl_authority.leaf_format_version],
+        ],
+        bump,
+    )]
+    pub leaf_format: Box<Account<'info, LeafFormat>>,
+    pub log_wrapper: Program<'info, Noop>,
+    pub compression_program: Program<'info, SplCompression>,
+    pub token_metadata_program: Program<'info, Metadata>,
+    pub system_program: Program<'info, System>,
+    pub mpl_bubblegum_program: Program<'info, MplBubblegum>,
+}
@@ -1,18 +1,30 @@
-use anchor_lang::prelude::*;
+use anchor_lang::{prelude::*, system_program};
 
-use crate::state::LoggingMetadata;
+use crate::{error::TinySplError, utils::get_logging_metadata_buffer_start};
+
+pub fn close_metadata_account(ctx: Context<CloseMetadataAccount>) -> Result<()> {
+    get_logging_metadata_buffer_start(&ctx.accounts.metadata, ctx.accounts.authority.key)?;
+
+    // closed by hand since `close = authority` only works on a single layout
+    let metadata = ctx.accounts.metadata.to_account_info();
+    let authority = ctx.accounts.authority.to_account_info();
+    let lamports = authority
+        .lamports()
+        .checked_add(metadata.lamports())
+        .ok_or(TinySplError::ArithmeticOverflow)?;
+    **authority.try_borrow_mut_lamports()? = lamports;
+    **metadata.try_borrow_mut_lamports()? = 0;
+    metadata.assign(&system_program::ID);
+    metadata.realloc(0, false)?;
 
-pub fn close_metadata_account(_ctx: Context<CloseMetadataAccount>) -> Result<()> {
     Ok(())
 }
 
 #[derive(Accounts)]
 pub struct CloseMetadataAccount<'info> {
-    #[account(
-        mut,
-        has_one = authority,
-        close = authority
-    )]
-    pub metadata: Account<'info, LoggingMetadata>,
+    /// CHECK: layout and authority checked in instruction
+    #[account(mut, owner = crate::ID)]
+    pub metadata: UncheckedAccount<'info>,
+    #[account(mut)]
     pub authority: Signer<'info>,
 }
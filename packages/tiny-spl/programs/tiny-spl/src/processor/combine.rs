@@ -1,135 +1,121 @@
-use std::collections::HashSet;
-
 use anchor_lang::prelude::*;
 use anchor_spl::metadata::{mpl_token_metadata, Metadata};
 
 use crate::{
-    constants::TINY_SPL_AUTHORITY_SEED,
+    constants::{
+        BUBBLEGUM_SIGNER_SEED, LEAF_FORMAT_SEED, TINY_SPL_AUTHORITY_SEED, TRANSFER_HOOK_SEED,
+    },
     error::TinySplError,
     program_wrappers::{MplBubblegum, Noop, SplCompression},
-    state::TinySplAuthority,
+    state::{LeafFormat, TinySplAuthority, TransferHook},
     utils::{
-        burn_cnft, get_tiny_spl_metadata, mint_tiny_spl_to_collection, verify_cnft_metadata,
-        BurnCnft, MintTinySplToCollection,
+        burn_leaf_batch, execute_transfer_hook, get_tiny_spl_metadata, get_transfer_hook_accounts,
+        mint_tiny_spl_to_collection, resolve_new_leaf_delegate, verify_tree_authority,
+        verify_wallet_allowed, verify_wallet_not_denied, BurnLeafBatch, ExecuteTransferHook,
+        LeafBatch, MintTinySplToCollection, TransferHookArgs, TransferHookDestination,
     },
 };
 
 pub fn combine<'info>(
     ctx: Context<'_, '_, '_, 'info, Combine<'info>>,
-    amounts: Vec<u64>,
-    asset_ids: Vec<Pubkey>,
-    roots: Vec<[u8; 32]>,
-    nonces: Vec<u64>,
-    indexes: Vec<u32>,
-    proof_path_end_indexes_exclusive: Vec<u32>,
+    leaf_batch: LeafBatch,
+    destination_tree_index: Option<u8>,
+    preserve_delegate: bool,
 ) -> Result<()> {
-    require!(
-        amounts.len() == asset_ids.len()
-            && amounts.len() == roots.len()
-            && amounts.len() == nonces.len()
-            && amounts.len() == indexes.len()
-            && amounts.len() == proof_path_end_indexes_exclusive.len(),
-        TinySplError::InvalidCombineParameters
-    );
+    // extra accounts of the mint's transfer hook go after the proof paths
+    let (remaining_accounts, transfer_hook_accounts) = get_transfer_hook_accounts(
+        &ctx.accounts.tiny_spl_authority,
+        ctx.accounts.transfer_hook.as_deref(),
+        ctx.accounts.transfer_hook_program.as_deref(),
+        ctx.remaining_accounts,
+    )?;
 
-    let mut asset_id_set = HashSet::new();
-    asset_ids.iter().for_each(|x| {
-        asset_id_set.insert(x);
-    });
-    require!(
-        asset_id_set.len() == asset_ids.len(),
-        TinySplError::CannotCombineSameAsset
-    );
+    // wallet checks run before anything is burned, like in `distribute`
+    verify_wallet_not_denied(
+        &ctx.accounts.tiny_spl_authority,
+        &ctx.accounts.collection_mint.key(),
+        &ctx.accounts.authority.key(),
+        ctx.accounts.authority_denied_wallet.as_deref(),
+    )?;
+    verify_wallet_not_denied(
+        &ctx.accounts.tiny_spl_authority,
+        &ctx.accounts.collection_mint.key(),
+        &ctx.accounts.leaf_owner.key(),
+        ctx.accounts.leaf_owner_denied_wallet.as_deref(),
+    )?;
+    verify_wallet_not_denied(
+        &ctx.accounts.tiny_spl_authority,
+        &ctx.accounts.collection_mint.key(),
+        &ctx.accounts.new_leaf_owner.key(),
+        ctx.accounts.new_leaf_owner_denied_wallet.as_deref(),
+    )?;
+    verify_wallet_allowed(
+        &ctx.accounts.tiny_spl_authority,
+        &ctx.accounts.collection_mint.key(),
+        &ctx.accounts.new_leaf_owner.key(),
+        ctx.accounts.new_leaf_owner_allowed_wallet.as_deref(),
+    )?;
 
-    let mut burn_cpi_context = CpiContext::new(
+    let burn_cpi_context = CpiContext::new(
         ctx.accounts.mpl_bubblegum_program.to_account_info(),
-        BurnCnft {
-            tree_authority: ctx.accounts.destination_tree_authority.to_account_info(),
+        BurnLeafBatch {
             leaf_owner: ctx.accounts.leaf_owner.to_account_info(),
             leaf_delegate: ctx.accounts.leaf_delegate.to_account_info(),
-            merkle_tree: ctx.accounts.destination_merkle_tree.to_account_info(),
+            collection_mint: ctx.accounts.collection_mint.to_account_info(),
             log_wrapper: ctx.accounts.log_wrapper.to_account_info(),
             compression_program: ctx.accounts.compression_program.to_account_info(),
             system_program: ctx.accounts.system_program.to_account_info(),
         },
-    );
+    )
+    .with_remaining_accounts(remaining_accounts.to_vec());
+    let total_amount = burn_leaf_batch(
+        &burn_cpi_context,
+        &ctx.accounts.tiny_spl_authority,
+        &ctx.accounts.source_leaf_format,
+        &leaf_batch,
+    )?;
 
-    let collection_metadata = mpl_token_metadata::accounts::Metadata::safe_deserialize(
+    let new_leaf_delegate = resolve_new_leaf_delegate(
+        preserve_delegate,
+        &ctx.accounts.leaf_owner.to_account_info(),
+        &ctx.accounts.leaf_delegate.to_account_info(),
+        &ctx.accounts.new_leaf_owner.to_account_info(),
         ctx.accounts
-            .collection_metadata
-            .data
-            .try_borrow()
-            .unwrap()
-            .as_ref(),
+            .new_leaf_delegate
+            .as_ref()
+            .map(|x| x.to_account_info()),
     )?;
-    for (i, asset_id) in asset_ids.iter().enumerate() {
-        let merkle_tree_count: usize = asset_ids.len();
-        let merkle_tree = &ctx.remaining_accounts[i];
-        let tree_authority = &ctx.remaining_accounts[merkle_tree_count + i];
 
-        let proof_path_offset: u32 = merkle_tree_count
-            .checked_mul(2)
-            .unwrap()
-            .try_into()
-            .unwrap();
-        let proof_path_end_index_exclusive = proof_path_offset
-            .checked_add(proof_path_end_indexes_exclusive[i])
-            .unwrap();
-        let proof_path_start_index = if i == 0 {
-            proof_path_offset
-        } else {
-            proof_path_offset
-                .checked_add(proof_path_end_indexes_exclusive[i - 1])
-                .unwrap()
+    // The combined leaf can also be minted into one of the trees of the input
+    // tree table instead of `destination_merkle_tree`. The table was verified
+    // while burning the inputs.
+    let destination_tree = (
+        ctx.accounts.destination_merkle_tree.as_ref(),
+        ctx.accounts.destination_tree_authority.as_ref(),
+    );
+    let (destination_merkle_tree, destination_tree_authority) =
+        match (destination_tree_index, destination_tree) {
+            (Some(tree_index), (None, None)) => {
+                require!(
+                    tree_index < leaf_batch.tree_count,
+                    TinySplError::InvalidDestinationTreeIndex
+                );
+                let tree_index = usize::from(tree_index) * 2;
+                let tree_accounts = remaining_accounts
+                    .get(tree_index..tree_index + 2)
+                    .ok_or(TinySplError::MissingRemainingAccounts)?;
+                (tree_accounts[0].clone(), tree_accounts[1].clone())
+            }
+            (None, (Some(destination_merkle_tree), Some(destination_tree_authority))) => {
+                verify_tree_authority(destination_merkle_tree, destination_tree_authority)?;
+                (
+                    destination_merkle_tree.to_account_info(),
+                    destination_tree_authority.to_account_info(),
+                )
+            }
+            _ => return err!(TinySplError::InvalidDestinationTree),
         };
 
-        let amount = amounts[i];
-        let root = roots[i];
-        let nonce = nonces[i];
-        let index = indexes[i];
-
-        let remaining_accounts = &ctx.remaining_accounts[proof_path_start_index.try_into().unwrap()
-            ..proof_path_end_index_exclusive.try_into().unwrap()];
-
-        let cnft_metadata = get_tiny_spl_metadata(
-            collection_metadata.symbol.clone(),
-            amount,
-            ctx.accounts.collection_mint.key(),
-            ctx.accounts.tiny_spl_authority.key(),
-        );
-
-        let (calculated_asset_id, data_hash, creator_hash) = verify_cnft_metadata(
-            root,
-            &cnft_metadata,
-            nonce,
-            index,
-            merkle_tree,
-            &ctx.accounts.leaf_owner.to_account_info(),
-            &ctx.accounts.leaf_delegate.to_account_info(),
-            &ctx.accounts.collection_mint.to_account_info(),
-            &ctx.accounts.compression_program.to_account_info(),
-            remaining_accounts,
-        )?;
-
-        require!(
-            calculated_asset_id == *asset_id,
-            TinySplError::AssetIdMismatch
-        );
-
-        burn_cpi_context.accounts.merkle_tree = merkle_tree.clone();
-        burn_cpi_context.accounts.tree_authority = tree_authority.clone();
-
-        burn_cnft(
-            &burn_cpi_context,
-            root,
-            data_hash,
-            creator_hash,
-            nonce,
-            index,
-            remaining_accounts,
-        )?;
-    }
-
     let mint_pubkey = ctx.accounts.collection_mint.key();
     let tiny_spl_seeds: &[&[&[u8]]] = &[&[
         TINY_SPL_AUTHORITY_SEED,
@@ -139,9 +125,10 @@ pub fn combine<'info>(
     let mint_cpi_context = CpiContext::new_with_signer(
         ctx.accounts.mpl_bubblegum_program.to_account_info(),
         MintTinySplToCollection {
-            tree_config: ctx.accounts.destination_tree_authority.to_account_info(),
+            tree_config: destination_tree_authority,
             new_leaf_owner: ctx.accounts.new_leaf_owner.to_account_info(),
-            merkle_tree: ctx.accounts.destination_merkle_tree.to_account_info(),
+            new_leaf_delegate,
+            merkle_tree: destination_merkle_tree,
             payer: ctx.accounts.authority.to_account_info(),
             tree_creator_or_delegate: ctx.accounts.tree_creator_or_delegate.to_account_info(),
             collection_mint: ctx.accounts.collection_mint.to_account_info(),
@@ -149,23 +136,48 @@ pub fn combine<'info>(
             collection_edition: ctx.accounts.edition_account.to_account_info(),
             bubblegum_signer: ctx.accounts.bubblegum_signer.to_account_info(),
             tiny_spl_authority: ctx.accounts.tiny_spl_authority.to_account_info(),
+            collection_authority_record: ctx
+                .accounts
+                .collection_authority_record
+                .as_ref()
+                .map(|x| x.to_account_info()),
             log_wrapper: ctx.accounts.log_wrapper.to_account_info(),
             compression_program: ctx.accounts.compression_program.to_account_info(),
             system_program: ctx.accounts.system_program.to_account_info(),
             token_metadata_program: ctx.accounts.token_metadata_program.to_account_info(),
         },
-        &tiny_spl_seeds,
+        tiny_spl_seeds,
     );
 
-    let mut total_amount: u64 = 0;
-    for amount in amounts.iter() {
-        total_amount = total_amount.checked_add(*amount).unwrap();
+    if let (Some(transfer_hook), Some(transfer_hook_program)) = (
+        &ctx.accounts.transfer_hook,
+        &ctx.accounts.transfer_hook_program,
+    ) {
+        let hook_cpi_context = CpiContext::new(
+            transfer_hook_program.to_account_info(),
+            ExecuteTransferHook {
+                tiny_spl_authority: ctx.accounts.tiny_spl_authority.to_account_info(),
+                collection_mint: ctx.accounts.collection_mint.to_account_info(),
+            },
+        )
+        .with_remaining_accounts(transfer_hook_accounts.to_vec());
+        execute_transfer_hook(
+            &hook_cpi_context,
+            transfer_hook,
+            TransferHookArgs {
+                source_owner: ctx.accounts.leaf_owner.key(),
+                destinations: vec![TransferHookDestination {
+                    owner: ctx.accounts.new_leaf_owner.key(),
+                    amount: total_amount,
+                }],
+            },
+        )?;
     }
 
     mint_tiny_spl_to_collection(
         &mint_cpi_context,
         get_tiny_spl_metadata(
-            collection_metadata.symbol,
+            &ctx.accounts.leaf_format,
             total_amount,
             ctx.accounts.collection_mint.key(),
             ctx.accounts.tiny_spl_authority.key(),
@@ -191,15 +203,43 @@ pub struct Combine<'info> {
     pub leaf_delegate: UncheckedAccount<'info>,
     /// CHECK: This account is checked in cpi
     pub new_leaf_owner: UncheckedAccount<'info>,
+    /// CHECK: This account is checked in cpi
+    pub new_leaf_delegate: Option<UncheckedAccount<'info>>,
+    /// CHECK: This account is checked in instruction
+    pub new_leaf_owner_allowed_wallet: Option<UncheckedAccount<'info>>,
+    /// CHECK: This account is checked in instruction
+    pub authority_denied_wallet: Option<UncheckedAccount<'info>>,
+    /// CHECK: This account is checked in instruction
+    pub leaf_owner_denied_wallet: Option<UncheckedAccount<'info>>,
+    /// CHECK: This account is checked in instruction
+    pub new_leaf_owner_denied_wallet: Option<UncheckedAccount<'info>>,
     /// CHECK: checked in cpi to bubblegum
     pub collection_mint: UncheckedAccount<'info>,
-    #[account(mut)]
-    /// CHECK: checked in cpi to bubblegum
+    /// CHECK: checked in accounts constraint
+    #[account(
+        mut,
+        address = mpl_token_metadata::accounts::Metadata::find_pda(&collection_mint.key()).0
+            @ TinySplError::InvalidCollectionMetadata,
+    )]
     pub collection_metadata: UncheckedAccount<'info>,
-    /// CHECK: checked in cpi to bubblegum
+    /// CHECK: checked in accounts constraint
+    #[account(
+        address = mpl_token_metadata::accounts::MasterEdition::find_pda(&collection_mint.key()).0
+            @ TinySplError::InvalidCollectionEdition,
+    )]
     pub edition_account: UncheckedAccount<'info>,
-    /// CHECK: checked in cpi to bubblegum
+    /// CHECK: checked in accounts constraint
+    #[account(
+        address = Pubkey::find_program_address(&[BUBBLEGUM_SIGNER_SEED], &mpl_bubblegum::ID).0
+            @ TinySplError::InvalidBubblegumSigner,
+    )]
     pub bubblegum_signer: UncheckedAccount<'info>,
+    /// CHECK: checked in cpi to bubblegum
+    #[account(
+        constraint = tiny_spl_authority.collection_authority_record
+            == Some(collection_authority_record.key())
+    )]
+    pub collection_authority_record: Option<UncheckedAccount<'info>>,
     #[account(
         seeds = [
             TINY_SPL_AUTHORITY_SEED,
@@ -209,12 +249,45 @@ pub struct Combine<'info> {
         constraint = tiny_spl_authority.is_verified_tiny_spl_mint
     )]
     pub tiny_spl_authority: Box<Account<'info, TinySplAuthority>>,
+    #[account(
+        seeds = [
+            LEAF_FORMAT_SEED,
+            collection_mint.key().as_ref(),
+            &[source_leaf_format.version],
+        ],
+        bump,
+    )]
+    pub source_leaf_format: Box<Account<'info, LeafFormat>>,
+    #[account(
+        seeds = [
+            LEAF_FORMAT_SEED,
+            collection_mint.key().as_ref(),
+            &[tiny_spl_authority.leaf_format_version],
+        ],
+        bump,
+    )]
+    pub leaf_format: Box<Account<'info, LeafFormat>>,
+    // only passed when the combined leaf is not minted into the input tree table
     #[account(mut)]
     /// CHECK: checked in cpi to bubblegum
-    pub destination_tree_authority: UncheckedAccount<'info>,
+    pub destination_tree_authority: Option<UncheckedAccount<'info>>,
     /// CHECK: This account is checked in cpi
     #[account(mut)]
-    pub destination_merkle_tree: UncheckedAccount<'info>,
+    pub destination_merkle_tree: Option<UncheckedAccount<'info>>,
+    #[account(
+        seeds = [
+            TRANSFER_HOOK_SEED,
+            collection_mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub transfer_hook: Option<Box<Account<'info, TransferHook>>>,
+    /// CHECK: checked against the registered transfer hook program
+    #[account(
+        constraint = tiny_spl_authority.transfer_hook_program
+            == Some(transfer_hook_program.key())
+    )]
+    pub transfer_hook_program: Option<UncheckedAccount<'info>>,
     pub log_wrapper: Program<'info, Noop>,
     pub compression_program: Program<'info, SplCompression>,
     pub token_metadata_program: Program<'info, Metadata>,
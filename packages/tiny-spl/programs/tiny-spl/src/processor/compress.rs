@@ -0,0 +1,162 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    metadata::{mpl_token_metadata, Metadata},
+    token_2022::{self, Token2022},
+    token_interface::{Mint, TokenAccount},
+};
+
+use crate::{
+    constants::{BUBBLEGUM_SIGNER_SEED, LEAF_FORMAT_SEED, TINY_SPL_AUTHORITY_SEED},
+    error::TinySplError,
+    program_wrappers::{MplBubblegum, Noop, SplCompression},
+    state::{LeafFormat, TinySplAuthority},
+    utils::{
+        get_tiny_spl_metadata, mint_tiny_spl_to_collection, verify_tree_authority,
+        MintTinySplToCollection,
+    },
+};
+
+pub fn compress(ctx: Context<Compress>, amount: u64) -> Result<()> {
+    require!(amount > 0, TinySplError::InvalidSplitAmounts);
+    verify_tree_authority(&ctx.accounts.merkle_tree, &ctx.accounts.tree_authority)?;
+
+    token_2022::burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token_2022::Burn {
+                mint: ctx.accounts.twin_mint.to_account_info(),
+                from: ctx.accounts.source_token_account.to_account_info(),
+                authority: ctx.accounts.owner.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    let new_leaf_delegate = match &ctx.accounts.new_leaf_delegate {
+        Some(new_leaf_delegate) => new_leaf_delegate.to_account_info(),
+        None => ctx.accounts.new_leaf_owner.to_account_info(),
+    };
+
+    let mint_pubkey = ctx.accounts.collection_mint.key();
+    let tiny_spl_seeds: &[&[&[u8]]] = &[&[
+        TINY_SPL_AUTHORITY_SEED,
+        mint_pubkey.as_ref(),
+        &[ctx.bumps.tiny_spl_authority],
+    ]];
+    let mint_cpi_context = CpiContext::new_with_signer(
+        ctx.accounts.mpl_bubblegum_program.to_account_info(),
+        MintTinySplToCollection {
+            tree_config: ctx.accounts.tree_authority.to_account_info(),
+            new_leaf_owner: ctx.accounts.new_leaf_owner.to_account_info(),
+            new_leaf_delegate,
+            merkle_tree: ctx.accounts.merkle_tree.to_account_info(),
+            payer: ctx.accounts.owner.to_account_info(),
+            tree_creator_or_delegate: ctx.accounts.tree_creator_or_delegate.to_account_info(),
+            collection_mint: ctx.accounts.collection_mint.to_account_info(),
+            collection_metadata: ctx.accounts.collection_metadata.to_account_info(),
+            collection_edition: ctx.accounts.edition_account.to_account_info(),
+            bubblegum_signer: ctx.accounts.bubblegum_signer.to_account_info(),
+            tiny_spl_authority: ctx.accounts.tiny_spl_authority.to_account_info(),
+            collection_authority_record: ctx
+                .accounts
+                .collection_authority_record
+                .as_ref()
+                .map(|x| x.to_account_info()),
+            log_wrapper: ctx.accounts.log_wrapper.to_account_info(),
+            compression_program: ctx.accounts.compression_program.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            token_metadata_program: ctx.accounts.token_metadata_program.to_account_info(),
+        },
+        tiny_spl_seeds,
+    );
+    mint_tiny_spl_to_collection(
+        &mint_cpi_context,
+        get_tiny_spl_metadata(
+            &ctx.accounts.leaf_format,
+            amount,
+            ctx.accounts.collection_mint.key(),
+            ctx.accounts.tiny_spl_authority.key(),
+        ),
+    )?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct Compress<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        mut,
+        token::mint = twin_mint,
+        token::authority = owner,
+        token::token_program = token_program,
+    )]
+    pub source_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub twin_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mut)]
+    /// CHECK: checked in cpi to bubblegum
+    pub tree_authority: UncheckedAccount<'info>,
+    /// CHECK: This account is neither written to nor read from.
+    pub new_leaf_owner: UncheckedAccount<'info>,
+    /// CHECK: This account is neither written to nor read from.
+    pub new_leaf_delegate: Option<UncheckedAccount<'info>>,
+    #[account(mut)]
+    /// CHECK: checked in cpi to account compression
+    pub merkle_tree: UncheckedAccount<'info>,
+    /// CHECK: This account is checked in CPI
+    pub tree_creator_or_delegate: UncheckedAccount<'info>,
+    /// CHECK: checked in cpi to bubblegum
+    pub collection_mint: UncheckedAccount<'info>,
+    /// CHECK: checked in accounts constraint
+    #[account(
+        mut,
+        address = mpl_token_metadata::accounts::Metadata::find_pda(&collection_mint.key()).0
+            @ TinySplError::InvalidCollectionMetadata,
+    )]
+    pub collection_metadata: UncheckedAccount<'info>,
+    /// CHECK: checked in accounts constraint
+    #[account(
+        address = mpl_token_metadata::accounts::MasterEdition::find_pda(&collection_mint.key()).0
+            @ TinySplError::InvalidCollectionEdition,
+    )]
+    pub edition_account: UncheckedAccount<'info>,
+    /// CHECK: checked in accounts constraint
+    #[account(
+        address = Pubkey::find_program_address(&[BUBBLEGUM_SIGNER_SEED], &mpl_bubblegum::ID).0
+            @ TinySplError::InvalidBubblegumSigner,
+    )]
+    pub bubblegum_signer: UncheckedAccount<'info>,
+    /// CHECK: checked in cpi to bubblegum
+    #[account(
+        constraint = tiny_spl_authority.collection_authority_record
+            == Some(collection_authority_record.key())
+    )]
+    pub collection_authority_record: Option<UncheckedAccount<'info>>,
+    #[account(
+        seeds = [
+            TINY_SPL_AUTHORITY_SEED,
+            collection_mint.key().as_ref(),
+        ],
+        bump,
+        constraint = tiny_spl_authority.is_verified_tiny_spl_mint
+            && tiny_spl_authority.twin_mint == Some(twin_mint.key())
+    )]
+    pub tiny_spl_authority: Box<Account<'info, TinySplAuthority>>,
+    #[account(
+        seeds = [
+            LEAF_FORMAT_SEED,
+            collection_mint.key().as_ref(),
+            &[tiny_spl_authority.leaf_format_version],
+        ],
+        bump,
+    )]
+    pub leaf_format: Box<Account<'info, LeafFormat>>,
+    pub token_program: Program<'info, Token2022>,
+    pub log_wrapper: Program<'info, Noop>,
+    pub compression_program: Program<'info, SplCompression>,
+    pub token_metadata_program: Program<'info, Metadata>,
+    pub system_program: Program<'info, System>,
+    pub mpl_bubblegum_program: Program<'info, MplBubblegum>,
+}
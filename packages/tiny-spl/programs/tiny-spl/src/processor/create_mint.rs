@@ -4,14 +4,28 @@ use anchor_lang::{
 };
 use anchor_spl::{
     metadata::{mpl_token_metadata, Metadata},
-    token::Token,
+    token_2022::spl_token_2022::{
+        self,
+        extension::{
+            metadata_pointer::MetadataPointer, BaseStateWithExtensions, StateWithExtensions,
+        },
+    },
+    token_interface::TokenInterface,
 };
 
-use crate::{constants::TINY_SPL_AUTHORITY_SEED, error::TinySplError, state::TinySplAuthority};
+use crate::{
+    constants::{
+        DEFAULT_LEAF_URI_TEMPLATE, LEAF_FORMAT_SEED, MAX_ADDITIONAL_CREATORS,
+        MAX_SELLER_FEE_BASIS_POINTS, TINY_SPL_AUTHORITY_SEED,
+    },
+    error::TinySplError,
+    state::{AdditionalCreator, LeafFormat, LeafNameStyle, TinySplAuthority, VersionedAccount},
+};
 
 pub fn create_mint(
     ctx: Context<CreateMint>,
     create_mint_metadata: CreateMintMetadata,
+    compliance_config: Option<ComplianceConfig>,
 ) -> Result<()> {
     if !ctx.accounts.mint.data_is_empty() {
         return Err(TinySplError::MintAccountNotEmpty.into());
@@ -25,6 +39,31 @@ pub fn create_mint(
         return Err(TinySplError::MasterEditionAccountNotEmpty.into());
     }
 
+    let additional_shares = create_mint_metadata
+        .additional_creators
+        .iter()
+        .try_fold(0u8, |total, creator| total.checked_add(creator.share));
+    require!(
+        create_mint_metadata.additional_creators.len() <= MAX_ADDITIONAL_CREATORS
+            && additional_shares.is_some_and(|shares| shares <= 100)
+            && create_mint_metadata.seller_fee_basis_points <= MAX_SELLER_FEE_BASIS_POINTS,
+        TinySplError::InvalidRoyaltyConfig
+    );
+
+    let leaf_format = &mut ctx.accounts.leaf_format;
+    leaf_format.collection_mint = ctx.accounts.mint.key();
+    leaf_format.version = 0;
+    leaf_format.symbol = create_mint_metadata.symbol.clone();
+    leaf_format.uri_template = DEFAULT_LEAF_URI_TEMPLATE.to_string();
+    leaf_format.name_style = LeafNameStyle::Compact;
+    leaf_format.seller_fee_basis_points = create_mint_metadata.seller_fee_basis_points;
+    leaf_format.primary_sale_happened = create_mint_metadata.primary_sale_happened;
+    leaf_format.is_mutable = create_mint_metadata.is_mutable;
+    leaf_format.additional_creators = create_mint_metadata.additional_creators.clone();
+    leaf_format.layout_version = LeafFormat::LAYOUT_VERSION;
+
+    let is_mutable = create_mint_metadata.is_mutable;
+
     let mint_pubkey = ctx.accounts.mint.key();
     let seeds: &[&[&[u8]]] = &[&[
         TINY_SPL_AUTHORITY_SEED,
@@ -44,14 +83,34 @@ pub fn create_mint(
             spl_token_program: ctx.accounts.spl_token_program.clone(),
             system_program: ctx.accounts.system_program.clone(),
         },
-        &seeds,
+        seeds,
     );
     create_nft_collection(cpi_context, create_mint_metadata)?;
+    if ctx.accounts.spl_token_program.key() == spl_token_2022::ID {
+        verify_metadata_pointer(&ctx.accounts.mint, &ctx.accounts.metadata.key())?;
+    }
 
     let tiny_spl_authority = &mut ctx.accounts.tiny_spl_authority;
     tiny_spl_authority.is_verified_tiny_spl_mint = true;
     tiny_spl_authority.current_supply = 0;
     tiny_spl_authority.mint_authority = Some(*ctx.accounts.mint_authority.key);
+    tiny_spl_authority.metadata_authority = if is_mutable {
+        Some(*ctx.accounts.mint_authority.key)
+    } else {
+        None
+    };
+    tiny_spl_authority.leaf_format_version = 0;
+    tiny_spl_authority.collection_authority_record = None;
+    tiny_spl_authority.twin_mint = None;
+    tiny_spl_authority.transfer_hook_program = None;
+    tiny_spl_authority.compliance_authority = compliance_config.as_ref().map(|x| x.authority);
+    tiny_spl_authority.is_allowlist_enabled = compliance_config
+        .as_ref()
+        .is_some_and(|x| x.is_allowlist_enabled);
+    tiny_spl_authority.is_denylist_enabled = compliance_config
+        .as_ref()
+        .is_some_and(|x| x.is_denylist_enabled);
+    tiny_spl_authority.layout_version = TinySplAuthority::LAYOUT_VERSION;
 
     Ok(())
 }
@@ -60,6 +119,27 @@ fn create_nft_collection<'info>(
     ctx: CpiContext<'_, '_, '_, 'info, CreateNftCollection<'info>>,
     create_mint_metadata: CreateMintMetadata,
 ) -> Result<()> {
+    let additional_shares: u8 = create_mint_metadata
+        .additional_creators
+        .iter()
+        .map(|creator| creator.share)
+        .sum();
+    let mut creators = vec![mpl_token_metadata::types::Creator {
+        address: ctx.accounts.authority.key(),
+        verified: true,
+        share: 100 - additional_shares,
+    }];
+    creators.extend(
+        create_mint_metadata
+            .additional_creators
+            .iter()
+            .map(|creator| mpl_token_metadata::types::Creator {
+                address: creator.address,
+                verified: false,
+                share: creator.share,
+            }),
+    );
+
     let ix = mpl_token_metadata::instructions::Create {
         metadata: *ctx.accounts.metadata.key,
         master_edition: Some(*ctx.accounts.master_edition.key),
@@ -76,14 +156,10 @@ fn create_nft_collection<'info>(
             name: create_mint_metadata.name,
             symbol: create_mint_metadata.symbol,
             uri: create_mint_metadata.uri,
-            seller_fee_basis_points: 0,
-            creators: Some(vec![mpl_token_metadata::types::Creator {
-                address: ctx.accounts.authority.key(),
-                verified: true,
-                share: 100,
-            }]),
-            primary_sale_happened: false,
-            is_mutable: true,
+            seller_fee_basis_points: create_mint_metadata.seller_fee_basis_points,
+            creators: Some(creators),
+            primary_sale_happened: create_mint_metadata.primary_sale_happened,
+            is_mutable: create_mint_metadata.is_mutable,
             token_standard: mpl_token_metadata::types::TokenStandard::NonFungible,
             collection: None,
             uses: None,
@@ -102,6 +178,23 @@ fn create_nft_collection<'info>(
     .map_err(Into::into)
 }
 
+// token metadata creates token 2022 mints with a metadata pointer to their
+// metadata account, which is how token 2022 wallets find the collection
+// metadata. the mint is useless to them without it, so fail instead
+fn verify_metadata_pointer(mint: &AccountInfo, metadata: &Pubkey) -> Result<()> {
+    let mint_data = mint.try_borrow_data()?;
+    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
+    let metadata_pointer = mint
+        .get_extension::<MetadataPointer>()
+        .map_err(|_| TinySplError::InvalidMetadataPointer)?;
+    require!(
+        Option::<Pubkey>::from(metadata_pointer.metadata_address) == Some(*metadata),
+        TinySplError::InvalidMetadataPointer
+    );
+
+    Ok(())
+}
+
 #[derive(Accounts)]
 pub struct CreateMint<'info> {
     #[account(mut)]
@@ -117,7 +210,7 @@ pub struct CreateMint<'info> {
     #[account(
         init,
         payer = mint_authority,
-        space = 8 + (TinySplAuthority::INIT_SPACE * 2),
+        space = TinySplAuthority::SPACE,
         seeds = [
             TINY_SPL_AUTHORITY_SEED,
             mint.key().as_ref(),
@@ -125,11 +218,26 @@ pub struct CreateMint<'info> {
         bump,
     )]
     pub tiny_spl_authority: Box<Account<'info, TinySplAuthority>>,
+    #[account(
+        init,
+        payer = mint_authority,
+        space = LeafFormat::SPACE,
+        seeds = [
+            LEAF_FORMAT_SEED,
+            mint.key().as_ref(),
+            &[0],
+        ],
+        bump,
+    )]
+    pub leaf_format: Box<Account<'info, LeafFormat>>,
     pub system_program: Program<'info, System>,
     /// CHECK: checked in accounts constraint
     #[account(address = sysvar::instructions::id())]
     pub sysvar_instructions: AccountInfo<'info>,
-    pub spl_token_program: Program<'info, Token>,
+    // either spl token or token 2022, token metadata creates the mint under
+    // whichever is passed. token 2022 mints get a metadata pointer extension
+    // to their metadata, which is checked after the cpi
+    pub spl_token_program: Interface<'info, TokenInterface>,
     pub mpl_token_metadata_program: Program<'info, Metadata>,
 }
 
@@ -150,7 +258,14 @@ pub struct CreateNftCollection<'info> {
     /// CHECK: checked in account constraint
     pub sysvar_instructions: AccountInfo<'info>,
     pub system_program: Program<'info, System>,
-    pub spl_token_program: Program<'info, Token>,
+    pub spl_token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ComplianceConfig {
+    authority: Pubkey,
+    is_allowlist_enabled: bool,
+    is_denylist_enabled: bool,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
@@ -158,4 +273,8 @@ pub struct CreateMintMetadata {
     name: String,
     symbol: String,
     uri: String,
+    seller_fee_basis_points: u16,
+    primary_sale_happened: bool,
+    is_mutable: bool,
+    additional_creators: Vec<AdditionalCreator>,
 }
@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{token_2022::Token2022, token_interface::Mint};
+
+use crate::{
+    constants::{TINY_SPL_AUTHORITY_SEED, TWIN_MINT_SEED},
+    error::TinySplError,
+    state::TinySplAuthority,
+};
+
+pub fn create_twin_mint(ctx: Context<CreateTwinMint>) -> Result<()> {
+    // fungible balances cannot be restricted to allowed wallets, kept from
+    // denied wallets or passed through a transfer hook once they leave tiny
+    // spl
+    let tiny_spl_authority = &mut ctx.accounts.tiny_spl_authority;
+    require!(
+        !tiny_spl_authority.is_allowlist_enabled
+            && !tiny_spl_authority.is_denylist_enabled
+            && tiny_spl_authority.transfer_hook_program.is_none(),
+        TinySplError::TwinMintNotAllowed
+    );
+
+    tiny_spl_authority.twin_mint = Some(ctx.accounts.twin_mint.key());
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CreateTwinMint<'info> {
+    #[account(mut)]
+    pub mint_authority: Signer<'info>,
+    /// CHECK: checked in tiny spl authority seeds
+    pub collection_mint: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            TINY_SPL_AUTHORITY_SEED,
+            collection_mint.key().as_ref(),
+        ],
+        bump,
+        constraint = tiny_spl_authority.is_verified_tiny_spl_mint
+            && tiny_spl_authority.mint_authority == Some(mint_authority.key())
+            && tiny_spl_authority.twin_mint.is_none()
+    )]
+    pub tiny_spl_authority: Box<Account<'info, TinySplAuthority>>,
+    #[account(
+        init,
+        payer = mint_authority,
+        seeds = [
+            TWIN_MINT_SEED,
+            collection_mint.key().as_ref(),
+        ],
+        bump,
+        mint::decimals = 0,
+        mint::authority = tiny_spl_authority,
+        mint::token_program = token_program,
+    )]
+    pub twin_mint: Box<InterfaceAccount<'info, Mint>>,
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
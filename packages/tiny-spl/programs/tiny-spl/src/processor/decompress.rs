@@ -0,0 +1,149 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token_2022::{self, Token2022},
+    token_interface::{Mint, TokenAccount},
+};
+
+use crate::{
+    constants::{LEAF_FORMAT_SEED, TINY_SPL_AUTHORITY_SEED},
+    error::TinySplError,
+    program_wrappers::{MplBubblegum, Noop, SplCompression},
+    state::{LeafFormat, TinySplAuthority},
+    utils::{
+        burn_cnft, get_tiny_spl_metadata, hash_cnft_metadata, verify_tree_authority, BurnCnft,
+    },
+};
+
+pub fn decompress<'info>(
+    ctx: Context<'_, '_, '_, 'info, Decompress<'info>>,
+    amount: u64,
+    asset_id: Pubkey,
+    root: [u8; 32],
+    nonce: u64,
+    index: u32,
+) -> Result<()> {
+    verify_tree_authority(
+        &ctx.accounts.source_merkle_tree,
+        &ctx.accounts.source_tree_authority,
+    )?;
+
+    let cnft_metadata = get_tiny_spl_metadata(
+        &ctx.accounts.source_leaf_format,
+        amount,
+        ctx.accounts.collection_mint.key(),
+        ctx.accounts.tiny_spl_authority.key(),
+    );
+
+    let burn_cpi_context = CpiContext::new(
+        ctx.accounts.mpl_bubblegum_program.to_account_info(),
+        BurnCnft {
+            tree_authority: ctx.accounts.source_tree_authority.to_account_info(),
+            leaf_owner: ctx.accounts.leaf_owner.to_account_info(),
+            leaf_delegate: ctx.accounts.leaf_delegate.to_account_info(),
+            merkle_tree: ctx.accounts.source_merkle_tree.to_account_info(),
+            log_wrapper: ctx.accounts.log_wrapper.to_account_info(),
+            compression_program: ctx.accounts.compression_program.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+        },
+    );
+
+    let (calculated_asset_id, data_hash, creator_hash) = hash_cnft_metadata(
+        &cnft_metadata,
+        nonce,
+        &burn_cpi_context.accounts.merkle_tree,
+        &burn_cpi_context.accounts.leaf_owner,
+        &burn_cpi_context.accounts.leaf_delegate,
+        &ctx.accounts.collection_mint,
+    )?;
+    require!(
+        calculated_asset_id == asset_id,
+        TinySplError::AssetIdMismatch
+    );
+
+    burn_cnft(
+        &burn_cpi_context,
+        root,
+        data_hash,
+        creator_hash,
+        nonce,
+        index,
+        ctx.remaining_accounts,
+    )?;
+
+    // the leaf and the fungible tokens are the same supply, so
+    // `current_supply` stays as is
+    let mint_pubkey = ctx.accounts.collection_mint.key();
+    let tiny_spl_seeds: &[&[&[u8]]] = &[&[
+        TINY_SPL_AUTHORITY_SEED,
+        mint_pubkey.as_ref(),
+        &[ctx.bumps.tiny_spl_authority],
+    ]];
+    token_2022::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token_2022::MintTo {
+                mint: ctx.accounts.twin_mint.to_account_info(),
+                to: ctx.accounts.destination_token_account.to_account_info(),
+                authority: ctx.accounts.tiny_spl_authority.to_account_info(),
+            },
+            tiny_spl_seeds,
+        ),
+        amount,
+    )?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct Decompress<'info> {
+    #[account(
+        mut,
+        constraint = leaf_owner.key() == authority.key()
+            || leaf_delegate.key() == authority.key()
+    )]
+    pub authority: Signer<'info>,
+    /// CHECK: This account is checked in instruction
+    pub leaf_owner: UncheckedAccount<'info>,
+    /// CHECK: This account is checked in instruction
+    pub leaf_delegate: UncheckedAccount<'info>,
+    /// CHECK: checked in tiny spl authority seeds
+    pub collection_mint: UncheckedAccount<'info>,
+    #[account(
+        seeds = [
+            TINY_SPL_AUTHORITY_SEED,
+            collection_mint.key().as_ref(),
+        ],
+        bump,
+        constraint = tiny_spl_authority.is_verified_tiny_spl_mint
+            && tiny_spl_authority.twin_mint == Some(twin_mint.key())
+    )]
+    pub tiny_spl_authority: Box<Account<'info, TinySplAuthority>>,
+    #[account(
+        seeds = [
+            LEAF_FORMAT_SEED,
+            collection_mint.key().as_ref(),
+            &[source_leaf_format.version],
+        ],
+        bump,
+    )]
+    pub source_leaf_format: Box<Account<'info, LeafFormat>>,
+    #[account(mut)]
+    /// CHECK: checked in cpi to bubblegum
+    pub source_tree_authority: UncheckedAccount<'info>,
+    /// CHECK: This account is checked in cpi
+    #[account(mut)]
+    pub source_merkle_tree: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub twin_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        token::mint = twin_mint,
+        token::token_program = token_program,
+    )]
+    pub destination_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Program<'info, Token2022>,
+    pub log_wrapper: Program<'info, Noop>,
+    pub compression_program: Program<'info, SplCompression>,
+    pub system_program: Program<'info, System>,
+    pub mpl_bubblegum_program: Program<'info, MplBubblegum>,
+}
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{DENIED_WALLET_SEED, TINY_SPL_AUTHORITY_SEED},
+    state::{DeniedWallet, TinySplAuthority, VersionedAccount},
+};
+
+pub fn deny_wallet(ctx: Context<DenyWallet>, wallet: Pubkey) -> Result<()> {
+    let denied_wallet = &mut ctx.accounts.denied_wallet;
+    denied_wallet.collection_mint = ctx.accounts.collection_mint.key();
+    denied_wallet.wallet = wallet;
+    denied_wallet.layout_version = DeniedWallet::LAYOUT_VERSION;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey)]
+pub struct DenyWallet<'info> {
+    #[account(mut)]
+    pub compliance_authority: Signer<'info>,
+    /// CHECK: checked in tiny spl authority seeds
+    pub collection_mint: UncheckedAccount<'info>,
+    #[account(
+        seeds = [
+            TINY_SPL_AUTHORITY_SEED,
+            collection_mint.key().as_ref(),
+        ],
+        bump,
+        constraint = tiny_spl_authority.is_verified_tiny_spl_mint
+            && tiny_spl_authority.is_denylist_enabled
+            && tiny_spl_authority.compliance_authority == Some(compliance_authority.key())
+    )]
+    pub tiny_spl_authority: Box<Account<'info, TinySplAuthority>>,
+    #[account(
+        init,
+        payer = compliance_authority,
+        space = DeniedWallet::SPACE,
+        seeds = [
+            DENIED_WALLET_SEED,
+            collection_mint.key().as_ref(),
+            wallet.as_ref(),
+        ],
+        bump,
+    )]
+    pub denied_wallet: Account<'info, DeniedWallet>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{ALLOWED_WALLET_SEED, TINY_SPL_AUTHORITY_SEED},
+    state::{AllowedWallet, TinySplAuthority},
+};
+
+pub fn disallow_wallet(_ctx: Context<DisallowWallet>, _wallet: Pubkey) -> Result<()> {
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey)]
+pub struct DisallowWallet<'info> {
+    #[account(mut)]
+    pub compliance_authority: Signer<'info>,
+    /// CHECK: checked in tiny spl authority seeds
+    pub collection_mint: UncheckedAccount<'info>,
+    #[account(
+        seeds = [
+            TINY_SPL_AUTHORITY_SEED,
+            collection_mint.key().as_ref(),
+        ],
+        bump,
+        constraint = tiny_spl_authority.is_verified_tiny_spl_mint
+            && tiny_spl_authority.compliance_authority == Some(compliance_authority.key())
+    )]
+    pub tiny_spl_authority: Box<Account<'info, TinySplAuthority>>,
+    #[account(
+        mut,
+        close = compliance_authority,
+        seeds = [
+            ALLOWED_WALLET_SEED,
+            collection_mint.key().as_ref(),
+            wallet.as_ref(),
+        ],
+        bump,
+    )]
+    pub allowed_wallet: Account<'info, AllowedWallet>,
+}
@@ -0,0 +1,260 @@
+use anchor_lang::prelude::*;
+use anchor_spl::metadata::{mpl_token_metadata, Metadata};
+
+use crate::{
+    constants::{
+        BUBBLEGUM_SIGNER_SEED, DISTRIBUTION_SESSION_SEED, LEAF_FORMAT_SEED,
+        TINY_SPL_AUTHORITY_SEED, TRANSFER_HOOK_SEED,
+    },
+    error::TinySplError,
+    program_wrappers::{MplBubblegum, Noop, SplCompression},
+    state::{DistributionSession, LeafFormat, TinySplAuthority, TransferHook},
+    utils::{
+        execute_transfer_hook, get_transfer_hook_accounts, mint_tiny_spl_to_collection,
+        verify_tree_authority, verify_wallet_allowed, verify_wallet_not_denied,
+        with_heap_checkpoint, ExecuteTransferHook, LeafMetadataTemplate, MintTinySplToCollection,
+        TransferHookArgs, TransferHookDestination,
+    },
+};
+
+pub fn distribute<'info>(
+    ctx: Context<'_, '_, '_, 'info, Distribute<'info>>,
+    amounts: Vec<u64>,
+) -> Result<()> {
+    let mut total_amount: u64 = 0;
+    for amount in amounts.iter() {
+        total_amount = total_amount
+            .checked_add(*amount)
+            .ok_or(TinySplError::ArithmeticOverflow)?;
+    }
+    require!(
+        amounts.iter().all(|&amount| amount > 0)
+            && total_amount <= ctx.accounts.distribution_session.remaining_amount,
+        TinySplError::InvalidSplitAmounts
+    );
+
+    verify_tree_authority(&ctx.accounts.merkle_tree, &ctx.accounts.tree_authority)?;
+    // the session was funded before the owner could have been denied, so the
+    // owner is checked again on every distribution
+    verify_wallet_not_denied(
+        &ctx.accounts.tiny_spl_authority,
+        &ctx.accounts.collection_mint.key(),
+        &ctx.accounts.owner.key(),
+        ctx.accounts.owner_denied_wallet.as_deref(),
+    )?;
+
+    // remaining accounts hold the recipient of every amount, then their allowed
+    // wallet accounts for allowlisted mints, then their denied wallet accounts
+    // for denylisted mints, and finally the extra accounts of the transfer hook
+    let (remaining_accounts, transfer_hook_accounts) = get_transfer_hook_accounts(
+        &ctx.accounts.tiny_spl_authority,
+        ctx.accounts.transfer_hook.as_deref(),
+        ctx.accounts.transfer_hook_program.as_deref(),
+        ctx.remaining_accounts,
+    )?;
+    let recipient_count = amounts.len();
+    let get_wallet_accounts = |section: usize| {
+        remaining_accounts
+            .get(recipient_count * section..recipient_count * (section + 1))
+            .ok_or(TinySplError::InvalidDistributionAccounts)
+    };
+    let recipients = get_wallet_accounts(0)?;
+    let allowed_wallets = if ctx.accounts.tiny_spl_authority.is_allowlist_enabled {
+        get_wallet_accounts(1)?
+    } else {
+        &[]
+    };
+    let denied_wallets = if ctx.accounts.tiny_spl_authority.is_denylist_enabled {
+        get_wallet_accounts(1 + usize::from(ctx.accounts.tiny_spl_authority.is_allowlist_enabled))?
+    } else {
+        &[]
+    };
+
+    for (i, recipient) in recipients.iter().enumerate() {
+        verify_wallet_not_denied(
+            &ctx.accounts.tiny_spl_authority,
+            &ctx.accounts.collection_mint.key(),
+            &recipient.key(),
+            denied_wallets.get(i),
+        )?;
+        verify_wallet_allowed(
+            &ctx.accounts.tiny_spl_authority,
+            &ctx.accounts.collection_mint.key(),
+            &recipient.key(),
+            allowed_wallets.get(i),
+        )?;
+    }
+
+    if let (Some(transfer_hook), Some(transfer_hook_program)) = (
+        &ctx.accounts.transfer_hook,
+        &ctx.accounts.transfer_hook_program,
+    ) {
+        let hook_cpi_context = CpiContext::new(
+            transfer_hook_program.to_account_info(),
+            ExecuteTransferHook {
+                tiny_spl_authority: ctx.accounts.tiny_spl_authority.to_account_info(),
+                collection_mint: ctx.accounts.collection_mint.to_account_info(),
+            },
+        )
+        .with_remaining_accounts(transfer_hook_accounts.to_vec());
+        execute_transfer_hook(
+            &hook_cpi_context,
+            transfer_hook,
+            TransferHookArgs {
+                source_owner: ctx.accounts.owner.key(),
+                destinations: recipients
+                    .iter()
+                    .zip(amounts.iter())
+                    .map(|(recipient, amount)| TransferHookDestination {
+                        owner: recipient.key(),
+                        amount: *amount,
+                    })
+                    .collect(),
+            },
+        )?;
+    }
+
+    let mint_pubkey = ctx.accounts.collection_mint.key();
+    let tiny_spl_seeds: &[&[&[u8]]] = &[&[
+        TINY_SPL_AUTHORITY_SEED,
+        mint_pubkey.as_ref(),
+        &[ctx.bumps.tiny_spl_authority],
+    ]];
+    let mut mint_cpi_context = CpiContext::new_with_signer(
+        ctx.accounts.mpl_bubblegum_program.to_account_info(),
+        MintTinySplToCollection {
+            tree_config: ctx.accounts.tree_authority.to_account_info(),
+            new_leaf_owner: ctx.accounts.owner.to_account_info(),
+            new_leaf_delegate: ctx.accounts.owner.to_account_info(),
+            merkle_tree: ctx.accounts.merkle_tree.to_account_info(),
+            payer: ctx.accounts.owner.to_account_info(),
+            tree_creator_or_delegate: ctx.accounts.tree_creator_or_delegate.to_account_info(),
+            collection_mint: ctx.accounts.collection_mint.to_account_info(),
+            collection_metadata: ctx.accounts.collection_metadata.to_account_info(),
+            collection_edition: ctx.accounts.edition_account.to_account_info(),
+            bubblegum_signer: ctx.accounts.bubblegum_signer.to_account_info(),
+            tiny_spl_authority: ctx.accounts.tiny_spl_authority.to_account_info(),
+            collection_authority_record: ctx
+                .accounts
+                .collection_authority_record
+                .as_ref()
+                .map(|x| x.to_account_info()),
+            log_wrapper: ctx.accounts.log_wrapper.to_account_info(),
+            compression_program: ctx.accounts.compression_program.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            token_metadata_program: ctx.accounts.token_metadata_program.to_account_info(),
+        },
+        tiny_spl_seeds,
+    );
+    let leaf_metadata_template = LeafMetadataTemplate::new(
+        &ctx.accounts.leaf_format,
+        ctx.accounts.collection_mint.key(),
+        ctx.accounts.tiny_spl_authority.key(),
+    );
+    for (recipient, amount) in recipients.iter().zip(amounts) {
+        mint_cpi_context.accounts.new_leaf_owner = recipient.clone();
+        mint_cpi_context.accounts.new_leaf_delegate = recipient.clone();
+
+        with_heap_checkpoint(|| {
+            mint_tiny_spl_to_collection(&mint_cpi_context, leaf_metadata_template.build(amount))
+        })?;
+    }
+
+    let distribution_session = &mut ctx.accounts.distribution_session;
+    distribution_session.remaining_amount = distribution_session
+        .remaining_amount
+        .checked_sub(total_amount)
+        .ok_or(TinySplError::ArithmeticOverflow)?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct Distribute<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    /// CHECK: This account is checked in instruction
+    pub owner_denied_wallet: Option<UncheckedAccount<'info>>,
+    #[account(
+        mut,
+        seeds = [
+            DISTRIBUTION_SESSION_SEED,
+            collection_mint.key().as_ref(),
+            owner.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub distribution_session: Account<'info, DistributionSession>,
+    #[account(mut)]
+    /// CHECK: checked in cpi to bubblegum
+    pub tree_authority: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: checked in cpi to account compression
+    pub merkle_tree: UncheckedAccount<'info>,
+    /// CHECK: This account is checked in CPI
+    pub tree_creator_or_delegate: UncheckedAccount<'info>,
+    /// CHECK: checked in cpi to bubblegum
+    pub collection_mint: UncheckedAccount<'info>,
+    /// CHECK: checked in accounts constraint
+    #[account(
+        mut,
+        address = mpl_token_metadata::accounts::Metadata::find_pda(&collection_mint.key()).0
+            @ TinySplError::InvalidCollectionMetadata,
+    )]
+    pub collection_metadata: UncheckedAccount<'info>,
+    /// CHECK: checked in accounts constraint
+    #[account(
+        address = mpl_token_metadata::accounts::MasterEdition::find_pda(&collection_mint.key()).0
+            @ TinySplError::InvalidCollectionEdition,
+    )]
+    pub edition_account: UncheckedAccount<'info>,
+    /// CHECK: checked in accounts constraint
+    #[account(
+        address = Pubkey::find_program_address(&[BUBBLEGUM_SIGNER_SEED], &mpl_bubblegum::ID).0
+            @ TinySplError::InvalidBubblegumSigner,
+    )]
+    pub bubblegum_signer: UncheckedAccount<'info>,
+    /// CHECK: checked in cpi to bubblegum
+    #[account(
+        constraint = tiny_spl_authority.collection_authority_record
+            == Some(collection_authority_record.key())
+    )]
+    pub collection_authority_record: Option<UncheckedAccount<'info>>,
+    #[account(
+        seeds = [
+            TINY_SPL_AUTHORITY_SEED,
+            collection_mint.key().as_ref(),
+        ],
+        bump,
+        constraint = tiny_spl_authority.is_verified_tiny_spl_mint
+    )]
+    pub tiny_spl_authority: Box<Account<'info, TinySplAuthority>>,
+    #[account(
+        seeds = [
+            LEAF_FORMAT_SEED,
+            collection_mint.key().as_ref(),
+            &[tiny_spl_authority.leaf_format_version],
+        ],
+        bump,
+    )]
+    pub leaf_format: Box<Account<'info, LeafFormat>>,
+    #[account(
+        seeds = [
+            TRANSFER_HOOK_SEED,
+            collection_mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub transfer_hook: Option<Box<Account<'info, TransferHook>>>,
+    /// CHECK: checked against the registered transfer hook program
+    #[account(
+        constraint = tiny_spl_authority.transfer_hook_program
+            == Some(transfer_hook_program.key())
+    )]
+    pub transfer_hook_program: Option<UncheckedAccount<'info>>,
+    pub log_wrapper: Program<'info, Noop>,
+    pub compression_program: Program<'info, SplCompression>,
+    pub token_metadata_program: Program<'info, Metadata>,
+    pub system_program: Program<'info, System>,
+    pub mpl_bubblegum_program: Program<'info, MplBubblegum>,
+}
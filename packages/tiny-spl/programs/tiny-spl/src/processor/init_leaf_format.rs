@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+use anchor_spl::metadata::mpl_token_metadata;
+
+use crate::{
+    constants::{DEFAULT_LEAF_URI_TEMPLATE, LEAF_FORMAT_SEED, TINY_SPL_AUTHORITY_SEED},
+    state::{LeafFormat, LeafNameStyle, TinySplAuthority, VersionedAccount},
+};
+
+// mints created before leaf formats existed had their leaves built from the
+// collection symbol, the default uri, grouped names, no royalties and mutable
+// metadata, which is exactly what version 0 holds. these values must match
+// what the old leaves were minted with byte for byte, or none of them verify
+// again. anyone can create the record because its values are fixed, apart from
+// the symbol, which is read from the collection. `update_collection_metadata`
+// requires this record, so the symbol cannot change before it is taken
+pub fn init_leaf_format(ctx: Context<InitLeafFormat>) -> Result<()> {
+    let collection_metadata = mpl_token_metadata::accounts::Metadata::safe_deserialize(
+        ctx.accounts.collection_metadata.try_borrow_data()?.as_ref(),
+    )?;
+
+    let leaf_format = &mut ctx.accounts.leaf_format;
+    leaf_format.collection_mint = ctx.accounts.collection_mint.key();
+    leaf_format.version = 0;
+    leaf_format.symbol = collection_metadata.symbol.replace("\0", "");
+    leaf_format.uri_template = DEFAULT_LEAF_URI_TEMPLATE.to_string();
+    leaf_format.name_style = LeafNameStyle::Grouped;
+    leaf_format.seller_fee_basis_points = 0;
+    leaf_format.primary_sale_happened = false;
+    leaf_format.is_mutable = true;
+    leaf_format.additional_creators = vec![];
+    leaf_format.layout_version = LeafFormat::LAYOUT_VERSION;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitLeafFormat<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// CHECK: checked in tiny spl authority seeds
+    pub collection_mint: UncheckedAccount<'info>,
+    /// CHECK: checked in accounts constraint
+    #[account(
+        address = mpl_token_metadata::accounts::Metadata::find_pda(&collection_mint.key()).0,
+        owner = mpl_token_metadata::ID,
+    )]
+    pub collection_metadata: UncheckedAccount<'info>,
+    #[account(
+        seeds = [
+            TINY_SPL_AUTHORITY_SEED,
+            collection_mint.key().as_ref(),
+        ],
+        bump,
+        constraint = tiny_spl_authority.is_verified_tiny_spl_mint
+            && tiny_spl_authority.leaf_format_version == 0
+    )]
+    pub tiny_spl_authority: Box<Account<'info, TinySplAuthority>>,
+    #[account(
+        init,
+        payer = payer,
+        space = LeafFormat::SPACE,
+        seeds = [
+            LEAF_FORMAT_SEED,
+            collection_mint.key().as_ref(),
+            &[0],
+        ],
+        bump,
+    )]
+    pub leaf_format: Box<Account<'info, LeafFormat>>,
+    pub system_program: Program<'info, System>,
+}
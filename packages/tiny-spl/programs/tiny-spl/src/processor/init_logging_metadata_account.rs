@@ -1,21 +1,21 @@
-use anchor_lang::{prelude::*, Discriminator};
+use anchor_lang::prelude::*;
 
-use crate::{constants::METADATA_BUFFER_START, state::LoggingMetadata};
+use crate::{constants::METADATA_BUFFER_START, state::VersionedLoggingMetadata};
 
 pub fn init_logging_metadata_account(
     ctx: Context<InitLoggingMetadataAccount>,
     _total_metadata_bytes: u32,
 ) -> Result<()> {
-    let metadata = LoggingMetadata {
+    let metadata = VersionedLoggingMetadata {
         authority: ctx.accounts.authority.key(),
+        layout_version: VersionedLoggingMetadata::LAYOUT_VERSION,
     };
-    let mut struct_data = LoggingMetadata::discriminator().try_to_vec().unwrap();
-    struct_data.append(&mut metadata.try_to_vec().unwrap());
 
     let metadata_account = &mut ctx.accounts.metadata;
 
     let mut account_data = metadata_account.data.borrow_mut();
-    account_data[0..struct_data.len()].copy_from_slice(&struct_data);
+    let mut writer: &mut [u8] = &mut account_data;
+    metadata.try_serialize(&mut writer)?;
 
     Ok(())
 }
@@ -0,0 +1,83 @@
+use anchor_lang::{prelude::*, solana_program::sysvar};
+use anchor_spl::metadata::{mpl_token_metadata, Metadata};
+
+use crate::{
+    constants::TINY_SPL_AUTHORITY_SEED,
+    error::TinySplError,
+    state::TinySplAuthority,
+    utils::{update_collection, UpdateCollection},
+};
+
+pub fn lock_collection_metadata(ctx: Context<LockCollectionMetadata>) -> Result<()> {
+    // tiny spl is not the update authority of adopted collections
+    require!(
+        ctx.accounts
+            .tiny_spl_authority
+            .collection_authority_record
+            .is_none(),
+        TinySplError::CollectionMetadataNotGoverned
+    );
+
+    let mint_pubkey = ctx.accounts.collection_mint.key();
+    let seeds: &[&[&[u8]]] = &[&[
+        TINY_SPL_AUTHORITY_SEED,
+        mint_pubkey.as_ref(),
+        &[ctx.bumps.tiny_spl_authority],
+    ]];
+    let cpi_context = CpiContext::new_with_signer(
+        ctx.accounts.token_metadata_program.to_account_info(),
+        UpdateCollection {
+            authority: ctx.accounts.tiny_spl_authority.to_account_info(),
+            mint: ctx.accounts.collection_mint.to_account_info(),
+            metadata: ctx.accounts.collection_metadata.to_account_info(),
+            edition: ctx.accounts.edition_account.to_account_info(),
+            payer: ctx.accounts.metadata_authority.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            sysvar_instructions: ctx.accounts.sysvar_instructions.clone(),
+        },
+        seeds,
+    );
+    update_collection(&cpi_context, None, Some(false))?;
+
+    // nothing is left to govern once the collection is immutable
+    ctx.accounts.tiny_spl_authority.metadata_authority = None;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct LockCollectionMetadata<'info> {
+    #[account(mut)]
+    pub metadata_authority: Signer<'info>,
+    /// CHECK: checked in cpi to mpl token metadata
+    pub collection_mint: UncheckedAccount<'info>,
+    /// CHECK: checked in accounts constraint
+    #[account(
+        mut,
+        address = mpl_token_metadata::accounts::Metadata::find_pda(&collection_mint.key()).0
+            @ TinySplError::InvalidCollectionMetadata,
+    )]
+    pub collection_metadata: UncheckedAccount<'info>,
+    /// CHECK: checked in accounts constraint
+    #[account(
+        address = mpl_token_metadata::accounts::MasterEdition::find_pda(&collection_mint.key()).0
+            @ TinySplError::InvalidCollectionEdition,
+    )]
+    pub edition_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            TINY_SPL_AUTHORITY_SEED,
+            collection_mint.key().as_ref(),
+        ],
+        bump,
+        constraint = tiny_spl_authority.is_verified_tiny_spl_mint
+            && tiny_spl_authority.metadata_authority == Some(metadata_authority.key())
+    )]
+    pub tiny_spl_authority: Box<Account<'info, TinySplAuthority>>,
+    pub system_program: Program<'info, System>,
+    /// CHECK: checked in accounts constraint
+    #[account(address = sysvar::instructions::id())]
+    pub sysvar_instructions: AccountInfo<'info>,
+    pub token_metadata_program: Program<'info, Metadata>,
+}
@@ -2,30 +2,28 @@ use anchor_lang::prelude::*;
 use anchor_lang::solana_program::program::invoke;
 use anchor_lang::solana_program::pubkey::Pubkey;
 
-use crate::A;
 use crate::program_wrappers::Noop;
-use crate::{constants::METADATA_BUFFER_START, state::LoggingMetadata};
+use crate::utils::{get_logging_metadata_buffer_start, with_heap_checkpoint};
 
 const MAX_CPI_BYTES: usize = 1238;
 
 pub fn log_metadata(ctx: Context<LogMetadata>) -> Result<()> {
+    let buffer_start =
+        get_logging_metadata_buffer_start(&ctx.accounts.metadata, ctx.accounts.authority.key)?;
     let account_info = ctx.accounts.metadata.to_account_info();
     let account_data = account_info.data.borrow();
 
-    for i in (METADATA_BUFFER_START..account_data.len()).step_by(MAX_CPI_BYTES) {
+    for i in (buffer_start..account_data.len()).step_by(MAX_CPI_BYTES) {
         let left = i;
         let right = std::cmp::min(i + MAX_CPI_BYTES, account_data.len());
 
-        let heap_start: usize = unsafe { A.pos() };
-
-        invoke(
-            &spl_noop::instruction(account_data[left..right].to_vec()),
-            &[ctx.accounts.noop_program.to_account_info()],
-        )?;
-
-        unsafe {
-            A.move_cursor(heap_start);
-        }
+        with_heap_checkpoint(|| {
+            invoke(
+                &spl_noop::instruction(account_data[left..right].to_vec()),
+                &[ctx.accounts.noop_program.to_account_info()],
+            )?;
+            Ok(())
+        })?;
     }
 
     Ok(())
@@ -33,10 +31,9 @@ pub fn log_metadata(ctx: Context<LogMetadata>) -> Result<()> {
 
 #[derive(Accounts)]
 pub struct LogMetadata<'info> {
-    #[account(
-        has_one = authority,
-    )]
-    pub metadata: Account<'info, LoggingMetadata>,
+    /// CHECK: layout and authority checked in instruction
+    #[account(owner = crate::ID)]
+    pub metadata: UncheckedAccount<'info>,
     pub authority: Signer<'info>,
     pub noop_program: Program<'info, Noop>,
 }
@@ -0,0 +1,240 @@
+use anchor_lang::{prelude::*, system_program, Discriminator};
+
+use crate::{
+    constants::{LEGACY_METADATA_BUFFER_START, METADATA_BUFFER_START},
+    error::TinySplError,
+    state::{
+        AllowedWallet, CombineSession, DeniedWallet, DistributionSession, LeafFormat,
+        LoggingMetadata, TinySplAuthority, TransferHook, VersionedAccount,
+        VersionedLoggingMetadata,
+    },
+};
+
+// upgrades any tiny spl account to the current layout of its type, growing it
+// first if the new layout needs more space. the upgrade is the same no matter
+// who asks for it, so anyone willing to pay for the extra rent can migrate
+pub fn migrate_account(ctx: Context<MigrateAccount>) -> Result<()> {
+    let discriminator: [u8; 8] = ctx
+        .accounts
+        .account
+        .try_borrow_data()?
+        .get(..8)
+        .and_then(|discriminator| discriminator.try_into().ok())
+        .ok_or(TinySplError::UnsupportedAccountType)?;
+
+    match discriminator {
+        TinySplAuthority::DISCRIMINATOR => migrate_tiny_spl_authority(&ctx),
+        LeafFormat::DISCRIMINATOR => migrate_layout::<LeafFormat>(&ctx),
+        TransferHook::DISCRIMINATOR => migrate_layout::<TransferHook>(&ctx),
+        AllowedWallet::DISCRIMINATOR => migrate_layout::<AllowedWallet>(&ctx),
+        DeniedWallet::DISCRIMINATOR => migrate_layout::<DeniedWallet>(&ctx),
+        CombineSession::DISCRIMINATOR => migrate_layout::<CombineSession>(&ctx),
+        DistributionSession::DISCRIMINATOR => migrate_layout::<DistributionSession>(&ctx),
+        LoggingMetadata::DISCRIMINATOR => migrate_logging_metadata(&ctx),
+        VersionedLoggingMetadata::DISCRIMINATOR => err!(TinySplError::AccountLayoutAlreadyCurrent),
+        _ => err!(TinySplError::UnsupportedAccountType),
+    }
+}
+
+fn migrate_layout<T: VersionedAccount>(ctx: &Context<MigrateAccount>) -> Result<()> {
+    upgrade_layout::<T>(ctx, |_| ())
+}
+
+// mints created before collection metadata could be governed were never given
+// a metadata authority, so upgrading them hands it to their mint authority.
+// this happens once, when the layout version goes from 0 to 1
+fn migrate_tiny_spl_authority(ctx: &Context<MigrateAccount>) -> Result<()> {
+    upgrade_layout::<TinySplAuthority>(ctx, upgrade_tiny_spl_authority)
+}
+
+fn upgrade_tiny_spl_authority(tiny_spl_authority: &mut TinySplAuthority) {
+    if tiny_spl_authority.layout_version == 0 && tiny_spl_authority.metadata_authority.is_none() {
+        tiny_spl_authority.metadata_authority = tiny_spl_authority.mint_authority;
+    }
+}
+
+fn upgrade_layout<T: VersionedAccount>(
+    ctx: &Context<MigrateAccount>,
+    upgrade: impl FnOnce(&mut T),
+) -> Result<()> {
+    // accounts without spare space only fit the version once they are grown
+    grow_account(ctx, T::SPACE)?;
+
+    upgrade_layout_data(&mut ctx.accounts.account.try_borrow_mut_data()?, upgrade)
+}
+
+fn upgrade_layout_data<T: VersionedAccount>(
+    data: &mut [u8],
+    upgrade: impl FnOnce(&mut T),
+) -> Result<()> {
+    let mut account = T::try_deserialize(&mut &data[..])?;
+    require!(
+        *account.layout_version_mut() < T::LAYOUT_VERSION,
+        TinySplError::AccountLayoutAlreadyCurrent
+    );
+    upgrade(&mut account);
+    *account.layout_version_mut() = T::LAYOUT_VERSION;
+
+    let mut writer: &mut [u8] = data;
+    account.try_serialize(&mut writer)
+}
+
+// legacy logging metadata gets the versioned discriminator and a version byte
+// in front of its buffer, so the buffer moves up by one
+fn migrate_logging_metadata(ctx: &Context<MigrateAccount>) -> Result<()> {
+    let legacy_len = ctx.accounts.account.data_len();
+    grow_account(
+        ctx,
+        legacy_len + METADATA_BUFFER_START - LEGACY_METADATA_BUFFER_START,
+    )?;
+
+    shift_logging_metadata(&mut ctx.accounts.account.try_borrow_mut_data()?, legacy_len)
+}
+
+fn shift_logging_metadata(data: &mut [u8], legacy_len: usize) -> Result<()> {
+    let legacy = LoggingMetadata::try_deserialize(&mut &data[..])?;
+    data.copy_within(
+        LEGACY_METADATA_BUFFER_START..legacy_len,
+        METADATA_BUFFER_START,
+    );
+
+    let metadata = VersionedLoggingMetadata {
+        authority: legacy.authority,
+        layout_version: VersionedLoggingMetadata::LAYOUT_VERSION,
+    };
+    let mut writer: &mut [u8] = data;
+    metadata.try_serialize(&mut writer)
+}
+
+fn grow_account(ctx: &Context<MigrateAccount>, space: usize) -> Result<()> {
+    let account = ctx.accounts.account.to_account_info();
+    if account.data_len() >= space {
+        return Ok(());
+    }
+
+    let rent = Rent::get()?
+        .minimum_balance(space)
+        .saturating_sub(account.lamports());
+    if rent > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: account.clone(),
+                },
+            ),
+            rent,
+        )?;
+    }
+    account.realloc(space, true)?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct MigrateAccount<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// CHECK: checked in instruction
+    #[account(mut, owner = crate::ID)]
+    pub account: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // what `create_mint` wrote before layouts were versioned, in an account
+    // already grown to the current space
+    fn legacy_tiny_spl_authority_data(mint_authority: Option<Pubkey>) -> Vec<u8> {
+        let mut data = vec![0; TinySplAuthority::SPACE];
+        (
+            TinySplAuthority::DISCRIMINATOR,
+            true,
+            1_000u64,
+            mint_authority,
+        )
+            .serialize(&mut &mut data[..])
+            .unwrap();
+        data
+    }
+
+    fn upgrade(data: &mut [u8]) -> TinySplAuthority {
+        upgrade_layout_data(data, upgrade_tiny_spl_authority).unwrap();
+        TinySplAuthority::try_deserialize(&mut &data[..]).unwrap()
+    }
+
+    #[test]
+    fn legacy_mint_authority_becomes_metadata_authority() {
+        let mint_authority = Pubkey::new_unique();
+        let mut data = legacy_tiny_spl_authority_data(Some(mint_authority));
+
+        let tiny_spl_authority = upgrade(&mut data);
+
+        assert_eq!(tiny_spl_authority.metadata_authority, Some(mint_authority));
+        assert_eq!(tiny_spl_authority.mint_authority, Some(mint_authority));
+        assert_eq!(tiny_spl_authority.current_supply, 1_000);
+        assert_eq!(
+            tiny_spl_authority.layout_version,
+            TinySplAuthority::LAYOUT_VERSION
+        );
+    }
+
+    #[test]
+    fn legacy_metadata_authority_is_kept() {
+        let metadata_authority = Pubkey::new_unique();
+        let mut data = legacy_tiny_spl_authority_data(Some(Pubkey::new_unique()));
+        let mut tiny_spl_authority = TinySplAuthority::try_deserialize(&mut &data[..]).unwrap();
+        tiny_spl_authority.metadata_authority = Some(metadata_authority);
+        tiny_spl_authority
+            .try_serialize(&mut &mut data[..])
+            .unwrap();
+
+        let tiny_spl_authority = upgrade(&mut data);
+
+        assert_eq!(
+            tiny_spl_authority.metadata_authority,
+            Some(metadata_authority)
+        );
+    }
+
+    #[test]
+    fn current_layout_is_not_migrated_again() {
+        let mut data = legacy_tiny_spl_authority_data(None);
+        upgrade(&mut data);
+
+        let result = upgrade_layout_data(&mut data, upgrade_tiny_spl_authority);
+
+        assert_eq!(
+            result.unwrap_err(),
+            TinySplError::AccountLayoutAlreadyCurrent.into()
+        );
+    }
+
+    #[test]
+    fn logging_metadata_buffer_moves_behind_the_layout_version() {
+        let authority = Pubkey::new_unique();
+        let buffer = br#"{"name":"Tiny SPL"}"#;
+        let mut data = LoggingMetadata::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(authority.as_ref());
+        data.extend_from_slice(buffer);
+        let legacy_len = data.len();
+        // what `grow_account` leaves behind
+        data.resize(
+            legacy_len + METADATA_BUFFER_START - LEGACY_METADATA_BUFFER_START,
+            0,
+        );
+
+        shift_logging_metadata(&mut data, legacy_len).unwrap();
+
+        let metadata = VersionedLoggingMetadata::try_deserialize(&mut &data[..]).unwrap();
+        assert_eq!(metadata.authority, authority);
+        assert_eq!(
+            metadata.layout_version,
+            VersionedLoggingMetadata::LAYOUT_VERSION
+        );
+        assert_eq!(&data[METADATA_BUFFER_START..], buffer);
+    }
+}
@@ -0,0 +1,210 @@
+use anchor_lang::prelude::*;
+use anchor_spl::metadata::{mpl_token_metadata, Metadata};
+
+use crate::{
+    constants::{BUBBLEGUM_SIGNER_SEED, LEAF_FORMAT_SEED, TINY_SPL_AUTHORITY_SEED},
+    error::TinySplError,
+    program_wrappers::{MplBubblegum, Noop, SplCompression},
+    state::{LeafFormat, TinySplAuthority},
+    utils::{
+        burn_cnft, get_tiny_spl_metadata, hash_cnft_metadata, mint_tiny_spl_to_collection,
+        verify_tree_authority, BurnCnft, MintTinySplToCollection,
+    },
+};
+
+pub fn migrate_leaf<'info>(
+    ctx: Context<'_, '_, '_, 'info, MigrateLeaf<'info>>,
+    amount: u64,
+    asset_id: Pubkey,
+    root: [u8; 32],
+    nonce: u64,
+    index: u32,
+) -> Result<()> {
+    require!(
+        ctx.accounts.source_leaf_format.version != ctx.accounts.leaf_format.version,
+        TinySplError::LeafFormatAlreadyCurrent
+    );
+    verify_tree_authority(
+        &ctx.accounts.source_merkle_tree,
+        &ctx.accounts.source_tree_authority,
+    )?;
+    verify_tree_authority(
+        &ctx.accounts.destination_merkle_tree,
+        &ctx.accounts.destination_tree_authority,
+    )?;
+
+    let cnft_metadata = get_tiny_spl_metadata(
+        &ctx.accounts.source_leaf_format,
+        amount,
+        ctx.accounts.collection_mint.key(),
+        ctx.accounts.tiny_spl_authority.key(),
+    );
+
+    let burn_cpi_context = CpiContext::new(
+        ctx.accounts.mpl_bubblegum_program.to_account_info(),
+        BurnCnft {
+            tree_authority: ctx.accounts.source_tree_authority.to_account_info(),
+            leaf_owner: ctx.accounts.leaf_owner.to_account_info(),
+            leaf_delegate: ctx.accounts.leaf_delegate.to_account_info(),
+            merkle_tree: ctx.accounts.source_merkle_tree.to_account_info(),
+            log_wrapper: ctx.accounts.log_wrapper.to_account_info(),
+            compression_program: ctx.accounts.compression_program.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+        },
+    );
+
+    let (calculated_asset_id, data_hash, creator_hash) = hash_cnft_metadata(
+        &cnft_metadata,
+        nonce,
+        &burn_cpi_context.accounts.merkle_tree,
+        &burn_cpi_context.accounts.leaf_owner,
+        &burn_cpi_context.accounts.leaf_delegate,
+        &ctx.accounts.collection_mint,
+    )?;
+    require!(
+        calculated_asset_id == asset_id,
+        TinySplError::AssetIdMismatch
+    );
+
+    burn_cnft(
+        &burn_cpi_context,
+        root,
+        data_hash,
+        creator_hash,
+        nonce,
+        index,
+        ctx.remaining_accounts,
+    )?;
+
+    // the reissued leaf keeps both the owner and the delegate of the old one
+    let mint_pubkey = ctx.accounts.collection_mint.key();
+    let tiny_spl_seeds: &[&[&[u8]]] = &[&[
+        TINY_SPL_AUTHORITY_SEED,
+        mint_pubkey.as_ref(),
+        &[ctx.bumps.tiny_spl_authority],
+    ]];
+    let mint_cpi_context = CpiContext::new_with_signer(
+        ctx.accounts.mpl_bubblegum_program.to_account_info(),
+        MintTinySplToCollection {
+            tree_config: ctx.accounts.destination_tree_authority.to_account_info(),
+            new_leaf_owner: ctx.accounts.leaf_owner.to_account_info(),
+            new_leaf_delegate: ctx.accounts.leaf_delegate.to_account_info(),
+            merkle_tree: ctx.accounts.destination_merkle_tree.to_account_info(),
+            payer: ctx.accounts.authority.to_account_info(),
+            tree_creator_or_delegate: ctx.accounts.tree_creator_or_delegate.to_account_info(),
+            collection_mint: ctx.accounts.collection_mint.to_account_info(),
+            collection_metadata: ctx.accounts.collection_metadata.to_account_info(),
+            collection_edition: ctx.accounts.edition_account.to_account_info(),
+            bubblegum_signer: ctx.accounts.bubblegum_signer.to_account_info(),
+            tiny_spl_authority: ctx.accounts.tiny_spl_authority.to_account_info(),
+            collection_authority_record: ctx
+                .accounts
+                .collection_authority_record
+                .as_ref()
+                .map(|x| x.to_account_info()),
+            log_wrapper: ctx.accounts.log_wrapper.to_account_info(),
+            compression_program: ctx.accounts.compression_program.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            token_metadata_program: ctx.accounts.token_metadata_program.to_account_info(),
+        },
+        tiny_spl_seeds,
+    );
+    mint_tiny_spl_to_collection(
+        &mint_cpi_context,
+        get_tiny_spl_metadata(
+            &ctx.accounts.leaf_format,
+            amount,
+            ctx.accounts.collection_mint.key(),
+            ctx.accounts.tiny_spl_authority.key(),
+        ),
+    )?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct MigrateLeaf<'info> {
+    #[account(
+        mut,
+        constraint = leaf_owner.key() == authority.key()
+            || leaf_delegate.key() == authority.key()
+    )]
+    pub authority: Signer<'info>,
+    /// CHECK: This account is checked in CPI
+    pub tree_creator_or_delegate: UncheckedAccount<'info>,
+    /// CHECK: This account is checked in instruction
+    pub leaf_owner: UncheckedAccount<'info>,
+    /// CHECK: This account is checked in instruction
+    pub leaf_delegate: UncheckedAccount<'info>,
+    /// CHECK: checked in cpi to bubblegum
+    pub collection_mint: UncheckedAccount<'info>,
+    /// CHECK: checked in accounts constraint
+    #[account(
+        mut,
+        address = mpl_token_metadata::accounts::Metadata::find_pda(&collection_mint.key()).0
+            @ TinySplError::InvalidCollectionMetadata,
+    )]
+    pub collection_metadata: UncheckedAccount<'info>,
+    /// CHECK: checked in accounts constraint
+    #[account(
+        address = mpl_token_metadata::accounts::MasterEdition::find_pda(&collection_mint.key()).0
+            @ TinySplError::InvalidCollectionEdition,
+    )]
+    pub edition_account: UncheckedAccount<'info>,
+    /// CHECK: checked in accounts constraint
+    #[account(
+        address = Pubkey::find_program_address(&[BUBBLEGUM_SIGNER_SEED], &mpl_bubblegum::ID).0
+            @ TinySplError::InvalidBubblegumSigner,
+    )]
+    pub bubblegum_signer: UncheckedAccount<'info>,
+    /// CHECK: checked in cpi to bubblegum
+    #[account(
+        constraint = tiny_spl_authority.collection_authority_record
+            == Some(collection_authority_record.key())
+    )]
+    pub collection_authority_record: Option<UncheckedAccount<'info>>,
+    #[account(
+        seeds = [
+            TINY_SPL_AUTHORITY_SEED,
+            collection_mint.key().as_ref(),
+        ],
+        bump,
+        constraint = tiny_spl_authority.is_verified_tiny_spl_mint
+    )]
+    pub tiny_spl_authority: Box<Account<'info, TinySplAuthority>>,
+    #[account(
+        seeds = [
+            LEAF_FORMAT_SEED,
+            collection_mint.key().as_ref(),
+            &[source_leaf_format.version],
+        ],
+        bump,
+    )]
+    pub source_leaf_format: Box<Account<'info, LeafFormat>>,
+    #[account(
+        seeds = [
+            LEAF_FORMAT_SEED,
+            collection_mint.key().as_ref(),
+            &[tiny_spl_authority.leaf_format_version],
+        ],
+        bump,
+    )]
+    pub leaf_format: Box<Account<'info, LeafFormat>>,
+    #[account(mut)]
+    /// CHECK: checked in cpi to bubblegum
+    pub source_tree_authority: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: checked in cpi to bubblegum
+    pub destination_tree_authority: UncheckedAccount<'info>,
+    /// CHECK: This account is checked in cpi
+    #[account(mut)]
+    pub source_merkle_tree: UncheckedAccount<'info>,
+    /// CHECK: This account is checked in cpi
+    #[account(mut)]
+    pub destination_merkle_tree: UncheckedAccount<'info>,
+    pub log_wrapper: Program<'info, Noop>,
+    pub compression_program: Program<'info, SplCompression>,
+    pub token_metadata_program: Program<'info, Metadata>,
+    pub system_program: Program<'info, System>,
+    pub mpl_bubblegum_program: Program<'info, MplBubblegum>,
+}
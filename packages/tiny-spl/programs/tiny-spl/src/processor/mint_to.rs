@@ -2,22 +2,35 @@ use anchor_lang::prelude::*;
 use anchor_spl::metadata::{mpl_token_metadata, Metadata};
 
 use crate::{
-    constants::TINY_SPL_AUTHORITY_SEED,
+    constants::{BUBBLEGUM_SIGNER_SEED, LEAF_FORMAT_SEED, TINY_SPL_AUTHORITY_SEED},
     error::TinySplError,
     program_wrappers::{MplBubblegum, Noop, SplCompression},
-    state::TinySplAuthority,
-    utils::{get_tiny_spl_metadata, mint_tiny_spl_to_collection, MintTinySplToCollection},
+    state::{LeafFormat, TinySplAuthority},
+    utils::{
+        get_tiny_spl_metadata, mint_tiny_spl_to_collection, verify_tree_authority,
+        verify_wallet_allowed, verify_wallet_not_denied, MintTinySplToCollection,
+    },
 };
 
 pub fn mint_to(ctx: Context<MintTo>, amount: u64, max_supply: Option<u64>) -> Result<()> {
-    let collection_metadata = mpl_token_metadata::accounts::Metadata::safe_deserialize(
-        ctx.accounts
-            .collection_metadata
-            .data
-            .try_borrow()
-            .unwrap()
-            .as_ref(),
+    verify_tree_authority(&ctx.accounts.merkle_tree, &ctx.accounts.tree_authority)?;
+    verify_wallet_not_denied(
+        &ctx.accounts.tiny_spl_authority,
+        &ctx.accounts.collection_mint.key(),
+        &ctx.accounts.new_leaf_owner.key(),
+        ctx.accounts.new_leaf_owner_denied_wallet.as_deref(),
     )?;
+    verify_wallet_allowed(
+        &ctx.accounts.tiny_spl_authority,
+        &ctx.accounts.collection_mint.key(),
+        &ctx.accounts.new_leaf_owner.key(),
+        ctx.accounts.new_leaf_owner_allowed_wallet.as_deref(),
+    )?;
+
+    let new_leaf_delegate = match &ctx.accounts.new_leaf_delegate {
+        Some(new_leaf_delegate) => new_leaf_delegate.to_account_info(),
+        None => ctx.accounts.new_leaf_owner.to_account_info(),
+    };
 
     let mint_pubkey = ctx.accounts.collection_mint.key();
     let seeds: &[&[&[u8]]] = &[&[
@@ -30,6 +43,7 @@ pub fn mint_to(ctx: Context<MintTo>, amount: u64, max_supply: Option<u64>) -> Re
         MintTinySplToCollection {
             tree_config: ctx.accounts.tree_authority.to_account_info(),
             new_leaf_owner: ctx.accounts.new_leaf_owner.to_account_info(),
+            new_leaf_delegate,
             merkle_tree: ctx.accounts.merkle_tree.to_account_info(),
             payer: ctx.accounts.mint_authority.to_account_info(),
             tree_creator_or_delegate: ctx.accounts.tree_creator_or_delegate.to_account_info(),
@@ -38,18 +52,23 @@ pub fn mint_to(ctx: Context<MintTo>, amount: u64, max_supply: Option<u64>) -> Re
             collection_edition: ctx.accounts.edition_account.to_account_info(),
             bubblegum_signer: ctx.accounts.bubblegum_signer.to_account_info(),
             tiny_spl_authority: ctx.accounts.tiny_spl_authority.to_account_info(),
+            collection_authority_record: ctx
+                .accounts
+                .collection_authority_record
+                .as_ref()
+                .map(|x| x.to_account_info()),
             log_wrapper: ctx.accounts.log_wrapper.to_account_info(),
             compression_program: ctx.accounts.compression_program.to_account_info(),
             system_program: ctx.accounts.system_program.to_account_info(),
             token_metadata_program: ctx.accounts.token_metadata_program.to_account_info(),
         },
-        &seeds,
+        seeds,
     );
 
     mint_tiny_spl_to_collection(
         &cpi_context,
         get_tiny_spl_metadata(
-            collection_metadata.symbol,
+            &ctx.accounts.leaf_format,
             amount,
             ctx.accounts.collection_mint.key(),
             ctx.accounts.tiny_spl_authority.key(),
@@ -60,16 +79,13 @@ pub fn mint_to(ctx: Context<MintTo>, amount: u64, max_supply: Option<u64>) -> Re
     tiny_spl_authority.current_supply = tiny_spl_authority
         .current_supply
         .checked_add(amount)
-        .unwrap();
+        .ok_or(TinySplError::ArithmeticOverflow)?;
 
-    match max_supply {
-        Some(max_supply) => {
-            require!(
-                tiny_spl_authority.current_supply <= max_supply,
-                TinySplError::ExceededMaxMintSupply
-            )
-        }
-        None => (),
+    if let Some(max_supply) = max_supply {
+        require!(
+            tiny_spl_authority.current_supply <= max_supply,
+            TinySplError::ExceededMaxMintSupply
+        );
     }
 
     Ok(())
@@ -81,6 +97,12 @@ pub struct MintTo<'info> {
     pub tree_authority: UncheckedAccount<'info>,
     /// CHECK: This account is neither written to nor read from.
     pub new_leaf_owner: UncheckedAccount<'info>,
+    /// CHECK: This account is neither written to nor read from.
+    pub new_leaf_delegate: Option<UncheckedAccount<'info>>,
+    /// CHECK: This account is checked in instruction
+    pub new_leaf_owner_allowed_wallet: Option<UncheckedAccount<'info>>,
+    /// CHECK: This account is checked in instruction
+    pub new_leaf_owner_denied_wallet: Option<UncheckedAccount<'info>>,
     #[account(mut)]
     /// CHECK: checked in cpi to account compression
     pub merkle_tree: UncheckedAccount<'info>,
@@ -89,13 +111,31 @@ pub struct MintTo<'info> {
     pub tree_creator_or_delegate: UncheckedAccount<'info>,
     /// CHECK: checked in cpi to bubblegum
     pub collection_mint: UncheckedAccount<'info>,
-    #[account(mut)]
-    /// CHECK: checked in cpi to bubblegum
+    /// CHECK: checked in accounts constraint
+    #[account(
+        mut,
+        address = mpl_token_metadata::accounts::Metadata::find_pda(&collection_mint.key()).0
+            @ TinySplError::InvalidCollectionMetadata,
+    )]
     pub collection_metadata: UncheckedAccount<'info>,
-    /// CHECK: checked in cpi to bubblegum
+    /// CHECK: checked in accounts constraint
+    #[account(
+        address = mpl_token_metadata::accounts::MasterEdition::find_pda(&collection_mint.key()).0
+            @ TinySplError::InvalidCollectionEdition,
+    )]
     pub edition_account: UncheckedAccount<'info>,
-    /// CHECK: checked in cpi to bubblegum
+    /// CHECK: checked in accounts constraint
+    #[account(
+        address = Pubkey::find_program_address(&[BUBBLEGUM_SIGNER_SEED], &mpl_bubblegum::ID).0
+            @ TinySplError::InvalidBubblegumSigner,
+    )]
     pub bubblegum_signer: UncheckedAccount<'info>,
+    /// CHECK: checked in cpi to bubblegum
+    #[account(
+        constraint = tiny_spl_authority.collection_authority_record
+            == Some(collection_authority_record.key())
+    )]
+    pub collection_authority_record: Option<UncheckedAccount<'info>>,
     #[account(
         mut,
         seeds = [
@@ -107,6 +147,15 @@ pub struct MintTo<'info> {
             && tiny_spl_authority.mint_authority == Some(mint_authority.key())
     )]
     pub tiny_spl_authority: Box<Account<'info, TinySplAuthority>>,
+    #[account(
+        seeds = [
+            LEAF_FORMAT_SEED,
+            collection_mint.key().as_ref(),
+            &[tiny_spl_authority.leaf_format_version],
+        ],
+        bump,
+    )]
+    pub leaf_format: Box<Account<'info, LeafFormat>>,
     pub log_wrapper: Program<'info, Noop>,
     pub compression_program: Program<'info, SplCompression>,
     pub token_metadata_program: Program<'info, Metadata>,
@@ -6,6 +6,29 @@ mod log_metadata;
 mod close_metadata_account;
 mod split;
 mod combine;
+mod update_collection_metadata;
+mod lock_collection_metadata;
+mod transfer_metadata_authority;
+mod init_leaf_format;
+mod set_leaf_format;
+mod migrate_leaf;
+mod adopt_collection;
+mod create_twin_mint;
+mod decompress;
+mod compress;
+mod set_transfer_hook;
+mod allow_wallet;
+mod disallow_wallet;
+mod deny_wallet;
+mod undeny_wallet;
+mod open_combine_session;
+mod absorb;
+mod close_combine_session;
+mod open_distribution_session;
+mod fund_distribution_session;
+mod distribute;
+mod close_distribution_session;
+mod migrate_account;
 
 pub use create_mint::*;
 pub use upload_logging_metadata::*;
@@ -15,3 +38,26 @@ pub use log_metadata::*;
 pub use close_metadata_account::*;
 pub use split::*;
 pub use combine::*;
+pub use update_collection_metadata::*;
+pub use lock_collection_metadata::*;
+pub use transfer_metadata_authority::*;
+pub use init_leaf_format::*;
+pub use set_leaf_format::*;
+pub use migrate_leaf::*;
+pub use adopt_collection::*;
+pub use create_twin_mint::*;
+pub use decompress::*;
+pub use compress::*;
+pub use set_transfer_hook::*;
+pub use allow_wallet::*;
+pub use disallow_wallet::*;
+pub use deny_wallet::*;
+pub use undeny_wallet::*;
+pub use open_combine_session::*;
+pub use absorb::*;
+pub use close_combine_session::*;
+pub use open_distribution_session::*;
+pub use fund_distribution_session::*;
+pub use distribute::*;
+pub use close_distribution_session::*;
+pub use migrate_account::*;
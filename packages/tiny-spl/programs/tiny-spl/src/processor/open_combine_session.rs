@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{COMBINE_SESSION_SEED, TINY_SPL_AUTHORITY_SEED},
+    state::{CombineSession, TinySplAuthority, VersionedAccount},
+};
+
+pub fn open_combine_session(ctx: Context<OpenCombineSession>) -> Result<()> {
+    let combine_session = &mut ctx.accounts.combine_session;
+    combine_session.collection_mint = ctx.accounts.collection_mint.key();
+    combine_session.owner = ctx.accounts.owner.key();
+    combine_session.total_amount = 0;
+    combine_session.opened_at = Clock::get()?.unix_timestamp;
+    combine_session.layout_version = CombineSession::LAYOUT_VERSION;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct OpenCombineSession<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    /// CHECK: checked in tiny spl authority seeds
+    pub collection_mint: UncheckedAccount<'info>,
+    #[account(
+        seeds = [
+            TINY_SPL_AUTHORITY_SEED,
+            collection_mint.key().as_ref(),
+        ],
+        bump,
+        constraint = tiny_spl_authority.is_verified_tiny_spl_mint
+    )]
+    pub tiny_spl_authority: Box<Account<'info, TinySplAuthority>>,
+    #[account(
+        init,
+        payer = owner,
+        space = CombineSession::SPACE,
+        seeds = [
+            COMBINE_SESSION_SEED,
+            collection_mint.key().as_ref(),
+            owner.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub combine_session: Account<'info, CombineSession>,
+    pub system_program: Program<'info, System>,
+}
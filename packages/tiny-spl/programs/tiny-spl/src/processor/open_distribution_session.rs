@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{DISTRIBUTION_SESSION_SEED, TINY_SPL_AUTHORITY_SEED},
+    state::{DistributionSession, TinySplAuthority, VersionedAccount},
+};
+
+pub fn open_distribution_session(ctx: Context<OpenDistributionSession>) -> Result<()> {
+    let distribution_session = &mut ctx.accounts.distribution_session;
+    distribution_session.collection_mint = ctx.accounts.collection_mint.key();
+    distribution_session.owner = ctx.accounts.owner.key();
+    distribution_session.remaining_amount = 0;
+    distribution_session.layout_version = DistributionSession::LAYOUT_VERSION;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct OpenDistributionSession<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    /// CHECK: checked in tiny spl authority seeds
+    pub collection_mint: UncheckedAccount<'info>,
+    #[account(
+        seeds = [
+            TINY_SPL_AUTHORITY_SEED,
+            collection_mint.key().as_ref(),
+        ],
+        bump,
+        constraint = tiny_spl_authority.is_verified_tiny_spl_mint
+    )]
+    pub tiny_spl_authority: Box<Account<'info, TinySplAuthority>>,
+    #[account(
+        init,
+        payer = owner,
+        space = DistributionSession::SPACE,
+        seeds = [
+            DISTRIBUTION_SESSION_SEED,
+            collection_mint.key().as_ref(),
+            owner.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub distribution_session: Account<'info, DistributionSession>,
+    pub system_program: Program<'info, System>,
+}
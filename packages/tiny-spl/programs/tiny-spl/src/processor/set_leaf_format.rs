@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{LEAF_FORMAT_SEED, MAX_SYMBOL_LENGTH, TINY_SPL_AUTHORITY_SEED},
+    error::TinySplError,
+    state::{LeafFormat, LeafNameStyle, TinySplAuthority, VersionedAccount},
+    utils::is_valid_uri_template,
+};
+
+pub fn set_leaf_format(
+    ctx: Context<SetLeafFormat>,
+    version: u8,
+    symbol: String,
+    uri_template: String,
+    name_style: LeafNameStyle,
+) -> Result<()> {
+    require!(
+        Some(version)
+            == ctx
+                .accounts
+                .tiny_spl_authority
+                .leaf_format_version
+                .checked_add(1),
+        TinySplError::InvalidLeafFormatVersion
+    );
+    require!(
+        symbol.len() <= MAX_SYMBOL_LENGTH
+            && is_valid_uri_template(&uri_template, ctx.accounts.collection_mint.key),
+        TinySplError::InvalidLeafFormat
+    );
+
+    let leaf_format = &mut ctx.accounts.leaf_format;
+    leaf_format.collection_mint = ctx.accounts.collection_mint.key();
+    leaf_format.version = version;
+    leaf_format.symbol = symbol;
+    leaf_format.uri_template = uri_template;
+    leaf_format.name_style = name_style;
+    leaf_format.seller_fee_basis_points = ctx.accounts.current_leaf_format.seller_fee_basis_points;
+    leaf_format.primary_sale_happened = ctx.accounts.current_leaf_format.primary_sale_happened;
+    leaf_format.is_mutable = ctx.accounts.current_leaf_format.is_mutable;
+    leaf_format.additional_creators = ctx.accounts.current_leaf_format.additional_creators.clone();
+    leaf_format.layout_version = LeafFormat::LAYOUT_VERSION;
+
+    ctx.accounts.tiny_spl_authority.leaf_format_version = version;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(version: u8)]
+pub struct SetLeafFormat<'info> {
+    #[account(mut)]
+    pub metadata_authority: Signer<'info>,
+    /// CHECK: checked in tiny spl authority seeds
+    pub collection_mint: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            TINY_SPL_AUTHORITY_SEED,
+            collection_mint.key().as_ref(),
+        ],
+        bump,
+        constraint = tiny_spl_authority.is_verified_tiny_spl_mint
+            && tiny_spl_authority.metadata_authority == Some(metadata_authority.key())
+    )]
+    pub tiny_spl_authority: Box<Account<'info, TinySplAuthority>>,
+    // royalties and creators are fixed at mint creation and carry over
+    #[account(
+        seeds = [
+            LEAF_FORMAT_SEED,
+            collection_mint.key().as_ref(),
+            &[tiny_spl_authority.leaf_format_version],
+        ],
+        bump,
+    )]
+    pub current_leaf_format: Box<Account<'info, LeafFormat>>,
+    #[account(
+        init,
+        payer = metadata_authority,
+        space = LeafFormat::SPACE,
+        seeds = [
+            LEAF_FORMAT_SEED,
+            collection_mint.key().as_ref(),
+            &[version],
+        ],
+        bump,
+    )]
+    pub leaf_format: Box<Account<'info, LeafFormat>>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{MAX_TRANSFER_HOOK_EXTRA_ACCOUNTS, TINY_SPL_AUTHORITY_SEED, TRANSFER_HOOK_SEED},
+    error::TinySplError,
+    state::{TinySplAuthority, TransferHook, TransferHookAccount, VersionedAccount},
+};
+
+pub fn set_transfer_hook(
+    ctx: Context<SetTransferHook>,
+    program_id: Option<Pubkey>,
+    extra_accounts: Vec<TransferHookAccount>,
+) -> Result<()> {
+    require!(
+        extra_accounts.len() <= MAX_TRANSFER_HOOK_EXTRA_ACCOUNTS,
+        TinySplError::TooManyTransferHookAccounts
+    );
+    // twin mint balances move without calling the hook, so a hook registered
+    // after the twin mint could be skipped by decompressing and compressing
+    require!(
+        program_id.is_none() || ctx.accounts.tiny_spl_authority.twin_mint.is_none(),
+        TinySplError::TransferHookNotAllowed
+    );
+
+    let transfer_hook = &mut ctx.accounts.transfer_hook;
+    transfer_hook.collection_mint = ctx.accounts.collection_mint.key();
+    transfer_hook.program_id = program_id.unwrap_or_default();
+    transfer_hook.extra_accounts = extra_accounts;
+    transfer_hook.layout_version = TransferHook::LAYOUT_VERSION;
+
+    ctx.accounts.tiny_spl_authority.transfer_hook_program = program_id;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetTransferHook<'info> {
+    #[account(mut)]
+    pub mint_authority: Signer<'info>,
+    /// CHECK: checked in tiny spl authority seeds
+    pub collection_mint: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            TINY_SPL_AUTHORITY_SEED,
+            collection_mint.key().as_ref(),
+        ],
+        bump,
+        constraint = tiny_spl_authority.is_verified_tiny_spl_mint
+            && tiny_spl_authority.mint_authority == Some(mint_authority.key())
+    )]
+    pub tiny_spl_authority: Box<Account<'info, TinySplAuthority>>,
+    #[account(
+        init_if_needed,
+        payer = mint_authority,
+        space = TransferHook::SPACE,
+        seeds = [
+            TRANSFER_HOOK_SEED,
+            collection_mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub transfer_hook: Box<Account<'info, TransferHook>>,
+    pub system_program: Program<'info, System>,
+}
@@ -2,16 +2,33 @@ use anchor_lang::prelude::*;
 use anchor_spl::metadata::{mpl_token_metadata, Metadata};
 
 use crate::{
-    constants::TINY_SPL_AUTHORITY_SEED,
+    constants::{
+        BUBBLEGUM_SIGNER_SEED, LEAF_FORMAT_SEED, TINY_SPL_AUTHORITY_SEED, TRANSFER_HOOK_SEED,
+    },
     error::TinySplError,
     program_wrappers::{MplBubblegum, Noop, SplCompression},
-    state::TinySplAuthority,
+    state::{LeafFormat, TinySplAuthority, TransferHook},
     utils::{
-        burn_cnft, get_tiny_spl_metadata, mint_tiny_spl_to_collection, verify_cnft_metadata,
-        verify_token_splits, BurnCnft, MintTinySplToCollection,
+        burn_cnft, execute_transfer_hook, get_tiny_spl_metadata, get_transfer_hook_accounts,
+        get_tree_table, hash_cnft_metadata, mint_tiny_spl_to_collection, resolve_new_leaf_delegate,
+        verify_token_splits, verify_tree_authority, verify_wallet_allowed,
+        verify_wallet_not_denied, with_heap_checkpoint, BurnCnft, ExecuteTransferHook,
+        LeafMetadataTemplate, MintTinySplToCollection, TransferHookArgs, TransferHookDestination,
+        TreeTableEntry,
     },
 };
 
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SplitOutputs {
+    pub amounts: Vec<u64>,
+    // index into the destination tree table of every output, or empty to mint
+    // every output into `destination_merkle_tree`
+    pub tree_indexes: Vec<u8>,
+    // trees of the table that follow `destination_merkle_tree`
+    pub extra_tree_count: u8,
+    pub preserve_delegate: bool,
+}
+
 pub fn split<'info>(
     ctx: Context<'_, '_, '_, 'info, Split<'info>>,
     source_amount: u64,
@@ -19,42 +36,85 @@ pub fn split<'info>(
     root: [u8; 32],
     nonce: u64,
     index: u32,
-    destination_amounts: Vec<u64>,
+    outputs: SplitOutputs,
 ) -> Result<()> {
+    let SplitOutputs {
+        amounts: destination_amounts,
+        tree_indexes: destination_tree_indexes,
+        extra_tree_count,
+        preserve_delegate,
+    } = outputs;
     verify_token_splits(source_amount, &destination_amounts)?;
+    require!(
+        destination_tree_indexes.is_empty()
+            || destination_tree_indexes.len() == destination_amounts.len(),
+        TinySplError::InvalidDestinationTreeIndex
+    );
+    require!(
+        destination_tree_indexes
+            .iter()
+            .all(|tree_index| *tree_index <= extra_tree_count),
+        TinySplError::InvalidDestinationTreeIndex
+    );
 
-    let collection_metadata = mpl_token_metadata::accounts::Metadata::safe_deserialize(
-        ctx.accounts
-            .collection_metadata
-            .data
-            .try_borrow()
-            .unwrap()
-            .as_ref(),
+    let (remaining_accounts, transfer_hook_accounts) = get_transfer_hook_accounts(
+        &ctx.accounts.tiny_spl_authority,
+        ctx.accounts.transfer_hook.as_deref(),
+        ctx.accounts.transfer_hook_program.as_deref(),
+        ctx.remaining_accounts,
+    )?;
+
+    // Tree index 0 is `destination_merkle_tree`, any further destination trees are
+    // passed as (merkle_tree, tree_authority) pairs in front of the proof path.
+    let (extra_destination_trees, proof_path) =
+        get_tree_table(remaining_accounts, extra_tree_count.into())?;
+    verify_tree_authority(
+        &ctx.accounts.source_merkle_tree,
+        &ctx.accounts.source_tree_authority,
+    )?;
+    verify_tree_authority(
+        &ctx.accounts.destination_merkle_tree,
+        &ctx.accounts.destination_tree_authority,
     )?;
+    let mut destination_trees = vec![TreeTableEntry {
+        merkle_tree: ctx.accounts.destination_merkle_tree.to_account_info(),
+        tree_authority: ctx.accounts.destination_tree_authority.to_account_info(),
+    }];
+    destination_trees.extend(extra_destination_trees);
+
+    // wallet checks run before anything is burned, like in `distribute`
+    verify_wallet_not_denied(
+        &ctx.accounts.tiny_spl_authority,
+        &ctx.accounts.collection_mint.key(),
+        &ctx.accounts.authority.key(),
+        ctx.accounts.authority_denied_wallet.as_deref(),
+    )?;
+    verify_wallet_not_denied(
+        &ctx.accounts.tiny_spl_authority,
+        &ctx.accounts.collection_mint.key(),
+        &ctx.accounts.leaf_owner.key(),
+        ctx.accounts.leaf_owner_denied_wallet.as_deref(),
+    )?;
+    verify_wallet_not_denied(
+        &ctx.accounts.tiny_spl_authority,
+        &ctx.accounts.collection_mint.key(),
+        &ctx.accounts.new_leaf_owner.key(),
+        ctx.accounts.new_leaf_owner_denied_wallet.as_deref(),
+    )?;
+    verify_wallet_allowed(
+        &ctx.accounts.tiny_spl_authority,
+        &ctx.accounts.collection_mint.key(),
+        &ctx.accounts.new_leaf_owner.key(),
+        ctx.accounts.new_leaf_owner_allowed_wallet.as_deref(),
+    )?;
+
     let cnft_metadata = get_tiny_spl_metadata(
-        collection_metadata.symbol.clone(),
+        &ctx.accounts.source_leaf_format,
         source_amount,
         ctx.accounts.collection_mint.key(),
         ctx.accounts.tiny_spl_authority.key(),
     );
 
-    let (calculated_asset_id, data_hash, creator_hash) = verify_cnft_metadata(
-        root,
-        &cnft_metadata,
-        nonce,
-        index,
-        &ctx.accounts.source_merkle_tree.to_account_info(),
-        &ctx.accounts.leaf_owner.to_account_info(),
-        &ctx.accounts.leaf_delegate.to_account_info(),
-        &ctx.accounts.collection_mint.to_account_info(),
-        &ctx.accounts.compression_program.to_account_info(),
-        &ctx.remaining_accounts,
-    )?;
-    require!(
-        calculated_asset_id == asset_id,
-        TinySplError::AssetIdMismatch
-    );
-
     let burn_cpi_context = CpiContext::new(
         ctx.accounts.mpl_bubblegum_program.to_account_info(),
         BurnCnft {
@@ -67,6 +127,20 @@ pub fn split<'info>(
             system_program: ctx.accounts.system_program.to_account_info(),
         },
     );
+
+    let (calculated_asset_id, data_hash, creator_hash) = hash_cnft_metadata(
+        &cnft_metadata,
+        nonce,
+        &burn_cpi_context.accounts.merkle_tree,
+        &burn_cpi_context.accounts.leaf_owner,
+        &burn_cpi_context.accounts.leaf_delegate,
+        &ctx.accounts.collection_mint,
+    )?;
+    require!(
+        calculated_asset_id == asset_id,
+        TinySplError::AssetIdMismatch
+    );
+
     burn_cnft(
         &burn_cpi_context,
         root,
@@ -74,7 +148,18 @@ pub fn split<'info>(
         creator_hash,
         nonce,
         index,
-        ctx.remaining_accounts,
+        proof_path,
+    )?;
+
+    let new_leaf_delegate = resolve_new_leaf_delegate(
+        preserve_delegate,
+        &ctx.accounts.leaf_owner.to_account_info(),
+        &ctx.accounts.leaf_delegate.to_account_info(),
+        &ctx.accounts.new_leaf_owner.to_account_info(),
+        ctx.accounts
+            .new_leaf_delegate
+            .as_ref()
+            .map(|x| x.to_account_info()),
     )?;
 
     let mint_pubkey = ctx.accounts.collection_mint.key();
@@ -83,11 +168,12 @@ pub fn split<'info>(
         mint_pubkey.as_ref(),
         &[ctx.bumps.tiny_spl_authority],
     ]];
-    let mint_cpi_context = CpiContext::new_with_signer(
+    let mut mint_cpi_context = CpiContext::new_with_signer(
         ctx.accounts.mpl_bubblegum_program.to_account_info(),
         MintTinySplToCollection {
             tree_config: ctx.accounts.destination_tree_authority.to_account_info(),
             new_leaf_owner: ctx.accounts.new_leaf_owner.to_account_info(),
+            new_leaf_delegate,
             merkle_tree: ctx.accounts.destination_merkle_tree.to_account_info(),
             payer: ctx.accounts.authority.to_account_info(),
             tree_creator_or_delegate: ctx.accounts.tree_creator_or_delegate.to_account_info(),
@@ -96,24 +182,61 @@ pub fn split<'info>(
             collection_edition: ctx.accounts.edition_account.to_account_info(),
             bubblegum_signer: ctx.accounts.bubblegum_signer.to_account_info(),
             tiny_spl_authority: ctx.accounts.tiny_spl_authority.to_account_info(),
+            collection_authority_record: ctx
+                .accounts
+                .collection_authority_record
+                .as_ref()
+                .map(|x| x.to_account_info()),
             log_wrapper: ctx.accounts.log_wrapper.to_account_info(),
             compression_program: ctx.accounts.compression_program.to_account_info(),
             system_program: ctx.accounts.system_program.to_account_info(),
             token_metadata_program: ctx.accounts.token_metadata_program.to_account_info(),
         },
-        &tiny_spl_seeds,
+        tiny_spl_seeds,
     );
-    for amount in destination_amounts {
-        mint_tiny_spl_to_collection(
-            &mint_cpi_context,
-            get_tiny_spl_metadata(
-                collection_metadata.symbol.clone(),
-                amount,
-                ctx.accounts.collection_mint.key(),
-                ctx.accounts.tiny_spl_authority.key(),
-            ),
+    if let (Some(transfer_hook), Some(transfer_hook_program)) = (
+        &ctx.accounts.transfer_hook,
+        &ctx.accounts.transfer_hook_program,
+    ) {
+        let hook_cpi_context = CpiContext::new(
+            transfer_hook_program.to_account_info(),
+            ExecuteTransferHook {
+                tiny_spl_authority: ctx.accounts.tiny_spl_authority.to_account_info(),
+                collection_mint: ctx.accounts.collection_mint.to_account_info(),
+            },
+        )
+        .with_remaining_accounts(transfer_hook_accounts.to_vec());
+        execute_transfer_hook(
+            &hook_cpi_context,
+            transfer_hook,
+            TransferHookArgs {
+                source_owner: ctx.accounts.leaf_owner.key(),
+                destinations: destination_amounts
+                    .iter()
+                    .map(|amount| TransferHookDestination {
+                        owner: ctx.accounts.new_leaf_owner.key(),
+                        amount: *amount,
+                    })
+                    .collect(),
+            },
         )?;
     }
+
+    let leaf_metadata_template = LeafMetadataTemplate::new(
+        &ctx.accounts.leaf_format,
+        ctx.accounts.collection_mint.key(),
+        ctx.accounts.tiny_spl_authority.key(),
+    );
+    for (i, amount) in destination_amounts.into_iter().enumerate() {
+        let tree_index = destination_tree_indexes.get(i).copied().unwrap_or(0);
+        let destination_tree = &destination_trees[usize::from(tree_index)];
+        mint_cpi_context.accounts.merkle_tree = destination_tree.merkle_tree.clone();
+        mint_cpi_context.accounts.tree_config = destination_tree.tree_authority.clone();
+
+        with_heap_checkpoint(|| {
+            mint_tiny_spl_to_collection(&mint_cpi_context, leaf_metadata_template.build(amount))
+        })?;
+    }
     Ok(())
 }
 
@@ -133,15 +256,43 @@ pub struct Split<'info> {
     pub leaf_delegate: UncheckedAccount<'info>,
     /// CHECK: This account is checked in cpi
     pub new_leaf_owner: UncheckedAccount<'info>,
+    /// CHECK: This account is checked in cpi
+    pub new_leaf_delegate: Option<UncheckedAccount<'info>>,
+    /// CHECK: This account is checked in instruction
+    pub new_leaf_owner_allowed_wallet: Option<UncheckedAccount<'info>>,
+    /// CHECK: This account is checked in instruction
+    pub authority_denied_wallet: Option<UncheckedAccount<'info>>,
+    /// CHECK: This account is checked in instruction
+    pub leaf_owner_denied_wallet: Option<UncheckedAccount<'info>>,
+    /// CHECK: This account is checked in instruction
+    pub new_leaf_owner_denied_wallet: Option<UncheckedAccount<'info>>,
     /// CHECK: checked in cpi to bubblegum
     pub collection_mint: UncheckedAccount<'info>,
-    #[account(mut)]
-    /// CHECK: checked in cpi to bubblegum
+    /// CHECK: checked in accounts constraint
+    #[account(
+        mut,
+        address = mpl_token_metadata::accounts::Metadata::find_pda(&collection_mint.key()).0
+            @ TinySplError::InvalidCollectionMetadata,
+    )]
     pub collection_metadata: UncheckedAccount<'info>,
-    /// CHECK: checked in cpi to bubblegum
+    /// CHECK: checked in accounts constraint
+    #[account(
+        address = mpl_token_metadata::accounts::MasterEdition::find_pda(&collection_mint.key()).0
+            @ TinySplError::InvalidCollectionEdition,
+    )]
     pub edition_account: UncheckedAccount<'info>,
-    /// CHECK: checked in cpi to bubblegum
+    /// CHECK: checked in accounts constraint
+    #[account(
+        address = Pubkey::find_program_address(&[BUBBLEGUM_SIGNER_SEED], &mpl_bubblegum::ID).0
+            @ TinySplError::InvalidBubblegumSigner,
+    )]
     pub bubblegum_signer: UncheckedAccount<'info>,
+    /// CHECK: checked in cpi to bubblegum
+    #[account(
+        constraint = tiny_spl_authority.collection_authority_record
+            == Some(collection_authority_record.key())
+    )]
+    pub collection_authority_record: Option<UncheckedAccount<'info>>,
     #[account(
         seeds = [
             TINY_SPL_AUTHORITY_SEED,
@@ -151,6 +302,24 @@ pub struct Split<'info> {
         constraint = tiny_spl_authority.is_verified_tiny_spl_mint
     )]
     pub tiny_spl_authority: Box<Account<'info, TinySplAuthority>>,
+    #[account(
+        seeds = [
+            LEAF_FORMAT_SEED,
+            collection_mint.key().as_ref(),
+            &[source_leaf_format.version],
+        ],
+        bump,
+    )]
+    pub source_leaf_format: Box<Account<'info, LeafFormat>>,
+    #[account(
+        seeds = [
+            LEAF_FORMAT_SEED,
+            collection_mint.key().as_ref(),
+            &[tiny_spl_authority.leaf_format_version],
+        ],
+        bump,
+    )]
+    pub leaf_format: Box<Account<'info, LeafFormat>>,
     #[account(mut)]
     /// CHECK: checked in cpi to bubblegum
     pub source_tree_authority: UncheckedAccount<'info>,
@@ -163,6 +332,20 @@ pub struct Split<'info> {
     /// CHECK: This account is checked in cpi
     #[account(mut)]
     pub destination_merkle_tree: UncheckedAccount<'info>,
+    #[account(
+        seeds = [
+            TRANSFER_HOOK_SEED,
+            collection_mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub transfer_hook: Option<Box<Account<'info, TransferHook>>>,
+    /// CHECK: checked against the registered transfer hook program
+    #[account(
+        constraint = tiny_spl_authority.transfer_hook_program
+            == Some(transfer_hook_program.key())
+    )]
+    pub transfer_hook_program: Option<UncheckedAccount<'info>>,
     pub log_wrapper: Program<'info, Noop>,
     pub compression_program: Program<'info, SplCompression>,
     pub token_metadata_program: Program<'info, Metadata>,
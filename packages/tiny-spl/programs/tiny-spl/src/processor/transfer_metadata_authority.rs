@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+
+use crate::{constants::TINY_SPL_AUTHORITY_SEED, state::TinySplAuthority};
+
+pub fn transfer_metadata_authority(
+    ctx: Context<TransferMetadataAuthority>,
+    new_metadata_authority: Option<Pubkey>,
+) -> Result<()> {
+    ctx.accounts.tiny_spl_authority.metadata_authority = new_metadata_authority;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct TransferMetadataAuthority<'info> {
+    pub metadata_authority: Signer<'info>,
+    /// CHECK: checked in tiny spl authority seeds
+    pub collection_mint: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [
+            TINY_SPL_AUTHORITY_SEED,
+            collection_mint.key().as_ref(),
+        ],
+        bump,
+        constraint = tiny_spl_authority.is_verified_tiny_spl_mint
+            && tiny_spl_authority.metadata_authority == Some(metadata_authority.key())
+    )]
+    pub tiny_spl_authority: Box<Account<'info, TinySplAuthority>>,
+}
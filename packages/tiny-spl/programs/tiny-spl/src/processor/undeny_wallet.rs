@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{DENIED_WALLET_SEED, TINY_SPL_AUTHORITY_SEED},
+    state::{DeniedWallet, TinySplAuthority},
+};
+
+pub fn undeny_wallet(_ctx: Context<UndenyWallet>, _wallet: Pubkey) -> Result<()> {
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey)]
+pub struct UndenyWallet<'info> {
+    #[account(mut)]
+    pub compliance_authority: Signer<'info>,
+    /// CHECK: checked in tiny spl authority seeds
+    pub collection_mint: UncheckedAccount<'info>,
+    #[account(
+        seeds = [
+            TINY_SPL_AUTHORITY_SEED,
+            collection_mint.key().as_ref(),
+        ],
+        bump,
+        constraint = tiny_spl_authority.is_verified_tiny_spl_mint
+            && tiny_spl_authority.compliance_authority == Some(compliance_authority.key())
+    )]
+    pub tiny_spl_authority: Box<Account<'info, TinySplAuthority>>,
+    #[account(
+        mut,
+        close = compliance_authority,
+        seeds = [
+            DENIED_WALLET_SEED,
+            collection_mint.key().as_ref(),
+            wallet.as_ref(),
+        ],
+        bump,
+    )]
+    pub denied_wallet: Account<'info, DeniedWallet>,
+}
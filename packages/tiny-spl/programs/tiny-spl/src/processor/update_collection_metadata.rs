@@ -0,0 +1,117 @@
+use anchor_lang::{prelude::*, solana_program::sysvar};
+use anchor_spl::metadata::{mpl_token_metadata, Metadata};
+
+use crate::{
+    constants::{LEAF_FORMAT_SEED, TINY_SPL_AUTHORITY_SEED},
+    error::TinySplError,
+    state::{LeafFormat, TinySplAuthority},
+    utils::{update_collection, UpdateCollection},
+};
+
+pub fn update_collection_metadata(
+    ctx: Context<UpdateCollectionMetadata>,
+    update_collection_metadata_args: UpdateCollectionMetadataArgs,
+) -> Result<()> {
+    // tiny spl is not the update authority of adopted collections
+    require!(
+        ctx.accounts
+            .tiny_spl_authority
+            .collection_authority_record
+            .is_none(),
+        TinySplError::CollectionMetadataNotGoverned
+    );
+
+    let collection_metadata = mpl_token_metadata::accounts::Metadata::safe_deserialize(
+        ctx.accounts.collection_metadata.try_borrow_data()?.as_ref(),
+    )?;
+    let data = mpl_token_metadata::types::Data {
+        name: update_collection_metadata_args
+            .name
+            .unwrap_or(collection_metadata.name.replace("\0", "")),
+        symbol: update_collection_metadata_args
+            .symbol
+            .unwrap_or(collection_metadata.symbol.replace("\0", "")),
+        uri: update_collection_metadata_args
+            .uri
+            .unwrap_or(collection_metadata.uri.replace("\0", "")),
+        seller_fee_basis_points: collection_metadata.seller_fee_basis_points,
+        creators: collection_metadata.creators,
+    };
+
+    let mint_pubkey = ctx.accounts.collection_mint.key();
+    let seeds: &[&[&[u8]]] = &[&[
+        TINY_SPL_AUTHORITY_SEED,
+        mint_pubkey.as_ref(),
+        &[ctx.bumps.tiny_spl_authority],
+    ]];
+    let cpi_context = CpiContext::new_with_signer(
+        ctx.accounts.token_metadata_program.to_account_info(),
+        UpdateCollection {
+            authority: ctx.accounts.tiny_spl_authority.to_account_info(),
+            mint: ctx.accounts.collection_mint.to_account_info(),
+            metadata: ctx.accounts.collection_metadata.to_account_info(),
+            edition: ctx.accounts.edition_account.to_account_info(),
+            payer: ctx.accounts.metadata_authority.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            sysvar_instructions: ctx.accounts.sysvar_instructions.clone(),
+        },
+        seeds,
+    );
+    update_collection(&cpi_context, Some(data), None)
+}
+
+#[derive(Accounts)]
+pub struct UpdateCollectionMetadata<'info> {
+    #[account(mut)]
+    pub metadata_authority: Signer<'info>,
+    /// CHECK: checked in cpi to mpl token metadata
+    pub collection_mint: UncheckedAccount<'info>,
+    /// CHECK: checked in accounts constraint
+    #[account(
+        mut,
+        address = mpl_token_metadata::accounts::Metadata::find_pda(&collection_mint.key()).0
+            @ TinySplError::InvalidCollectionMetadata,
+    )]
+    pub collection_metadata: UncheckedAccount<'info>,
+    /// CHECK: checked in accounts constraint
+    #[account(
+        address = mpl_token_metadata::accounts::MasterEdition::find_pda(&collection_mint.key()).0
+            @ TinySplError::InvalidCollectionEdition,
+    )]
+    pub edition_account: UncheckedAccount<'info>,
+    #[account(
+        seeds = [
+            TINY_SPL_AUTHORITY_SEED,
+            collection_mint.key().as_ref(),
+        ],
+        bump,
+        constraint = tiny_spl_authority.is_verified_tiny_spl_mint
+            && tiny_spl_authority.metadata_authority == Some(metadata_authority.key())
+    )]
+    pub tiny_spl_authority: Box<Account<'info, TinySplAuthority>>,
+    // version 0 snapshots the collection symbol, so it has to exist before
+    // the symbol can change
+    #[account(
+        seeds = [
+            LEAF_FORMAT_SEED,
+            collection_mint.key().as_ref(),
+            &[0],
+        ],
+        bump,
+    )]
+    pub leaf_format: Box<Account<'info, LeafFormat>>,
+    pub system_program: Program<'info, System>,
+    /// CHECK: checked in accounts constraint
+    #[account(address = sysvar::instructions::id())]
+    pub sysvar_instructions: AccountInfo<'info>,
+    pub token_metadata_program: Program<'info, Metadata>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct UpdateCollectionMetadataArgs {
+    name: Option<String>,
+    // leaves keep verifying against their leaf format, so the collection
+    // symbol can change without touching existing balances
+    symbol: Option<String>,
+    uri: Option<String>,
+}
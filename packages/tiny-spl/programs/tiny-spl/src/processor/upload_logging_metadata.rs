@@ -1,4 +1,4 @@
-use crate::{constants::METADATA_BUFFER_START, state::LoggingMetadata};
+use crate::{error::TinySplError, utils::get_logging_metadata_buffer_start};
 use anchor_lang::prelude::*;
 
 pub fn upload_logging_metadata(
@@ -6,20 +6,27 @@ pub fn upload_logging_metadata(
     index: u32,
     bytes: Vec<u8>,
 ) -> Result<()> {
+    let buffer_start =
+        get_logging_metadata_buffer_start(&ctx.accounts.metadata, ctx.accounts.authority.key)?;
     let account_info = ctx.accounts.metadata.to_account_info();
     let mut account_data = account_info.data.borrow_mut();
-    account_data[METADATA_BUFFER_START + (index as usize)
-        ..METADATA_BUFFER_START + (index as usize) + bytes.len()]
+    let start = buffer_start
+        .checked_add(index as usize)
+        .ok_or(TinySplError::UploadOutOfBounds)?;
+    let end = start
+        .checked_add(bytes.len())
+        .ok_or(TinySplError::UploadOutOfBounds)?;
+    account_data
+        .get_mut(start..end)
+        .ok_or(TinySplError::UploadOutOfBounds)?
         .copy_from_slice(&bytes);
     Ok(())
 }
 
 #[derive(Accounts)]
 pub struct UploadLoggingMetadata<'info> {
-    #[account(
-        mut,
-        has_one = authority,
-    )]
-    pub metadata: Account<'info, LoggingMetadata>,
+    /// CHECK: layout and authority checked in instruction
+    #[account(mut, owner = crate::ID)]
+    pub metadata: UncheckedAccount<'info>,
     pub authority: Signer<'info>,
 }
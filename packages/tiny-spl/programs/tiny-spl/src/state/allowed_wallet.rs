@@ -0,0 +1,10 @@
+use anchor_lang::prelude::*;
+
+// a wallet may receive leaves of an allowlisted mint while this account exists
+#[account]
+#[derive(InitSpace)]
+pub struct AllowedWallet {
+    pub collection_mint: Pubkey,
+    pub wallet: Pubkey,
+    pub layout_version: u8,
+}
@@ -0,0 +1,13 @@
+use anchor_lang::prelude::*;
+
+// running total of leaves burnt across several transactions, minted back to
+// the owner as a single leaf when the session is closed
+#[account]
+#[derive(InitSpace)]
+pub struct CombineSession {
+    pub collection_mint: Pubkey,
+    pub owner: Pubkey,
+    pub total_amount: u64,
+    pub opened_at: i64,
+    pub layout_version: u8,
+}
@@ -0,0 +1,11 @@
+use anchor_lang::prelude::*;
+
+// a wallet cannot send or receive leaves of a denylisted mint while this
+// account exists
+#[account]
+#[derive(InitSpace)]
+pub struct DeniedWallet {
+    pub collection_mint: Pubkey,
+    pub wallet: Pubkey,
+    pub layout_version: u8,
+}
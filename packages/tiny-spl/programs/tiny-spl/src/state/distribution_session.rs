@@ -0,0 +1,12 @@
+use anchor_lang::prelude::*;
+
+// balance burnt out of the owner's leaves and paid out in batches of new
+// leaves across several transactions
+#[account]
+#[derive(InitSpace)]
+pub struct DistributionSession {
+    pub collection_mint: Pubkey,
+    pub owner: Pubkey,
+    pub remaining_amount: u64,
+    pub layout_version: u8,
+}
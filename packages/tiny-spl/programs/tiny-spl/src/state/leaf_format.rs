@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+// snapshot of everything that goes into the metadata of a leaf, so leaves
+// minted under an older format can still be verified after it changes
+#[account]
+#[derive(InitSpace)]
+pub struct LeafFormat {
+    pub collection_mint: Pubkey,
+    pub version: u8,
+    #[max_len(10)]
+    pub symbol: String,
+    // `{collection_mint}` and `{amount}` are substituted when building a leaf
+    #[max_len(200)]
+    pub uri_template: String,
+    pub name_style: LeafNameStyle,
+    pub seller_fee_basis_points: u16,
+    pub primary_sale_happened: bool,
+    pub is_mutable: bool,
+    // the tiny spl authority is always the first, verified creator and gets
+    // whatever share is not taken by these
+    #[max_len(4)]
+    pub additional_creators: Vec<AdditionalCreator>,
+    pub layout_version: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub struct AdditionalCreator {
+    pub address: Pubkey,
+    pub share: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum LeafNameStyle {
+    // `18,446,744,073,709,551,615 SYMBOL`, can overflow bubblegum's 32 byte name
+    Grouped,
+    // `18.4Q SYMBOL`, always fits since the exact amount is in the uri anyway
+    Compact,
+}
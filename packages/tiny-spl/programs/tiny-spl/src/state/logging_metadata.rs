@@ -1,7 +1,21 @@
 use anchor_lang::prelude::*;
 
+#[account]
+pub struct VersionedLoggingMetadata {
+    pub authority: Pubkey,
+    pub layout_version: u8,
+    // followed by the actual json buffer, hidden to avoid deserialization
+}
+
+impl VersionedLoggingMetadata {
+    pub const LAYOUT_VERSION: u8 = 1;
+}
+
+// logging metadata accounts created before layouts were versioned. their
+// buffer starts right after the authority, and the discriminator is the only
+// thing telling them apart from the current layout. instructions read them at
+// the old offset until `migrate_account` moves them to the current layout
 #[account]
 pub struct LoggingMetadata {
     pub authority: Pubkey,
-    // actual json buffer is hidden to avoid deserialization
 }
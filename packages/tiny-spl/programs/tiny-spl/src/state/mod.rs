@@ -1,5 +1,19 @@
 mod tiny_spl_authority;
 mod logging_metadata;
+mod leaf_format;
+mod transfer_hook;
+mod allowed_wallet;
+mod denied_wallet;
+mod combine_session;
+mod distribution_session;
+mod versioned_account;
 
 pub use tiny_spl_authority::*;
 pub use logging_metadata::*;
+pub use leaf_format::*;
+pub use transfer_hook::*;
+pub use allowed_wallet::*;
+pub use denied_wallet::*;
+pub use combine_session::*;
+pub use distribution_session::*;
+pub use versioned_account::*;
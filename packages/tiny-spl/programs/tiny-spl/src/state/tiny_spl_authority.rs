@@ -6,4 +6,21 @@ pub struct TinySplAuthority {
     pub is_verified_tiny_spl_mint: bool,
     pub current_supply: u64,
     pub mint_authority: Option<Pubkey>,
+    pub metadata_authority: Option<Pubkey>,
+    pub leaf_format_version: u8,
+    // set for adopted collections, where the tiny spl authority is a
+    // collection authority rather than the update authority
+    pub collection_authority_record: Option<Pubkey>,
+    // fungible token 2022 mint that leaves can be decompressed into
+    pub twin_mint: Option<Pubkey>,
+    // program called whenever tokens move between owners
+    pub transfer_hook_program: Option<Pubkey>,
+    // manages the allowed and denied wallets of the mint
+    pub compliance_authority: Option<Pubkey>,
+    // only allowed wallets can receive leaves
+    pub is_allowlist_enabled: bool,
+    // denied wallets can neither send nor receive leaves
+    pub is_denylist_enabled: bool,
+    // 0 for mints created before layouts were versioned
+    pub layout_version: u8,
 }
@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+
+// extra accounts the transfer hook program needs, passed at the end of the
+// remaining accounts of every instruction that moves tokens between owners
+#[account]
+#[derive(InitSpace)]
+pub struct TransferHook {
+    pub collection_mint: Pubkey,
+    pub program_id: Pubkey,
+    #[max_len(8)] // MAX_TRANSFER_HOOK_EXTRA_ACCOUNTS
+    pub extra_accounts: Vec<TransferHookAccount>,
+    pub layout_version: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub struct TransferHookAccount {
+    pub pubkey: Pubkey,
+    pub is_writable: bool,
+}
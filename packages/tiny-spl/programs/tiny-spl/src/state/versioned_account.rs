@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{
+    AllowedWallet, CombineSession, DeniedWallet, DistributionSession, LeafFormat, TinySplAuthority,
+    TransferHook,
+};
+
+/// Accounts with a `layout_version` as their last field. Accounts created
+/// before their type was versioned read it as 0, or lack it entirely when
+/// their type has no spare space, until `migrate_account` upgrades them.
+pub trait VersionedAccount: AccountSerialize + AccountDeserialize + Space {
+    const LAYOUT_VERSION: u8;
+    /// Space new accounts are created with, including the discriminator
+    const SPACE: usize = 8 + Self::INIT_SPACE;
+
+    fn layout_version_mut(&mut self) -> &mut u8;
+}
+
+macro_rules! versioned_accounts {
+    ($($account:ident { layout_version: $version:literal $(, space: $space:expr)? }),* $(,)?) => {
+        $(
+            impl VersionedAccount for $account {
+                const LAYOUT_VERSION: u8 = $version;
+                $(const SPACE: usize = $space;)?
+
+                fn layout_version_mut(&mut self) -> &mut u8 {
+                    &mut self.layout_version
+                }
+            }
+        )*
+    };
+}
+
+versioned_accounts! {
+    // mints keep room to grow without a realloc
+    TinySplAuthority { layout_version: 1, space: 8 + (TinySplAuthority::INIT_SPACE * 2) },
+    LeafFormat { layout_version: 1 },
+    TransferHook { layout_version: 1 },
+    AllowedWallet { layout_version: 1 },
+    DeniedWallet { layout_version: 1 },
+    CombineSession { layout_version: 1 },
+    DistributionSession { layout_version: 1 },
+}
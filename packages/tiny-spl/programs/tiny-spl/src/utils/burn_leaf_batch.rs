@@ -0,0 +1,163 @@
+use std::collections::HashSet;
+
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::TinySplError,
+    state::{LeafFormat, TinySplAuthority},
+    utils::{
+        burn_cnft, get_leaf_format_table, get_tree_table, hash_cnft_metadata, with_heap_checkpoint,
+        BurnCnft, LeafMetadataTemplate,
+    },
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LeafInput {
+    pub amount: u64,
+    pub asset_id: Pubkey,
+    pub root: [u8; 32],
+    pub nonce: u64,
+    pub index: u32,
+    // index into the tree table of the batch
+    pub tree_index: u8,
+    // 0 is the source leaf format of the instruction, 1 and up index into the
+    // leaf format table of the batch
+    pub leaf_format_index: u8,
+    pub proof_length: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LeafBatch {
+    pub inputs: Vec<LeafInput>,
+    pub tree_count: u8,
+    pub leaf_format_count: u8,
+}
+
+/// Verifies and burns a batch of leaves of the same owner, returning their
+/// total amount.
+///
+/// The remaining accounts start with `tree_count` `(merkle_tree, tree_authority)`
+/// pairs that inputs refer to by index, followed by `leaf_format_count` leaf
+/// formats for inputs minted in another format than `leaf_format`, and the
+/// proof path of every input in order. Any account left over fails the batch.
+pub fn burn_leaf_batch<'info>(
+    ctx: &CpiContext<'_, '_, '_, 'info, BurnLeafBatch<'info>>,
+    tiny_spl_authority: &Account<'info, TinySplAuthority>,
+    leaf_format: &LeafFormat,
+    leaf_batch: &LeafBatch,
+) -> Result<u64> {
+    let inputs = &leaf_batch.inputs;
+
+    let mut asset_id_set = HashSet::new();
+    inputs.iter().for_each(|input| {
+        asset_id_set.insert(input.asset_id);
+    });
+    require!(
+        asset_id_set.len() == inputs.len(),
+        TinySplError::CannotCombineSameAsset
+    );
+
+    let (tree_table, rest) = get_tree_table(&ctx.remaining_accounts, leaf_batch.tree_count.into())?;
+    let (leaf_format_table, mut proof_paths) = get_leaf_format_table(
+        rest,
+        leaf_batch.leaf_format_count.into(),
+        ctx.accounts.collection_mint.key,
+    )?;
+
+    let mut burn_cpi_context = CpiContext::new(
+        ctx.program.clone(),
+        BurnCnft {
+            tree_authority: ctx.accounts.system_program.clone(),
+            leaf_owner: ctx.accounts.leaf_owner.clone(),
+            leaf_delegate: ctx.accounts.leaf_delegate.clone(),
+            merkle_tree: ctx.accounts.system_program.clone(),
+            log_wrapper: ctx.accounts.log_wrapper.clone(),
+            compression_program: ctx.accounts.compression_program.clone(),
+            system_program: ctx.accounts.system_program.clone(),
+        },
+    );
+
+    let leaf_metadata_templates: Vec<LeafMetadataTemplate> = std::iter::once(leaf_format)
+        .chain(leaf_format_table.iter())
+        .map(|leaf_format| {
+            LeafMetadataTemplate::new(
+                leaf_format,
+                ctx.accounts.collection_mint.key(),
+                tiny_spl_authority.key(),
+            )
+        })
+        .collect();
+
+    let mut total_amount: u64 = 0;
+    for input in inputs.iter() {
+        let tree = tree_table
+            .get(usize::from(input.tree_index))
+            .ok_or(TinySplError::InvalidTreeTable)?;
+        let leaf_metadata_template = leaf_metadata_templates
+            .get(usize::from(input.leaf_format_index))
+            .ok_or(TinySplError::InvalidLeafFormatTable)?;
+        let proof_length = usize::from(input.proof_length);
+        require!(
+            proof_paths.len() >= proof_length,
+            TinySplError::InvalidProofLayout
+        );
+        let (proof_path, rest) = proof_paths.split_at(proof_length);
+        proof_paths = rest;
+
+        with_heap_checkpoint(|| {
+            let cnft_metadata = leaf_metadata_template.build(input.amount);
+
+            burn_cpi_context.accounts.merkle_tree = tree.merkle_tree.clone();
+            burn_cpi_context.accounts.tree_authority = tree.tree_authority.clone();
+
+            let (calculated_asset_id, data_hash, creator_hash) = hash_cnft_metadata(
+                &cnft_metadata,
+                input.nonce,
+                &burn_cpi_context.accounts.merkle_tree,
+                &burn_cpi_context.accounts.leaf_owner,
+                &burn_cpi_context.accounts.leaf_delegate,
+                &ctx.accounts.collection_mint,
+            )?;
+
+            require!(
+                calculated_asset_id == input.asset_id,
+                TinySplError::AssetIdMismatch
+            );
+
+            burn_cnft(
+                &burn_cpi_context,
+                input.root,
+                data_hash,
+                creator_hash,
+                input.nonce,
+                input.index,
+                proof_path,
+            )
+        })?;
+
+        total_amount = total_amount
+            .checked_add(input.amount)
+            .ok_or(TinySplError::ArithmeticOverflow)?;
+    }
+
+    // every remaining account has to belong to one of the inputs
+    require!(proof_paths.is_empty(), TinySplError::InvalidProofLayout);
+
+    Ok(total_amount)
+}
+
+#[derive(Accounts)]
+pub struct BurnLeafBatch<'info> {
+    /// CHECK: checked in cpi to bubblegum
+    pub leaf_owner: AccountInfo<'info>,
+    /// CHECK: checked in cpi to bubblegum
+    pub leaf_delegate: AccountInfo<'info>,
+    /// CHECK: checked in cpi to bubblegum
+    pub collection_mint: AccountInfo<'info>,
+    /// CHECK: checked in cpi to bubblegum
+    pub log_wrapper: AccountInfo<'info>,
+    /// CHECK: checked in cpi to bubblegum
+    pub compression_program: AccountInfo<'info>,
+    /// CHECK: checked in cpi to bubblegum
+    pub system_program: AccountInfo<'info>,
+}
@@ -0,0 +1,104 @@
+use anchor_lang::{prelude::*, solana_program};
+
+use crate::{
+    constants::TRANSFER_HOOK_DISCRIMINATOR,
+    error::TinySplError,
+    state::{TinySplAuthority, TransferHook},
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct TransferHookDestination {
+    pub owner: Pubkey,
+    pub amount: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct TransferHookArgs {
+    pub source_owner: Pubkey,
+    pub destinations: Vec<TransferHookDestination>,
+}
+
+/// The extra accounts of a mint's transfer hook are passed at the end of the
+/// remaining accounts, in the order they were registered. Returns the remaining
+/// accounts in front of them and the extra accounts themselves.
+pub fn get_transfer_hook_accounts<'a, 'info>(
+    tiny_spl_authority: &TinySplAuthority,
+    transfer_hook: Option<&Account<'info, TransferHook>>,
+    transfer_hook_program: Option<&AccountInfo<'info>>,
+    remaining_accounts: &'a [AccountInfo<'info>],
+) -> Result<(&'a [AccountInfo<'info>], &'a [AccountInfo<'info>])> {
+    let Some(program_id) = tiny_spl_authority.transfer_hook_program else {
+        return Ok((remaining_accounts, &[]));
+    };
+
+    let transfer_hook = transfer_hook.ok_or(TinySplError::MissingTransferHookAccounts)?;
+    let transfer_hook_program =
+        transfer_hook_program.ok_or(TinySplError::MissingTransferHookAccounts)?;
+    require!(
+        transfer_hook.program_id == program_id && transfer_hook_program.key() == program_id,
+        TinySplError::MissingTransferHookAccounts
+    );
+
+    let extra_account_start = remaining_accounts
+        .len()
+        .checked_sub(transfer_hook.extra_accounts.len())
+        .ok_or(TinySplError::MissingTransferHookAccounts)?;
+    let (rest, extra_accounts) = remaining_accounts.split_at(extra_account_start);
+    for (account_info, expected) in extra_accounts.iter().zip(&transfer_hook.extra_accounts) {
+        require!(
+            account_info.key() == expected.pubkey,
+            TinySplError::MissingTransferHookAccounts
+        );
+    }
+
+    Ok((rest, extra_accounts))
+}
+
+/// Calls the mint's transfer hook program, failing the instruction if the hook
+/// rejects the movement.
+pub fn execute_transfer_hook<'info>(
+    ctx: &CpiContext<'_, '_, '_, 'info, ExecuteTransferHook<'info>>,
+    transfer_hook: &TransferHook,
+    args: TransferHookArgs,
+) -> Result<()> {
+    let mut data = TRANSFER_HOOK_DISCRIMINATOR.to_vec();
+    args.serialize(&mut data)?;
+
+    let mut accounts = vec![
+        AccountMeta::new_readonly(ctx.accounts.tiny_spl_authority.key(), false),
+        AccountMeta::new_readonly(ctx.accounts.collection_mint.key(), false),
+    ];
+    transfer_hook
+        .extra_accounts
+        .iter()
+        .for_each(|extra_account| {
+            accounts.push(if extra_account.is_writable {
+                AccountMeta::new(extra_account.pubkey, false)
+            } else {
+                AccountMeta::new_readonly(extra_account.pubkey, false)
+            });
+        });
+
+    let ix = solana_program::instruction::Instruction {
+        program_id: ctx.program.key(),
+        accounts,
+        data,
+    };
+
+    let mut account_infos = ToAccountInfos::to_account_infos(ctx);
+    ctx.remaining_accounts.iter().for_each(|account_info| {
+        account_infos.push(account_info.clone());
+    });
+
+    solana_program::program::invoke(&ix, &account_infos)?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ExecuteTransferHook<'info> {
+    /// CHECK: passed to the transfer hook program
+    pub tiny_spl_authority: AccountInfo<'info>,
+    /// CHECK: passed to the transfer hook program
+    pub collection_mint: AccountInfo<'info>,
+}
@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::TinySplError, state::LeafFormat};
+
+/// Reads `leaf_format_count` leaf formats of `collection_mint` from the start of
+/// `accounts`, returning them and the accounts that follow them.
+///
+/// Any leaf format of the collection is safe to accept, a leaf only verifies
+/// against the format it was minted in.
+pub fn get_leaf_format_table<'a, 'info>(
+    accounts: &'a [AccountInfo<'info>],
+    leaf_format_count: usize,
+    collection_mint: &Pubkey,
+) -> Result<(Vec<LeafFormat>, &'a [AccountInfo<'info>])> {
+    require!(
+        accounts.len() >= leaf_format_count,
+        TinySplError::InvalidLeafFormatTable
+    );
+
+    let (table_accounts, rest) = accounts.split_at(leaf_format_count);
+    let leaf_format_table = table_accounts
+        .iter()
+        .map(|account| {
+            require!(
+                account.owner == &crate::ID,
+                TinySplError::InvalidLeafFormatTable
+            );
+            let leaf_format = LeafFormat::try_deserialize(&mut &account.data.borrow()[..])?;
+            require!(
+                leaf_format.collection_mint == *collection_mint,
+                TinySplError::InvalidLeafFormatTable
+            );
+            Ok(leaf_format)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok((leaf_format_table, rest))
+}
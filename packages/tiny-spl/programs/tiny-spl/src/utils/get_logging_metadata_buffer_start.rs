@@ -0,0 +1,86 @@
+use anchor_lang::{prelude::*, Discriminator};
+
+use crate::{
+    constants::{LEGACY_METADATA_BUFFER_START, METADATA_BUFFER_START},
+    state::{LoggingMetadata, VersionedLoggingMetadata},
+};
+
+/// Checks that `metadata` is a logging metadata account of `authority` in
+/// either layout and returns where its buffer starts, so accounts keep working
+/// until they are migrated.
+pub fn get_logging_metadata_buffer_start(
+    metadata: &AccountInfo,
+    authority: &Pubkey,
+) -> Result<usize> {
+    let data = metadata.try_borrow_data()?;
+    let (stored_authority, buffer_start) = match data.get(..8) {
+        Some(discriminator) if discriminator == VersionedLoggingMetadata::DISCRIMINATOR => (
+            VersionedLoggingMetadata::try_deserialize(&mut &data[..])?.authority,
+            METADATA_BUFFER_START,
+        ),
+        Some(discriminator) if discriminator == LoggingMetadata::DISCRIMINATOR => (
+            LoggingMetadata::try_deserialize(&mut &data[..])?.authority,
+            LEGACY_METADATA_BUFFER_START,
+        ),
+        _ => return err!(ErrorCode::AccountDiscriminatorMismatch),
+    };
+    require_keys_eq!(stored_authority, *authority, ErrorCode::ConstraintHasOne);
+
+    Ok(buffer_start)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn buffer_start_of(header: impl AnchorSerialize, authority: &Pubkey) -> Result<usize> {
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mut data = vec![0; METADATA_BUFFER_START + 16];
+        header.serialize(&mut &mut data[..]).unwrap();
+        let metadata = AccountInfo::new(
+            &key,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &crate::ID,
+            false,
+            0,
+        );
+        get_logging_metadata_buffer_start(&metadata, authority)
+    }
+
+    #[test]
+    fn legacy_buffer_starts_after_the_authority() {
+        let authority = Pubkey::new_unique();
+        let header = (LoggingMetadata::DISCRIMINATOR, authority);
+
+        assert_eq!(
+            buffer_start_of(header, &authority).unwrap(),
+            LEGACY_METADATA_BUFFER_START
+        );
+    }
+
+    #[test]
+    fn current_buffer_starts_after_the_layout_version() {
+        let authority = Pubkey::new_unique();
+        let header = (
+            VersionedLoggingMetadata::DISCRIMINATOR,
+            authority,
+            VersionedLoggingMetadata::LAYOUT_VERSION,
+        );
+
+        assert_eq!(
+            buffer_start_of(header, &authority).unwrap(),
+            METADATA_BUFFER_START
+        );
+    }
+
+    #[test]
+    fn other_authorities_are_rejected() {
+        let header = (LoggingMetadata::DISCRIMINATOR, Pubkey::new_unique());
+
+        assert!(buffer_start_of(header, &Pubkey::new_unique()).is_err());
+    }
+}
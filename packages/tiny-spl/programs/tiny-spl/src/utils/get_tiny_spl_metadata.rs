@@ -1,44 +1,225 @@
 use anchor_lang::solana_program::pubkey::Pubkey;
 
+use crate::{
+    constants::MAX_URI_LENGTH,
+    state::{LeafFormat, LeafNameStyle},
+};
+
+// short scale, with q for quadrillion so u64::MAX reads as 18.4Q
+const COMPACT_AMOUNT_SUFFIXES: [&str; 6] = ["K", "M", "B", "T", "q", "Q"];
+
 pub fn get_tiny_spl_metadata(
-    symbol: String,
+    leaf_format: &LeafFormat,
     amount: u64,
     collection_mint: Pubkey,
     tiny_spl_authority: Pubkey,
 ) -> mpl_bubblegum::types::MetadataArgs {
-    let formatted_amount = amount
+    LeafMetadataTemplate::new(leaf_format, collection_mint, tiny_spl_authority).build(amount)
+}
+
+// everything in the metadata of a leaf except its amount, so instructions
+// that build many leaves only encode the collection mint and creators once
+pub struct LeafMetadataTemplate {
+    name_style: LeafNameStyle,
+    // the collection mint is already substituted
+    uri_template: String,
+    metadata: mpl_bubblegum::types::MetadataArgs,
+}
+
+impl LeafMetadataTemplate {
+    pub fn new(
+        leaf_format: &LeafFormat,
+        collection_mint: Pubkey,
+        tiny_spl_authority: Pubkey,
+    ) -> Self {
+        let uri_template = leaf_format
+            .uri_template
+            .replace("{collection_mint}", &collection_mint.to_string());
+
+        let additional_shares: u8 = leaf_format
+            .additional_creators
+            .iter()
+            .map(|creator| creator.share)
+            .sum();
+        let mut creators = vec![mpl_bubblegum::types::Creator {
+            address: tiny_spl_authority,
+            verified: true,
+            share: 100 - additional_shares,
+        }];
+        creators.extend(leaf_format.additional_creators.iter().map(|creator| {
+            mpl_bubblegum::types::Creator {
+                address: creator.address,
+                verified: false,
+                share: creator.share,
+            }
+        }));
+
+        LeafMetadataTemplate {
+            name_style: leaf_format.name_style,
+            uri_template,
+            metadata: mpl_bubblegum::types::MetadataArgs {
+                name: String::new(),
+                symbol: leaf_format.symbol.replace("\0", ""),
+                uri: String::new(),
+                seller_fee_basis_points: leaf_format.seller_fee_basis_points,
+                primary_sale_happened: leaf_format.primary_sale_happened,
+                is_mutable: leaf_format.is_mutable,
+                edition_nonce: None,
+                token_standard: Some(mpl_bubblegum::types::TokenStandard::NonFungible),
+                collection: Some(mpl_bubblegum::types::Collection {
+                    key: collection_mint,
+                    verified: true,
+                }),
+                uses: None,
+                // bubblegum decompresses leaves to spl token mints, whichever
+                // token program the collection mint was created under
+                token_program_version: mpl_bubblegum::types::TokenProgramVersion::Original,
+                creators,
+            },
+        }
+    }
+
+    pub fn build(&self, amount: u64) -> mpl_bubblegum::types::MetadataArgs {
+        let formatted_amount = match self.name_style {
+            LeafNameStyle::Grouped => format_grouped_amount(amount),
+            LeafNameStyle::Compact => format_compact_amount(amount),
+        };
+
+        let mut metadata = self.metadata.clone();
+        metadata.name = format!("{formatted_amount} {}", metadata.symbol);
+        metadata.uri = self
+            .uri_template
+            .replace("{amount}", &amount.to_string())
+            .replace("\0", "");
+        metadata
+    }
+}
+
+// compact names are lossy, so only the amount in the uri tells leaves of
+// different amounts apart, and their hashes with it. the uri also has to fit
+// bubblegum with the longest amount, or leaves stop minting once they reach it
+pub fn is_valid_uri_template(uri_template: &str, collection_mint: &Pubkey) -> bool {
+    uri_template.contains("{amount}")
+        && uri_template
+            .replace("{collection_mint}", &collection_mint.to_string())
+            .replace("{amount}", &u64::MAX.to_string())
+            .len()
+            <= MAX_URI_LENGTH
+}
+
+fn format_grouped_amount(amount: u64) -> String {
+    amount
         .to_string()
         .as_bytes()
         .rchunks(3)
         .rev()
-        .map(std::str::from_utf8)
-        .flat_map(|x| x)
+        .flat_map(std::str::from_utf8)
         .collect::<Vec<_>>()
-        .join(",");
-    let name = format!("{formatted_amount} {symbol}").replace("\0", "");
-    let symbol = symbol.replace("\0", "");
-    let uri = format!("https://metadata.tinys.pl/collection?id={collection_mint}&amount={amount}")
-        .replace("\0", "");
-
-    mpl_bubblegum::types::MetadataArgs {
-        name,
-        symbol,
-        uri,
-        seller_fee_basis_points: 0,
-        primary_sale_happened: false,
-        is_mutable: true,
-        edition_nonce: None,
-        token_standard: Some(mpl_bubblegum::types::TokenStandard::NonFungible),
-        collection: Some(mpl_bubblegum::types::Collection {
-            key: collection_mint,
-            verified: true,
-        }),
-        uses: None,
-        token_program_version: mpl_bubblegum::types::TokenProgramVersion::Original,
-        creators: vec![mpl_bubblegum::types::Creator {
-            address: tiny_spl_authority,
-            verified: true,
-            share: 100,
-        }],
+        .join(",")
+}
+
+// truncates rather than rounds, so a leaf never shows more than it holds
+fn format_compact_amount(amount: u64) -> String {
+    if amount < 1_000 {
+        return amount.to_string();
+    }
+
+    let mut unit: u64 = 1_000;
+    let mut suffix_index = 0;
+    while suffix_index + 1 < COMPACT_AMOUNT_SUFFIXES.len() && amount / unit >= 1_000 {
+        unit *= 1_000;
+        suffix_index += 1;
+    }
+
+    let whole = amount / unit;
+    let tenths = (amount % unit) / (unit / 10);
+    let suffix = COMPACT_AMOUNT_SUFFIXES[suffix_index];
+    if tenths == 0 {
+        format!("{whole}{suffix}")
+    } else {
+        format!("{whole}.{tenths}{suffix}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::{DEFAULT_LEAF_URI_TEMPLATE, MAX_SYMBOL_LENGTH};
+
+    // bubblegum rejects leaf names longer than this
+    const MAX_NAME_LENGTH: usize = 32;
+
+    fn compact_leaf_format(symbol: &str) -> LeafFormat {
+        LeafFormat {
+            collection_mint: Pubkey::new_unique(),
+            version: 1,
+            symbol: symbol.to_string(),
+            uri_template: String::new(),
+            name_style: LeafNameStyle::Compact,
+            seller_fee_basis_points: 0,
+            primary_sale_happened: false,
+            is_mutable: true,
+            additional_creators: vec![],
+            layout_version: 1,
+        }
+    }
+
+    #[test]
+    fn format_compact_amount_boundaries() {
+        assert_eq!(format_compact_amount(0), "0");
+        assert_eq!(format_compact_amount(999), "999");
+        assert_eq!(format_compact_amount(1_000), "1K");
+        assert_eq!(format_compact_amount(1_050), "1K");
+        assert_eq!(format_compact_amount(1_100), "1.1K");
+        assert_eq!(format_compact_amount(999_999), "999.9K");
+        assert_eq!(format_compact_amount(1_000_000), "1M");
+        assert_eq!(format_compact_amount(1_000_000_000_000_000), "1q");
+        assert_eq!(format_compact_amount(u64::MAX), "18.4Q");
+    }
+
+    #[test]
+    fn compact_name_fits_with_longest_symbol() {
+        let leaf_format = compact_leaf_format(&"W".repeat(MAX_SYMBOL_LENGTH));
+        let template =
+            LeafMetadataTemplate::new(&leaf_format, Pubkey::new_unique(), Pubkey::new_unique());
+
+        for amount in [0, 999, 1_000, 999_999, u64::MAX] {
+            let metadata = template.build(amount);
+            assert!(
+                metadata.name.len() <= MAX_NAME_LENGTH,
+                "{} is too long",
+                metadata.name
+            );
+        }
+    }
+
+    #[test]
+    fn compact_leaves_of_different_amounts_differ() {
+        let mut leaf_format = compact_leaf_format("TST");
+        leaf_format.uri_template = DEFAULT_LEAF_URI_TEMPLATE.to_string();
+        let template =
+            LeafMetadataTemplate::new(&leaf_format, Pubkey::new_unique(), Pubkey::new_unique());
+
+        let (small, large) = (template.build(1_000), template.build(1_050));
+
+        assert_eq!(small.name, large.name);
+        assert_ne!(small.uri, large.uri);
+    }
+
+    #[test]
+    fn uri_template_needs_the_amount_and_room_for_it() {
+        let collection_mint = Pubkey::new_unique();
+
+        assert!(is_valid_uri_template(
+            DEFAULT_LEAF_URI_TEMPLATE,
+            &collection_mint
+        ));
+        assert!(!is_valid_uri_template(
+            "https://example.com/leaf.json",
+            &collection_mint
+        ));
+        // fits as a template, but not once the amount is substituted
+        let long_template = format!("{}{{amount}}", "a".repeat(MAX_URI_LENGTH - 8));
+        assert!(!is_valid_uri_template(&long_template, &collection_mint));
     }
 }
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::TinySplError, utils::verify_tree_authority};
+
+#[derive(Clone)]
+pub struct TreeTableEntry<'info> {
+    pub merkle_tree: AccountInfo<'info>,
+    pub tree_authority: AccountInfo<'info>,
+}
+
+/// Reads `tree_count` consecutive `(merkle_tree, tree_authority)` pairs from the
+/// start of `accounts`, returning the table and the accounts that follow it.
+pub fn get_tree_table<'a, 'info>(
+    accounts: &'a [AccountInfo<'info>],
+    tree_count: usize,
+) -> Result<(Vec<TreeTableEntry<'info>>, &'a [AccountInfo<'info>])> {
+    let table_len = tree_count
+        .checked_mul(2)
+        .ok_or(TinySplError::InvalidTreeTable)?;
+    require!(accounts.len() >= table_len, TinySplError::InvalidTreeTable);
+
+    let (table_accounts, rest) = accounts.split_at(table_len);
+    let tree_table = table_accounts
+        .chunks_exact(2)
+        .map(|pair| {
+            verify_tree_authority(&pair[0], &pair[1])?;
+            Ok(TreeTableEntry {
+                merkle_tree: pair[0].clone(),
+                tree_authority: pair[1].clone(),
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok((tree_table, rest))
+}
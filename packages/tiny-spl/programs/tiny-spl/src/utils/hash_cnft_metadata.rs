@@ -0,0 +1,53 @@
+use anchor_lang::{prelude::*, solana_program::keccak};
+use mpl_bubblegum::utils::get_asset_id;
+
+use crate::error::TinySplError;
+
+/// Computes the asset id, data hash and creator hash of a leaf from its
+/// metadata. Nothing here checks that the leaf is in the tree.
+///
+/// Callers must either burn the leaf with the returned hashes or verify it
+/// with a bubblegum `verify_leaf` cpi before trusting the metadata. Bubblegum's
+/// burn recomputes the leaf from the same owner, delegate, nonce and hashes and
+/// checks it against the tree, so a burn right after this call is the check.
+pub fn hash_cnft_metadata(
+    metadata: &mpl_bubblegum::types::MetadataArgs,
+    nonce: u64,
+    merkle_tree: &AccountInfo,
+    owner: &AccountInfo,
+    delegate: &AccountInfo,
+    collection_mint: &AccountInfo,
+) -> Result<(Pubkey, [u8; 32], [u8; 32])> {
+    require!(
+        owner.is_signer || delegate.is_signer,
+        TinySplError::LeafAuthorityMustSign
+    );
+    require!(
+        metadata
+            .collection
+            .as_ref()
+            .is_some_and(|collection| collection.key == collection_mint.key()),
+        TinySplError::CollectionMismatch
+    );
+
+    let metadata_hash = keccak::hashv(&[metadata.try_to_vec()?.as_slice()]);
+    let data_hash = keccak::hashv(&[
+        &metadata_hash.to_bytes(),
+        &metadata.seller_fee_basis_points.to_le_bytes(),
+    ]);
+    let creator_data = metadata
+        .creators
+        .iter()
+        .map(|c| Ok([c.address.as_ref(), &[c.verified as u8], &[c.share]].concat()))
+        .collect::<Result<Vec<_>>>()?;
+    let creator_hash = keccak::hashv(
+        creator_data
+            .iter()
+            .map(|c| c.as_slice())
+            .collect::<Vec<&[u8]>>()
+            .as_ref(),
+    );
+    let asset_id = get_asset_id(&merkle_tree.key(), nonce);
+
+    Ok((asset_id, data_hash.to_bytes(), creator_hash.to_bytes()))
+}
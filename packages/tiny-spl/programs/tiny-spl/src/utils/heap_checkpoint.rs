@@ -0,0 +1,128 @@
+use anchor_lang::prelude::*;
+
+#[cfg(all(feature = "custom-heap", any(test, target_os = "solana")))]
+use crate::BumpAllocator;
+#[cfg(all(feature = "custom-heap", target_os = "solana"))]
+use crate::A;
+
+/// Remembers the heap cursor and rewinds it when dropped
+#[cfg(all(feature = "custom-heap", any(test, target_os = "solana")))]
+struct HeapCheckpoint<'a> {
+    allocator: &'a BumpAllocator,
+    pos: usize,
+}
+
+#[cfg(all(feature = "custom-heap", any(test, target_os = "solana")))]
+impl<'a> HeapCheckpoint<'a> {
+    fn new(allocator: &'a BumpAllocator) -> Self {
+        HeapCheckpoint {
+            allocator,
+            pos: unsafe { allocator.pos() },
+        }
+    }
+}
+
+#[cfg(all(feature = "custom-heap", any(test, target_os = "solana")))]
+impl Drop for HeapCheckpoint<'_> {
+    fn drop(&mut self) {
+        unsafe {
+            self.allocator.move_cursor(self.pos);
+        }
+    }
+}
+
+/// Runs `f` and hands back everything it allocated once it succeeds, so
+/// a loop of cpis only ever needs the heap of a single iteration.
+/// On error the allocations are kept since the error may point into them.
+///
+/// `f` must not leave anything it allocated reachable from outside,
+/// e.g. by pushing into a captured `Vec`. Only `Copy` values come back out.
+pub fn with_heap_checkpoint<T: Copy>(f: impl FnOnce() -> Result<T>) -> Result<T> {
+    #[cfg(all(feature = "custom-heap", target_os = "solana"))]
+    return with_checkpoint_of(&A, f);
+
+    #[cfg(not(all(feature = "custom-heap", target_os = "solana")))]
+    f()
+}
+
+#[cfg(all(feature = "custom-heap", any(test, target_os = "solana")))]
+fn with_checkpoint_of<T: Copy>(
+    allocator: &BumpAllocator,
+    f: impl FnOnce() -> Result<T>,
+) -> Result<T> {
+    let checkpoint = HeapCheckpoint::new(allocator);
+    let result = f();
+    if result.is_err() {
+        std::mem::forget(checkpoint);
+    }
+    result
+}
+
+#[cfg(all(test, feature = "custom-heap"))]
+mod tests {
+    use std::{
+        alloc::{GlobalAlloc, Layout},
+        mem::size_of,
+    };
+
+    use super::*;
+
+    // the default heap frame of a transaction
+    const HEAP_BYTES: usize = 32 * 1024;
+    // metadata args, instruction data and account metas of a single leaf mint
+    // cpi come to about this much
+    const MINT_CPI_BYTES: usize = 1024;
+    const SPLIT_OUTPUTS: usize = 64;
+
+    fn mint_cpi(allocator: &BumpAllocator) -> Result<bool> {
+        let layout = Layout::from_size_align(MINT_CPI_BYTES, 8).unwrap();
+        Ok(!unsafe { allocator.alloc(layout) }.is_null())
+    }
+
+    fn split(use_checkpoint: bool) -> usize {
+        let mut heap = vec![0usize; HEAP_BYTES / size_of::<usize>()];
+        let allocator = BumpAllocator {
+            start: heap.as_mut_ptr() as usize,
+            len: HEAP_BYTES,
+        };
+
+        (0..SPLIT_OUTPUTS)
+            .filter(|_| {
+                if use_checkpoint {
+                    with_checkpoint_of(&allocator, || mint_cpi(&allocator)).unwrap()
+                } else {
+                    mint_cpi(&allocator).unwrap()
+                }
+            })
+            .count()
+    }
+
+    #[test]
+    fn many_output_split_fits_the_heap_with_checkpoints() {
+        assert_eq!(split(true), SPLIT_OUTPUTS);
+    }
+
+    #[test]
+    fn many_output_split_runs_out_of_heap_without_checkpoints() {
+        assert!(split(false) < SPLIT_OUTPUTS);
+    }
+
+    #[test]
+    fn failed_cpi_keeps_its_allocations() {
+        let mut heap = vec![0usize; HEAP_BYTES / size_of::<usize>()];
+        let allocator = BumpAllocator {
+            start: heap.as_mut_ptr() as usize,
+            len: HEAP_BYTES,
+        };
+        mint_cpi(&allocator).unwrap();
+        let pos = unsafe { allocator.pos() };
+
+        let result: Result<()> = with_checkpoint_of(&allocator, || {
+            mint_cpi(&allocator)?;
+            err!(crate::error::TinySplError::InvalidSplitAmounts)
+        });
+
+        assert!(result.is_err());
+        assert_eq!(unsafe { allocator.pos() }, pos + MINT_CPI_BYTES);
+    }
+}
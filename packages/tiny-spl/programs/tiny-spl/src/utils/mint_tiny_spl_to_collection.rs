@@ -7,12 +7,16 @@ pub fn mint_tiny_spl_to_collection<'info>(
     let mut ix = mpl_bubblegum::instructions::MintToCollectionV1 {
         tree_config: *ctx.accounts.tree_config.key,
         leaf_owner: *ctx.accounts.new_leaf_owner.key,
-        leaf_delegate: *ctx.accounts.new_leaf_owner.key,
+        leaf_delegate: *ctx.accounts.new_leaf_delegate.key,
         merkle_tree: *ctx.accounts.merkle_tree.key,
         payer: *ctx.accounts.payer.key,
         tree_creator_or_delegate: ctx.accounts.tree_creator_or_delegate.key(),
         collection_authority: ctx.accounts.tiny_spl_authority.key(),
-        collection_authority_record_pda: None,
+        collection_authority_record_pda: ctx
+            .accounts
+            .collection_authority_record
+            .as_ref()
+            .map(|x| x.key()),
         collection_mint: *ctx.accounts.collection_mint.key,
         collection_metadata: *ctx.accounts.collection_metadata.key,
         collection_edition: *ctx.accounts.collection_edition.key,
@@ -49,6 +53,8 @@ pub struct MintTinySplToCollection<'info> {
     /// CHECK: checked in cpi to bubblegum
     pub new_leaf_owner: AccountInfo<'info>,
     /// CHECK: checked in cpi to bubblegum
+    pub new_leaf_delegate: AccountInfo<'info>,
+    /// CHECK: checked in cpi to bubblegum
     pub merkle_tree: AccountInfo<'info>,
     /// CHECK: checked in cpi to bubblegum
     pub payer: AccountInfo<'info>,
@@ -63,6 +69,8 @@ pub struct MintTinySplToCollection<'info> {
     /// CHECK: checked in cpi to bubblegum
     pub tiny_spl_authority: AccountInfo<'info>,
     /// CHECK: checked in cpi to bubblegum
+    pub collection_authority_record: Option<AccountInfo<'info>>,
+    /// CHECK: checked in cpi to bubblegum
     pub tree_creator_or_delegate: AccountInfo<'info>,
     /// CHECK: checked in cpi to bubblegum
     pub log_wrapper: AccountInfo<'info>,
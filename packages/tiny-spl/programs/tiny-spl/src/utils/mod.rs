@@ -1,11 +1,33 @@
-mod verify_cnft_metadata;
+mod hash_cnft_metadata;
 mod verify_token_splits;
 mod burn_cnft;
 mod mint_tiny_spl_to_collection;
 mod get_tiny_spl_metadata;
+mod resolve_new_leaf_delegate;
+mod verify_tree_authority;
+mod get_tree_table;
+mod get_leaf_format_table;
+mod update_collection;
+mod execute_transfer_hook;
+mod verify_wallet_allowed;
+mod verify_wallet_not_denied;
+mod burn_leaf_batch;
+mod heap_checkpoint;
+mod get_logging_metadata_buffer_start;
 
-pub use verify_cnft_metadata::*;
+pub use hash_cnft_metadata::*;
 pub use verify_token_splits::*;
 pub use burn_cnft::*;
 pub use mint_tiny_spl_to_collection::*;
 pub use get_tiny_spl_metadata::*;
+pub use resolve_new_leaf_delegate::*;
+pub use verify_tree_authority::*;
+pub use get_tree_table::*;
+pub use get_leaf_format_table::*;
+pub use update_collection::*;
+pub use execute_transfer_hook::*;
+pub use verify_wallet_allowed::*;
+pub use verify_wallet_not_denied::*;
+pub use burn_leaf_batch::*;
+pub use heap_checkpoint::*;
+pub use get_logging_metadata_buffer_start::*;
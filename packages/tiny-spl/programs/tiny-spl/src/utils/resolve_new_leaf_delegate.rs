@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+
+use crate::error::TinySplError;
+
+/// Picks the delegate of leaves minted out of a split or combine.
+///
+/// Without any delegate options the new owner becomes its own delegate, which
+/// is what bubblegum does by default. When `preserve_delegate` is set the
+/// source leaf's delegate is carried over, which is only allowed when the
+/// outputs stay with the same owner.
+pub fn resolve_new_leaf_delegate<'info>(
+    preserve_delegate: bool,
+    leaf_owner: &AccountInfo<'info>,
+    leaf_delegate: &AccountInfo<'info>,
+    new_leaf_owner: &AccountInfo<'info>,
+    new_leaf_delegate: Option<AccountInfo<'info>>,
+) -> Result<AccountInfo<'info>> {
+    match (preserve_delegate, new_leaf_delegate) {
+        (true, Some(_)) => Err(TinySplError::ConflictingDelegateOptions.into()),
+        (true, None) => {
+            require!(
+                leaf_owner.key() == new_leaf_owner.key(),
+                TinySplError::CannotPreserveDelegateOnTransfer
+            );
+            Ok(leaf_delegate.clone())
+        }
+        (false, Some(new_leaf_delegate)) => Ok(new_leaf_delegate),
+        (false, None) => Ok(new_leaf_owner.clone()),
+    }
+}
@@ -0,0 +1,58 @@
+use anchor_lang::{prelude::*, solana_program};
+use anchor_spl::metadata::mpl_token_metadata;
+
+pub fn update_collection<'info>(
+    ctx: &CpiContext<'_, '_, '_, 'info, UpdateCollection<'info>>,
+    data: Option<mpl_token_metadata::types::Data>,
+    is_mutable: Option<bool>,
+) -> Result<()> {
+    let ix = mpl_token_metadata::instructions::UpdateV1 {
+        authority: *ctx.accounts.authority.key,
+        delegate_record: None,
+        token: None,
+        mint: *ctx.accounts.mint.key,
+        metadata: *ctx.accounts.metadata.key,
+        edition: Some(*ctx.accounts.edition.key),
+        payer: *ctx.accounts.payer.key,
+        system_program: *ctx.accounts.system_program.key,
+        sysvar_instructions: *ctx.accounts.sysvar_instructions.key,
+        authorization_rules_program: None,
+        authorization_rules: None,
+    }
+    .instruction(mpl_token_metadata::instructions::UpdateV1InstructionArgs {
+        new_update_authority: None,
+        data,
+        primary_sale_happened: None,
+        is_mutable,
+        collection: mpl_token_metadata::types::CollectionToggle::None,
+        collection_details: mpl_token_metadata::types::CollectionDetailsToggle::None,
+        uses: mpl_token_metadata::types::UsesToggle::None,
+        rule_set: mpl_token_metadata::types::RuleSetToggle::None,
+        authorization_data: None,
+    });
+
+    solana_program::program::invoke_signed(
+        &ix,
+        &ToAccountInfos::to_account_infos(ctx),
+        ctx.signer_seeds,
+    )
+    .map_err(Into::into)
+}
+
+#[derive(Accounts)]
+pub struct UpdateCollection<'info> {
+    /// CHECK: checked in cpi to mpl token metadata
+    pub authority: AccountInfo<'info>,
+    /// CHECK: checked in cpi to mpl token metadata
+    pub mint: AccountInfo<'info>,
+    /// CHECK: checked in cpi to mpl token metadata
+    pub metadata: AccountInfo<'info>,
+    /// CHECK: checked in cpi to mpl token metadata
+    pub edition: AccountInfo<'info>,
+    /// CHECK: checked in cpi to mpl token metadata
+    pub payer: AccountInfo<'info>,
+    /// CHECK: checked in cpi to mpl token metadata
+    pub system_program: AccountInfo<'info>,
+    /// CHECK: checked in cpi to mpl token metadata
+    pub sysvar_instructions: AccountInfo<'info>,
+}
@@ -5,7 +5,9 @@ use crate::error::TinySplError;
 pub fn verify_token_splits(source_amount: u64, amounts: &Vec<u64>) -> Result<()> {
     let mut total_amount: u64 = 0;
     for amount in amounts {
-        total_amount = total_amount.checked_add(*amount).unwrap();
+        total_amount = total_amount
+            .checked_add(*amount)
+            .ok_or(TinySplError::ArithmeticOverflow)?;
     }
 
     let is_amounts_all_more_than_zero = amounts.iter().all(|&amount| amount > 0);
@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+use crate::error::TinySplError;
+
+pub fn verify_tree_authority(
+    merkle_tree: &AccountInfo,
+    tree_authority: &AccountInfo,
+) -> Result<()> {
+    require!(
+        *merkle_tree.owner == spl_account_compression::ID,
+        TinySplError::InvalidMerkleTree
+    );
+    let (expected_tree_authority, _) =
+        Pubkey::find_program_address(&[merkle_tree.key.as_ref()], &mpl_bubblegum::ID);
+    require!(
+        tree_authority.key() == expected_tree_authority,
+        TinySplError::TreeAuthorityMismatch
+    );
+
+    Ok(())
+}
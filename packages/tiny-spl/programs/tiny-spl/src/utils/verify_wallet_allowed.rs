@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::{constants::ALLOWED_WALLET_SEED, error::TinySplError, state::TinySplAuthority};
+
+/// Only mints in allowlist mode restrict who can receive leaves, so the allowed
+/// wallet account only has to be supplied for those mints.
+pub fn verify_wallet_allowed(
+    tiny_spl_authority: &TinySplAuthority,
+    collection_mint: &Pubkey,
+    wallet: &Pubkey,
+    allowed_wallet: Option<&AccountInfo>,
+) -> Result<()> {
+    if !tiny_spl_authority.is_allowlist_enabled {
+        return Ok(());
+    }
+
+    let allowed_wallet = allowed_wallet.ok_or(TinySplError::WalletNotAllowed)?;
+    let (expected_allowed_wallet, _) = Pubkey::find_program_address(
+        &[
+            ALLOWED_WALLET_SEED,
+            collection_mint.as_ref(),
+            wallet.as_ref(),
+        ],
+        &crate::ID,
+    );
+    require!(
+        allowed_wallet.key() == expected_allowed_wallet
+            && allowed_wallet.owner == &crate::ID
+            && !allowed_wallet.data_is_empty(),
+        TinySplError::WalletNotAllowed
+    );
+
+    Ok(())
+}
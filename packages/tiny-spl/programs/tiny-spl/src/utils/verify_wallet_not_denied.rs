@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+use crate::{constants::DENIED_WALLET_SEED, error::TinySplError, state::TinySplAuthority};
+
+/// Only mints in denylist mode can have denied wallets, so the denied wallet
+/// account only has to be supplied for those mints.
+pub fn verify_wallet_not_denied(
+    tiny_spl_authority: &TinySplAuthority,
+    collection_mint: &Pubkey,
+    wallet: &Pubkey,
+    denied_wallet: Option<&AccountInfo>,
+) -> Result<()> {
+    if !tiny_spl_authority.is_denylist_enabled {
+        return Ok(());
+    }
+
+    let denied_wallet = denied_wallet.ok_or(TinySplError::MissingDeniedWalletAccount)?;
+    let (expected_denied_wallet, _) = Pubkey::find_program_address(
+        &[
+            DENIED_WALLET_SEED,
+            collection_mint.as_ref(),
+            wallet.as_ref(),
+        ],
+        &crate::ID,
+    );
+    require!(
+        denied_wallet.key() == expected_denied_wallet,
+        TinySplError::MissingDeniedWalletAccount
+    );
+    require!(denied_wallet.data_is_empty(), TinySplError::WalletDenied);
+
+    Ok(())
+}
@@ -39,10 +39,13 @@ export const TINY_SPL_AUTHORITY_SEED = "tiny_spl";
 
 export const CNFT_METADATA_SEED = "cnft_metadata";
 
+export const LEAF_FORMAT_SEED = "leaf_format";
+
 export const TREE_ID = new PublicKey(process.env.TREE_ADDRESS!);
 
 export const RECEIVER_ID = new PublicKey(process.env.RECEIVER_ADDRESS!);
 
 export const METADATA_BUFFER_START =
   8 + // discriminator
-  32; // authority
+  32 + // authority
+  1; // layout version
@@ -1,3 +1,13 @@
+// Creates an address lookup table for the proof nodes of `split`, `combine`
+// and the other instructions that spend leaves, add the nodes with
+// `extend-lookup-table.ts`.
+//
+// Proof paths cannot be stored in a buffer account instead: bubblegum and
+// account compression read the proof nodes from the accounts of the
+// instruction, and every account of a cpi must also be an account of the
+// transaction. Clients using trees with a shallow canopy should send leaf
+// spends as versioned transactions that load the proof nodes from a lookup
+// table, which costs one byte per node instead of 32.
 import {
   AddressLookupTableProgram,
   TransactionMessage,
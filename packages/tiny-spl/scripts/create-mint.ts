@@ -1,6 +1,7 @@
 import "dotenv/config";
 import {
   TINY_SPL_AUTHORITY_SEED,
+  LEAF_FORMAT_SEED,
   CONNECTION,
   PROGRAM,
   SIGNER,
@@ -46,15 +47,29 @@ async function createMint() {
     [Buffer.from(TINY_SPL_AUTHORITY_SEED), mint.toBuffer()],
     PROGRAM.programId
   );
+  // new mints start at leaf format version 0
+  const [leafFormat] = PublicKey.findProgramAddressSync(
+    [Buffer.from(LEAF_FORMAT_SEED), mint.toBuffer(), Buffer.from([0])],
+    PROGRAM.programId
+  );
 
   const ix = await PROGRAM.methods
-    .createMint({
-      name: collectionMetadata.name,
-      symbol: collectionMetadata.symbol,
-      uri: process.env.MINT_METADATA_URI,
-    })
+    .createMint(
+      {
+        name: collectionMetadata.name,
+        symbol: collectionMetadata.symbol,
+        uri: process.env.MINT_METADATA_URI,
+        sellerFeeBasisPoints: 0,
+        primarySaleHappened: false,
+        isMutable: true,
+        additionalCreators: [],
+      },
+      // compliance config
+      null
+    )
     .accounts({
       tinySplAuthority,
+      leafFormat,
       masterEdition,
       metadata,
       mint,
@@ -1,5 +1,6 @@
 import {
   TINY_SPL_AUTHORITY_SEED,
+  LEAF_FORMAT_SEED,
   CONNECTION,
   PROGRAM,
   RECEIVER_ID,
@@ -54,14 +55,25 @@ async function mintTo() {
     [Buffer.from(TINY_SPL_AUTHORITY_SEED), mint.toBuffer()],
     PROGRAM.programId
   );
+  const { leafFormatVersion } =
+    await PROGRAM.account.tinySplAuthority.fetch(tinySplAuthority);
+  const [leafFormat] = PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(LEAF_FORMAT_SEED),
+      mint.toBuffer(),
+      Buffer.from([leafFormatVersion]),
+    ],
+    PROGRAM.programId
+  );
 
   const ix = await PROGRAM.methods
-    .mintTo(new BN(1000))
+    .mintTo(new BN(1000), null)
     .accounts({
       bubblegumSigner,
       collectionMetadata: metadata,
       collectionMint: mint,
       tinySplAuthority,
+      leafFormat,
       compressionProgram: COMPRESSION_PROGRAM_ID,
       editionAccount: masterEdition,
       logWrapper: SPL_NOOP_PROGRAM_ID,
@@ -0,0 +1,1162 @@
+import * as anchor from "@coral-xyz/anchor";
+import {
+  AccountMeta,
+  ComputeBudgetProgram,
+  Keypair,
+  LAMPORTS_PER_SOL,
+  PublicKey,
+  SystemProgram,
+  TransactionInstruction,
+} from "@solana/web3.js";
+import { PROGRAM_ID as BUBBLEGUM_PROGRAM_ID } from "@metaplex-foundation/mpl-bubblegum";
+import {
+  PROGRAM_ID as COMPRESSION_PROGRAM_ID,
+  ConcurrentMerkleTreeAccount,
+  SPL_NOOP_PROGRAM_ID,
+} from "@solana/spl-account-compression";
+import { TOKEN_PROGRAM_ID } from "@coral-xyz/anchor/dist/cjs/utils/token";
+import { keypairIdentity, Metaplex } from "@metaplex-foundation/js";
+import { expect } from "chai";
+import {
+  CONNECTION,
+  LEAF_FORMAT_SEED,
+  PROGRAM,
+  SIGNER,
+  TINY_SPL_AUTHORITY_SEED,
+  TREE_CREATOR,
+  TREE_ID,
+} from "../scripts/constants";
+import { ReadApiAsset } from "../scripts/ReadApi/types";
+import { sendAndConfirmIxs } from "../scripts/sendAndConfirmIxs";
+
+export const mplTokenMetadataProgramId = new PublicKey(
+  "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s"
+);
+export const [bubblegumSigner] = PublicKey.findProgramAddressSync(
+  [Buffer.from("collection_cpi")],
+  BUBBLEGUM_PROGRAM_ID
+);
+
+// allowed and denied wallets and sessions are all derived from the collection
+// mint and a wallet
+const getWalletPda = (
+  seed: string,
+  collectionMint: PublicKey,
+  wallet: PublicKey
+) =>
+  PublicKey.findProgramAddressSync(
+    [Buffer.from(seed), collectionMint.toBuffer(), wallet.toBuffer()],
+    PROGRAM.programId
+  )[0];
+
+export const getAllowedWallet = (
+  collectionMint: PublicKey,
+  wallet: PublicKey
+) => getWalletPda("allowed_wallet", collectionMint, wallet);
+
+export const getDeniedWallet = (collectionMint: PublicKey, wallet: PublicKey) =>
+  getWalletPda("denied_wallet", collectionMint, wallet);
+
+export const getCombineSession = (
+  collectionMint: PublicKey,
+  owner: PublicKey
+) => getWalletPda("combine_session", collectionMint, owner);
+
+export const getDistributionSession = (
+  collectionMint: PublicKey,
+  owner: PublicKey
+) => getWalletPda("distribution_session", collectionMint, owner);
+
+export const token2022ProgramId = new PublicKey(
+  "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb"
+);
+const associatedTokenProgramId = new PublicKey(
+  "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+);
+
+export const getTwinMint = (collectionMint: PublicKey) =>
+  PublicKey.findProgramAddressSync(
+    [Buffer.from("twin_mint"), collectionMint.toBuffer()],
+    PROGRAM.programId
+  )[0];
+
+// the associated token 2022 account of the owner for the twin mint
+export const getTwinTokenAccount = (
+  collectionMint: PublicKey,
+  owner: PublicKey
+) =>
+  PublicKey.findProgramAddressSync(
+    [
+      owner.toBuffer(),
+      token2022ProgramId.toBuffer(),
+      getTwinMint(collectionMint).toBuffer(),
+    ],
+    associatedTokenProgramId
+  )[0];
+
+export const getTransferHook = (collectionMint: PublicKey) =>
+  PublicKey.findProgramAddressSync(
+    [Buffer.from("transfer_hook"), collectionMint.toBuffer()],
+    PROGRAM.programId
+  )[0];
+
+export const getTreeAuthority = (merkleTree: PublicKey) =>
+  PublicKey.findProgramAddressSync(
+    [merkleTree.toBuffer()],
+    BUBBLEGUM_PROGRAM_ID
+  )[0];
+
+export const getLeafFormat = (collectionMint: PublicKey, version: number) =>
+  PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(LEAF_FORMAT_SEED),
+      collectionMint.toBuffer(),
+      Buffer.from([version]),
+    ],
+    PROGRAM.programId
+  )[0];
+
+// the accounts every instruction of a tiny spl mint derives from its
+// collection mint
+export type MintAccounts = {
+  collectionMint: PublicKey;
+  collectionMetadata: PublicKey;
+  editionAccount: PublicKey;
+  tinySplAuthority: PublicKey;
+  leafFormat: PublicKey;
+  // only adopted collections mint through a collection authority record
+  collectionAuthorityRecord?: PublicKey;
+};
+
+export const getMintAccounts = (
+  collectionMint: PublicKey,
+  leafFormatVersion = 0
+): MintAccounts => {
+  const [collectionMetadata] = PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("metadata"),
+      mplTokenMetadataProgramId.toBuffer(),
+      collectionMint.toBuffer(),
+    ],
+    mplTokenMetadataProgramId
+  );
+  const [editionAccount] = PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("metadata"),
+      mplTokenMetadataProgramId.toBuffer(),
+      collectionMint.toBuffer(),
+      Buffer.from("edition"),
+    ],
+    mplTokenMetadataProgramId
+  );
+  const [tinySplAuthority] = PublicKey.findProgramAddressSync(
+    [Buffer.from(TINY_SPL_AUTHORITY_SEED), collectionMint.toBuffer()],
+    PROGRAM.programId
+  );
+
+  return {
+    collectionMint,
+    collectionMetadata,
+    editionAccount,
+    tinySplAuthority,
+    leafFormat: getLeafFormat(collectionMint, leafFormatVersion),
+  };
+};
+
+export type ComplianceConfig = {
+  authority: PublicKey;
+  isAllowlistEnabled: boolean;
+  isDenylistEnabled: boolean;
+};
+
+// creates a tiny spl mint with SIGNER as its mint authority, so tests that
+// change a mint do not touch the shared TOKEN_MINT_KEY mint
+export const createTestMint = async ({
+  splTokenProgram = TOKEN_PROGRAM_ID,
+  complianceConfig = null,
+}: {
+  splTokenProgram?: PublicKey;
+  complianceConfig?: ComplianceConfig | null;
+} = {}) => {
+  const collectionMint = Keypair.generate();
+  const mintAccounts = getMintAccounts(collectionMint.publicKey);
+
+  const ix = await PROGRAM.methods
+    .createMint(
+      {
+        name: "Tiny SPL Test",
+        symbol: "TST",
+        uri: "https://example.com/metadata.json",
+        sellerFeeBasisPoints: 0,
+        primarySaleHappened: false,
+        isMutable: true,
+        additionalCreators: [],
+      },
+      complianceConfig
+    )
+    .accounts({
+      metadata: mintAccounts.collectionMetadata,
+      masterEdition: mintAccounts.editionAccount,
+      mint: collectionMint.publicKey,
+      mintAuthority: SIGNER.publicKey,
+      tinySplAuthority: mintAccounts.tinySplAuthority,
+      leafFormat: mintAccounts.leafFormat,
+      systemProgram: SystemProgram.programId,
+      sysvarInstructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+      splTokenProgram,
+      mplTokenMetadataProgram: mplTokenMetadataProgramId,
+    })
+    .instruction();
+
+  const result = await sendAndConfirmIxs(
+    [modifyComputeUnits, ix],
+    SIGNER.publicKey,
+    [SIGNER, collectionMint],
+    true
+  );
+  expect(result.value.err).to.be.null;
+
+  return mintAccounts;
+};
+
+export const getCollectionAuthorityRecord = (
+  collectionMint: PublicKey,
+  collectionAuthority: PublicKey
+) =>
+  PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("metadata"),
+      mplTokenMetadataProgramId.toBuffer(),
+      collectionMint.toBuffer(),
+      Buffer.from("collection_authority"),
+      collectionAuthority.toBuffer(),
+    ],
+    mplTokenMetadataProgramId
+  )[0];
+
+// a collection made outside of tiny spl, with SIGNER as its update authority
+export const createCollection = async ({
+  isSized,
+  creators,
+}: {
+  isSized: boolean;
+  creators: { address: PublicKey; share: number }[];
+}) => {
+  const { nft } = await Metaplex.make(CONNECTION)
+    .use(keypairIdentity(SIGNER))
+    .nfts()
+    .create({
+      name: "Adopted Collection",
+      symbol: "ADPT",
+      uri: "https://example.com/metadata.json",
+      sellerFeeBasisPoints: 500,
+      isCollection: true,
+      collectionIsSized: isSized,
+      creators,
+    });
+
+  return nft.address;
+};
+
+export const fetchCollectionMetadata = (collectionMint: PublicKey) =>
+  Metaplex.make(CONNECTION)
+    .nfts()
+    .findByMint({ mintAddress: collectionMint, loadJsonMetadata: false });
+
+// compliance mints made by `createTestMint` use SIGNER as their compliance
+// authority
+export const allowWallet = async (
+  mintAccounts: MintAccounts,
+  wallet: PublicKey
+) => {
+  const ix = await PROGRAM.methods
+    .allowWallet(wallet)
+    .accounts({
+      complianceAuthority: SIGNER.publicKey,
+      collectionMint: mintAccounts.collectionMint,
+      tinySplAuthority: mintAccounts.tinySplAuthority,
+      allowedWallet: getAllowedWallet(mintAccounts.collectionMint, wallet),
+      systemProgram: SystemProgram.programId,
+    })
+    .instruction();
+  const result = await sendAndConfirmIxs([ix], SIGNER.publicKey, [SIGNER]);
+  expect(result.value.err).to.be.null;
+};
+
+export const disallowWallet = async (
+  mintAccounts: MintAccounts,
+  wallet: PublicKey
+) => {
+  const ix = await PROGRAM.methods
+    .disallowWallet(wallet)
+    .accounts({
+      complianceAuthority: SIGNER.publicKey,
+      collectionMint: mintAccounts.collectionMint,
+      tinySplAuthority: mintAccounts.tinySplAuthority,
+      allowedWallet: getAllowedWallet(mintAccounts.collectionMint, wallet),
+    })
+    .instruction();
+  const result = await sendAndConfirmIxs([ix], SIGNER.publicKey, [SIGNER]);
+  expect(result.value.err).to.be.null;
+};
+
+export const denyWallet = async (
+  mintAccounts: MintAccounts,
+  wallet: PublicKey
+) => {
+  const ix = await PROGRAM.methods
+    .denyWallet(wallet)
+    .accounts({
+      complianceAuthority: SIGNER.publicKey,
+      collectionMint: mintAccounts.collectionMint,
+      tinySplAuthority: mintAccounts.tinySplAuthority,
+      deniedWallet: getDeniedWallet(mintAccounts.collectionMint, wallet),
+      systemProgram: SystemProgram.programId,
+    })
+    .instruction();
+  const result = await sendAndConfirmIxs([ix], SIGNER.publicKey, [SIGNER]);
+  expect(result.value.err).to.be.null;
+};
+
+export const modifyComputeUnits = ComputeBudgetProgram.setComputeUnitLimit({
+  units: 1_400_000,
+});
+
+export const expectCustomError = (
+  result: Awaited<ReturnType<typeof sendAndConfirmIxs>>,
+  errorCode: number
+) => {
+  expect((result.value?.err as any).InstructionError[1].Custom).to.equal(
+    errorCode
+  );
+};
+
+// every test owns its leaves through a fresh wallet, so it does not depend on
+// what earlier tests left in the wallet of SIGNER. SIGNER still pays the fees
+export const createOwner = async () => {
+  const owner = Keypair.generate();
+  const result = await sendAndConfirmIxs(
+    [
+      SystemProgram.transfer({
+        fromPubkey: SIGNER.publicKey,
+        toPubkey: owner.publicKey,
+        lamports: LAMPORTS_PER_SOL / 100,
+      }),
+    ],
+    SIGNER.publicKey,
+    [SIGNER]
+  );
+  expect(result.value.err).to.be.null;
+
+  return owner;
+};
+
+// newest first
+const fetchAssets = async (owner: PublicKey, collectionMint?: PublicKey) => {
+  const { items } = await CONNECTION.getAssetsByOwner({
+    ownerAddress: owner.toBase58(),
+    limit: 1000,
+    sortBy: {
+      sortBy: "created",
+      sortDirection: "desc",
+    },
+  });
+  return items.filter(
+    (asset) =>
+      !collectionMint ||
+      asset.grouping.some(
+        ({ group_value }) => group_value === collectionMint.toBase58()
+      )
+  );
+};
+
+// waits for the indexer until the owner holds at least `count` leaves, leaves
+// in `spent` were burned by the transaction under test and are skipped while
+// the indexer still lists them
+export const getNewestAssets = async (
+  owner: PublicKey,
+  count: number,
+  collectionMint?: PublicKey,
+  spent: Leaf[] = []
+): Promise<ReadApiAsset[]> => {
+  for (let attempt = 0; ; attempt++) {
+    const assets = (await fetchAssets(owner, collectionMint)).filter(
+      ({ id }) => !spent.some(({ asset }) => asset.id === id)
+    );
+    if (assets.length >= count || attempt === 10) {
+      return assets.slice(0, count);
+    }
+    await new Promise((resolve) => setTimeout(resolve, 1000));
+  }
+};
+
+export const getAssetAmount = (asset: ReadApiAsset) =>
+  parseInt(new URL(asset.content.json_uri).searchParams.get("amount"));
+
+export type Leaf = {
+  asset: ReadApiAsset;
+  assetId: PublicKey;
+  amount: number;
+  owner: PublicKey;
+  delegate: PublicKey;
+  merkleTree: PublicKey;
+  treeAuthority: PublicKey;
+  root: number[];
+  nonce: anchor.BN;
+  index: number;
+  // the proof without the nodes the canopy of the tree already stores
+  proofPath: AccountMeta[];
+  // the format the leaf was minted in, when it is not the one of the mint
+  // accounts it is used with
+  leafFormat?: PublicKey;
+};
+
+export const getLeaf = async (asset: ReadApiAsset): Promise<Leaf> => {
+  const assetProof = await CONNECTION.getAssetProof(new PublicKey(asset.id));
+  const merkleTree = new PublicKey(asset.compression.tree);
+  const treeAccount = await ConcurrentMerkleTreeAccount.fromAccountAddress(
+    CONNECTION,
+    merkleTree
+  );
+  const canopyDepth = treeAccount.getCanopyDepth();
+  const owner = new PublicKey(asset.ownership.owner);
+
+  return {
+    asset,
+    assetId: new PublicKey(asset.id),
+    amount: getAssetAmount(asset),
+    owner,
+    // the indexer has no delegate for leaves that are delegated to their owner
+    delegate: asset.ownership.delegate
+      ? new PublicKey(asset.ownership.delegate)
+      : owner,
+    merkleTree,
+    treeAuthority: getTreeAuthority(merkleTree),
+    root: [...new PublicKey(assetProof.root.trim()).toBytes()],
+    nonce: new anchor.BN(asset.compression.leaf_id),
+    index: asset.compression.leaf_id,
+    proofPath: assetProof.proof
+      .map((node: string) => ({
+        pubkey: new PublicKey(node),
+        isSigner: false,
+        isWritable: false,
+      }))
+      .slice(0, assetProof.proof.length - (!!canopyDepth ? canopyDepth : 0)),
+  };
+};
+
+// the allowed and denied wallet accounts of every wallet an instruction
+// checks, so the same builders work for mints with and without compliance.
+// `{ leafOwner }` becomes `leafOwnerAllowedWallet` and `leafOwnerDeniedWallet`,
+// and anchor skips the ones an instruction does not have
+export const getWalletAccounts = (
+  mintAccounts: MintAccounts,
+  wallets: Record<string, PublicKey>
+) =>
+  Object.fromEntries(
+    Object.entries(wallets).flatMap(([name, wallet]) => [
+      [
+        `${name}AllowedWallet`,
+        getAllowedWallet(mintAccounts.collectionMint, wallet),
+      ],
+      [
+        `${name}DeniedWallet`,
+        getDeniedWallet(mintAccounts.collectionMint, wallet),
+      ],
+    ])
+  );
+
+export const getTreeTableEntry = (merkleTree: PublicKey): AccountMeta[] => [
+  { pubkey: merkleTree, isSigner: false, isWritable: true },
+  { pubkey: getTreeAuthority(merkleTree), isSigner: false, isWritable: true },
+];
+
+export const buildMintToIx = async ({
+  mintAccounts,
+  owner,
+  amount,
+  merkleTree = TREE_ID,
+  accounts = {},
+}: {
+  mintAccounts: MintAccounts;
+  owner: PublicKey;
+  amount: number;
+  merkleTree?: PublicKey;
+  accounts?: Record<string, PublicKey | null>;
+}) =>
+  PROGRAM.methods
+    .mintTo(new anchor.BN(amount), null)
+    .accounts({
+      bubblegumSigner,
+      ...mintAccounts,
+      compressionProgram: COMPRESSION_PROGRAM_ID,
+      newLeafOwner: owner,
+      logWrapper: SPL_NOOP_PROGRAM_ID,
+      merkleTree,
+      mintAuthority: SIGNER.publicKey,
+      mplBubblegumProgram: BUBBLEGUM_PROGRAM_ID,
+      tokenMetadataProgram: mplTokenMetadataProgramId,
+      treeAuthority: getTreeAuthority(merkleTree),
+      treeCreatorOrDelegate: TREE_CREATOR.publicKey,
+      ...getWalletAccounts(mintAccounts, { newLeafOwner: owner }),
+      ...accounts,
+    })
+    .instruction();
+
+// mints a leaf of every amount to the owner and returns them in mint order
+export const mintLeaves = async (
+  mintAccounts: MintAccounts,
+  owner: PublicKey,
+  amounts: number[]
+): Promise<Leaf[]> => {
+  const previousCount = (
+    await fetchAssets(owner, mintAccounts.collectionMint)
+  ).length;
+  for (const amount of amounts) {
+    const result = await sendIxs(
+      [await buildMintToIx({ mintAccounts, owner, amount })],
+      []
+    );
+    expect(result.value.err).to.be.null;
+  }
+
+  const assets = await getNewestAssets(
+    owner,
+    previousCount + amounts.length,
+    mintAccounts.collectionMint
+  );
+  const leaves = await Promise.all(
+    assets.slice(0, amounts.length).reverse().map(getLeaf)
+  );
+  return leaves.map((leaf) => ({
+    ...leaf,
+    leafFormat: mintAccounts.leafFormat,
+  }));
+};
+
+export const buildSplitIx = async ({
+  mintAccounts,
+  leaf,
+  amounts,
+  sourceAmount = leaf.amount,
+  destinationMerkleTree = TREE_ID,
+  extraTrees = [],
+  treeIndexes = [],
+  preserveDelegate = false,
+  accounts = {},
+}: {
+  mintAccounts: MintAccounts;
+  leaf: Leaf;
+  amounts: number[];
+  sourceAmount?: number;
+  destinationMerkleTree?: PublicKey;
+  // further destination trees, addressed by tree index 1 and up
+  extraTrees?: PublicKey[];
+  treeIndexes?: number[];
+  preserveDelegate?: boolean;
+  accounts?: Record<string, PublicKey | null>;
+}) =>
+  PROGRAM.methods
+    .split(
+      new anchor.BN(sourceAmount),
+      leaf.assetId,
+      leaf.root,
+      leaf.nonce,
+      leaf.index,
+      {
+        amounts: amounts.map((amount) => new anchor.BN(amount)),
+        treeIndexes,
+        extraTreeCount: extraTrees.length,
+        preserveDelegate,
+      }
+    )
+    .accounts({
+      leafOwner: leaf.owner,
+      leafDelegate: leaf.delegate,
+      authority: leaf.owner,
+      newLeafOwner: leaf.owner,
+      compressionProgram: COMPRESSION_PROGRAM_ID,
+      ...mintAccounts,
+      sourceLeafFormat: leaf.leafFormat ?? mintAccounts.leafFormat,
+      sourceMerkleTree: leaf.merkleTree,
+      sourceTreeAuthority: leaf.treeAuthority,
+      destinationMerkleTree,
+      destinationTreeAuthority: getTreeAuthority(destinationMerkleTree),
+      systemProgram: SystemProgram.programId,
+      bubblegumSigner,
+      logWrapper: SPL_NOOP_PROGRAM_ID,
+      mplBubblegumProgram: BUBBLEGUM_PROGRAM_ID,
+      tokenMetadataProgram: mplTokenMetadataProgramId,
+      treeCreatorOrDelegate: TREE_CREATOR.publicKey,
+      ...getWalletAccounts(mintAccounts, {
+        authority: leaf.owner,
+        leafOwner: leaf.owner,
+        newLeafOwner: leaf.owner,
+      }),
+      ...accounts,
+    })
+    .remainingAccounts([
+      ...extraTrees.flatMap(getTreeTableEntry),
+      ...leaf.proofPath,
+    ])
+    .instruction();
+
+// the leaf batch of `combine`, `absorb` and `fund_distribution_session`, with
+// the remaining accounts it refers to. leaves in another format than
+// `sourceLeafFormat` get their format passed in the leaf format table
+export const buildLeafBatch = (
+  leaves: Leaf[],
+  sourceLeafFormat: PublicKey
+) => {
+  const trees = [
+    ...new Set(leaves.map(({ merkleTree }) => merkleTree.toBase58())),
+  ].map((tree) => new PublicKey(tree));
+  const leafFormats = [
+    sourceLeafFormat,
+    ...new Set(
+      leaves
+        .map(({ leafFormat }) => (leafFormat ?? sourceLeafFormat).toBase58())
+        .filter((leafFormat) => leafFormat !== sourceLeafFormat.toBase58())
+    ),
+  ].map((leafFormat) => new PublicKey(leafFormat));
+
+  return {
+    leafBatch: {
+      inputs: leaves.map((leaf) => ({
+        amount: new anchor.BN(leaf.amount),
+        assetId: leaf.assetId,
+        root: leaf.root,
+        nonce: leaf.nonce,
+        index: leaf.index,
+        treeIndex: trees.findIndex((tree) => tree.equals(leaf.merkleTree)),
+        leafFormatIndex: leafFormats.findIndex((leafFormat) =>
+          leafFormat.equals(leaf.leafFormat ?? sourceLeafFormat)
+        ),
+        proofLength: leaf.proofPath.length,
+      })),
+      treeCount: trees.length,
+      leafFormatCount: leafFormats.length - 1,
+    },
+    remainingAccounts: [
+      ...trees.flatMap(getTreeTableEntry),
+      ...leafFormats.slice(1).map((leafFormat) => ({
+        pubkey: leafFormat,
+        isSigner: false,
+        isWritable: false,
+      })),
+      ...leaves.flatMap(({ proofPath }) => proofPath),
+    ],
+  };
+};
+
+// every leaf has to be owned by the owner of the first one
+export const buildCombineIx = async ({
+  mintAccounts,
+  leaves,
+  destinationTreeIndex = null,
+  preserveDelegate = false,
+  accounts = {},
+}: {
+  mintAccounts: MintAccounts;
+  leaves: Leaf[];
+  destinationTreeIndex?: number | null;
+  preserveDelegate?: boolean;
+  accounts?: Record<string, PublicKey | null>;
+}) => {
+  const { leafBatch, remainingAccounts } = buildLeafBatch(
+    leaves,
+    mintAccounts.leafFormat
+  );
+  const [{ owner, delegate }] = leaves;
+
+  return PROGRAM.methods
+    .combine(leafBatch, destinationTreeIndex, preserveDelegate)
+    .accounts({
+      authority: owner,
+      leafOwner: owner,
+      leafDelegate: delegate,
+      newLeafOwner: owner,
+      bubblegumSigner,
+      ...mintAccounts,
+      sourceLeafFormat: mintAccounts.leafFormat,
+      compressionProgram: COMPRESSION_PROGRAM_ID,
+      logWrapper: SPL_NOOP_PROGRAM_ID,
+      destinationMerkleTree: TREE_ID,
+      destinationTreeAuthority: getTreeAuthority(TREE_ID),
+      mplBubblegumProgram: BUBBLEGUM_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+      tokenMetadataProgram: mplTokenMetadataProgramId,
+      treeCreatorOrDelegate: TREE_CREATOR.publicKey,
+      ...getWalletAccounts(mintAccounts, {
+        authority: owner,
+        leafOwner: owner,
+        newLeafOwner: owner,
+      }),
+      ...accounts,
+    })
+    .remainingAccounts(remainingAccounts)
+    .instruction();
+};
+
+// `mintAccounts` are those of the current leaf format, which the new one
+// takes its royalties and creators from
+export const buildSetLeafFormatIx = (
+  mintAccounts: MintAccounts,
+  version: number,
+  uriTemplate: string,
+  metadataAuthority = SIGNER.publicKey
+) =>
+  PROGRAM.methods
+    .setLeafFormat(version, "TST", uriTemplate, { grouped: {} })
+    .accounts({
+      metadataAuthority,
+      collectionMint: mintAccounts.collectionMint,
+      tinySplAuthority: mintAccounts.tinySplAuthority,
+      currentLeafFormat: mintAccounts.leafFormat,
+      leafFormat: getLeafFormat(mintAccounts.collectionMint, version),
+      systemProgram: SystemProgram.programId,
+    })
+    .instruction();
+
+// reissues the leaf in the leaf format of `mintAccounts` to TREE_ID
+export const buildMigrateLeafIx = (mintAccounts: MintAccounts, leaf: Leaf) =>
+  PROGRAM.methods
+    .migrateLeaf(
+      new anchor.BN(leaf.amount),
+      leaf.assetId,
+      leaf.root,
+      leaf.nonce,
+      leaf.index
+    )
+    .accounts({
+      authority: leaf.owner,
+      leafOwner: leaf.owner,
+      leafDelegate: leaf.delegate,
+      treeCreatorOrDelegate: TREE_CREATOR.publicKey,
+      ...mintAccounts,
+      sourceLeafFormat: leaf.leafFormat ?? mintAccounts.leafFormat,
+      bubblegumSigner,
+      sourceMerkleTree: leaf.merkleTree,
+      sourceTreeAuthority: leaf.treeAuthority,
+      destinationMerkleTree: TREE_ID,
+      destinationTreeAuthority: getTreeAuthority(TREE_ID),
+      logWrapper: SPL_NOOP_PROGRAM_ID,
+      compressionProgram: COMPRESSION_PROGRAM_ID,
+      tokenMetadataProgram: mplTokenMetadataProgramId,
+      systemProgram: SystemProgram.programId,
+      mplBubblegumProgram: BUBBLEGUM_PROGRAM_ID,
+    })
+    .remainingAccounts(leaf.proofPath)
+    .instruction();
+
+export const buildUpdateCollectionMetadataIx = (
+  mintAccounts: MintAccounts,
+  name: string,
+  metadataAuthority = SIGNER.publicKey
+) =>
+  PROGRAM.methods
+    .updateCollectionMetadata({ name, symbol: null, uri: null })
+    .accounts({
+      metadataAuthority,
+      ...mintAccounts,
+      leafFormat: getLeafFormat(mintAccounts.collectionMint, 0),
+      systemProgram: SystemProgram.programId,
+      sysvarInstructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+      tokenMetadataProgram: mplTokenMetadataProgramId,
+    })
+    .instruction();
+
+export const buildLockCollectionMetadataIx = (
+  mintAccounts: MintAccounts,
+  metadataAuthority = SIGNER.publicKey
+) =>
+  PROGRAM.methods
+    .lockCollectionMetadata()
+    .accounts({
+      metadataAuthority,
+      collectionMint: mintAccounts.collectionMint,
+      collectionMetadata: mintAccounts.collectionMetadata,
+      editionAccount: mintAccounts.editionAccount,
+      tinySplAuthority: mintAccounts.tinySplAuthority,
+      systemProgram: SystemProgram.programId,
+      sysvarInstructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+      tokenMetadataProgram: mplTokenMetadataProgramId,
+    })
+    .instruction();
+
+export const buildTransferMetadataAuthorityIx = (
+  mintAccounts: MintAccounts,
+  newMetadataAuthority: PublicKey | null,
+  metadataAuthority = SIGNER.publicKey
+) =>
+  PROGRAM.methods
+    .transferMetadataAuthority(newMetadataAuthority)
+    .accounts({
+      metadataAuthority,
+      collectionMint: mintAccounts.collectionMint,
+      tinySplAuthority: mintAccounts.tinySplAuthority,
+    })
+    .instruction();
+
+export const buildCreateTwinMintIx = (mintAccounts: MintAccounts) =>
+  PROGRAM.methods
+    .createTwinMint()
+    .accounts({
+      mintAuthority: SIGNER.publicKey,
+      collectionMint: mintAccounts.collectionMint,
+      tinySplAuthority: mintAccounts.tinySplAuthority,
+      twinMint: getTwinMint(mintAccounts.collectionMint),
+      tokenProgram: token2022ProgramId,
+      systemProgram: SystemProgram.programId,
+    })
+    .instruction();
+
+// `CreateIdempotent` of the associated token program, paid by SIGNER
+export const buildCreateTwinTokenAccountIx = (
+  mintAccounts: MintAccounts,
+  owner: PublicKey
+) =>
+  new TransactionInstruction({
+    programId: associatedTokenProgramId,
+    keys: [
+      { pubkey: SIGNER.publicKey, isSigner: true, isWritable: true },
+      {
+        pubkey: getTwinTokenAccount(mintAccounts.collectionMint, owner),
+        isSigner: false,
+        isWritable: true,
+      },
+      { pubkey: owner, isSigner: false, isWritable: false },
+      {
+        pubkey: getTwinMint(mintAccounts.collectionMint),
+        isSigner: false,
+        isWritable: false,
+      },
+      {
+        pubkey: SystemProgram.programId,
+        isSigner: false,
+        isWritable: false,
+      },
+      { pubkey: token2022ProgramId, isSigner: false, isWritable: false },
+    ],
+    data: Buffer.from([1]),
+  });
+
+// the fungible tokens go to the twin token account of the leaf owner
+export const buildDecompressIx = (mintAccounts: MintAccounts, leaf: Leaf) =>
+  PROGRAM.methods
+    .decompress(
+      new anchor.BN(leaf.amount),
+      leaf.assetId,
+      leaf.root,
+      leaf.nonce,
+      leaf.index
+    )
+    .accounts({
+      authority: leaf.owner,
+      leafOwner: leaf.owner,
+      leafDelegate: leaf.delegate,
+      collectionMint: mintAccounts.collectionMint,
+      tinySplAuthority: mintAccounts.tinySplAuthority,
+      sourceLeafFormat: leaf.leafFormat ?? mintAccounts.leafFormat,
+      sourceTreeAuthority: leaf.treeAuthority,
+      sourceMerkleTree: leaf.merkleTree,
+      twinMint: getTwinMint(mintAccounts.collectionMint),
+      destinationTokenAccount: getTwinTokenAccount(
+        mintAccounts.collectionMint,
+        leaf.owner
+      ),
+      tokenProgram: token2022ProgramId,
+      logWrapper: SPL_NOOP_PROGRAM_ID,
+      compressionProgram: COMPRESSION_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+      mplBubblegumProgram: BUBBLEGUM_PROGRAM_ID,
+    })
+    .remainingAccounts(leaf.proofPath)
+    .instruction();
+
+// burns fungible tokens of the owner into a new leaf of the owner in TREE_ID
+export const buildCompressIx = (
+  mintAccounts: MintAccounts,
+  owner: PublicKey,
+  amount: number
+) =>
+  PROGRAM.methods
+    .compress(new anchor.BN(amount))
+    .accounts({
+      owner,
+      sourceTokenAccount: getTwinTokenAccount(
+        mintAccounts.collectionMint,
+        owner
+      ),
+      twinMint: getTwinMint(mintAccounts.collectionMint),
+      treeAuthority: getTreeAuthority(TREE_ID),
+      newLeafOwner: owner,
+      newLeafDelegate: null,
+      merkleTree: TREE_ID,
+      treeCreatorOrDelegate: TREE_CREATOR.publicKey,
+      ...mintAccounts,
+      bubblegumSigner,
+      tokenProgram: token2022ProgramId,
+      logWrapper: SPL_NOOP_PROGRAM_ID,
+      compressionProgram: COMPRESSION_PROGRAM_ID,
+      tokenMetadataProgram: mplTokenMetadataProgramId,
+      systemProgram: SystemProgram.programId,
+      mplBubblegumProgram: BUBBLEGUM_PROGRAM_ID,
+    })
+    .instruction();
+
+// registers a transfer hook without extra accounts, SIGNER being the mint
+// authority of mints made by `createTestMint`
+export const buildSetTransferHookIx = (
+  mintAccounts: MintAccounts,
+  programId: PublicKey
+) =>
+  PROGRAM.methods
+    .setTransferHook(programId, [])
+    .accounts({
+      mintAuthority: SIGNER.publicKey,
+      collectionMint: mintAccounts.collectionMint,
+      tinySplAuthority: mintAccounts.tinySplAuthority,
+      transferHook: getTransferHook(mintAccounts.collectionMint),
+      systemProgram: SystemProgram.programId,
+    })
+    .instruction();
+
+// sends instructions with SIGNER paying. TREE_CREATOR signs every mint into
+// the test trees, which anchor cannot tell from the idl, and keypairs that no
+// instruction needs are left out because a transaction rejects them
+export const sendIxs = (ixs: TransactionInstruction[], signers: Keypair[]) => {
+  for (const key of ixs.flatMap(({ keys }) => keys)) {
+    if (key.pubkey.equals(TREE_CREATOR.publicKey)) {
+      key.isSigner = true;
+    }
+  }
+  const requiredSigners = new Set(
+    ixs.flatMap(({ keys }) =>
+      keys
+        .filter(({ isSigner }) => isSigner)
+        .map(({ pubkey }) => pubkey.toBase58())
+    )
+  );
+
+  return sendAndConfirmIxs(
+    [modifyComputeUnits, ...ixs],
+    SIGNER.publicKey,
+    [
+      SIGNER,
+      ...[TREE_CREATOR, ...signers].filter(({ publicKey }) =>
+        requiredSigners.has(publicKey.toBase58())
+      ),
+    ],
+    true
+  );
+};
+
+export const sendAsOwner = (
+  ixs: TransactionInstruction[],
+  owner: Keypair,
+  signers: Keypair[] = []
+) => sendIxs(ixs, [owner, ...signers]);
+
+export const buildOpenCombineSessionIx = (
+  mintAccounts: MintAccounts,
+  owner: PublicKey
+) =>
+  PROGRAM.methods
+    .openCombineSession()
+    .accounts({
+      owner,
+      collectionMint: mintAccounts.collectionMint,
+      tinySplAuthority: mintAccounts.tinySplAuthority,
+      combineSession: getCombineSession(mintAccounts.collectionMint, owner),
+      systemProgram: SystemProgram.programId,
+    })
+    .instruction();
+
+export const buildAbsorbIx = (mintAccounts: MintAccounts, leaves: Leaf[]) => {
+  const { leafBatch, remainingAccounts } = buildLeafBatch(
+    leaves,
+    mintAccounts.leafFormat
+  );
+  const [{ owner, delegate }] = leaves;
+
+  return PROGRAM.methods
+    .absorb(leafBatch)
+    .accounts({
+      owner,
+      leafDelegate: delegate,
+      ownerDeniedWallet: getDeniedWallet(mintAccounts.collectionMint, owner),
+      collectionMint: mintAccounts.collectionMint,
+      tinySplAuthority: mintAccounts.tinySplAuthority,
+      sourceLeafFormat: mintAccounts.leafFormat,
+      combineSession: getCombineSession(mintAccounts.collectionMint, owner),
+      logWrapper: SPL_NOOP_PROGRAM_ID,
+      compressionProgram: COMPRESSION_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+      mplBubblegumProgram: BUBBLEGUM_PROGRAM_ID,
+    })
+    .remainingAccounts(remainingAccounts)
+    .instruction();
+};
+
+// the combined leaf is minted to TREE_ID, and to the owner unless an
+// authority picks another wallet for an expired session
+export const buildCloseCombineSessionIx = (
+  mintAccounts: MintAccounts,
+  owner: PublicKey,
+  authority = owner,
+  newLeafOwner: PublicKey | null = null
+) =>
+  PROGRAM.methods
+    .closeCombineSession()
+    .accounts({
+      authority,
+      owner,
+      newLeafOwner,
+      ...getWalletAccounts(mintAccounts, {
+        newLeafOwner: newLeafOwner ?? owner,
+      }),
+      combineSession: getCombineSession(mintAccounts.collectionMint, owner),
+      treeAuthority: getTreeAuthority(TREE_ID),
+      merkleTree: TREE_ID,
+      treeCreatorOrDelegate: TREE_CREATOR.publicKey,
+      ...mintAccounts,
+      bubblegumSigner,
+      logWrapper: SPL_NOOP_PROGRAM_ID,
+      compressionProgram: COMPRESSION_PROGRAM_ID,
+      tokenMetadataProgram: mplTokenMetadataProgramId,
+      systemProgram: SystemProgram.programId,
+      mplBubblegumProgram: BUBBLEGUM_PROGRAM_ID,
+    })
+    .instruction();
+
+export const buildOpenDistributionSessionIx = (
+  mintAccounts: MintAccounts,
+  owner: PublicKey
+) =>
+  PROGRAM.methods
+    .openDistributionSession()
+    .accounts({
+      owner,
+      collectionMint: mintAccounts.collectionMint,
+      tinySplAuthority: mintAccounts.tinySplAuthority,
+      distributionSession: getDistributionSession(
+        mintAccounts.collectionMint,
+        owner
+      ),
+      systemProgram: SystemProgram.programId,
+    })
+    .instruction();
+
+export const buildFundDistributionSessionIx = (
+  mintAccounts: MintAccounts,
+  leaves: Leaf[]
+) => {
+  const { leafBatch, remainingAccounts } = buildLeafBatch(
+    leaves,
+    mintAccounts.leafFormat
+  );
+  const [{ owner, delegate }] = leaves;
+
+  return PROGRAM.methods
+    .fundDistributionSession(leafBatch)
+    .accounts({
+      owner,
+      leafDelegate: delegate,
+      ownerDeniedWallet: getDeniedWallet(mintAccounts.collectionMint, owner),
+      collectionMint: mintAccounts.collectionMint,
+      tinySplAuthority: mintAccounts.tinySplAuthority,
+      sourceLeafFormat: mintAccounts.leafFormat,
+      distributionSession: getDistributionSession(
+        mintAccounts.collectionMint,
+        owner
+      ),
+      logWrapper: SPL_NOOP_PROGRAM_ID,
+      compressionProgram: COMPRESSION_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+      mplBubblegumProgram: BUBBLEGUM_PROGRAM_ID,
+    })
+    .remainingAccounts(remainingAccounts)
+    .instruction();
+};
+
+// every recipient gets a leaf in TREE_ID
+export const buildDistributeIx = async (
+  mintAccounts: MintAccounts,
+  owner: PublicKey,
+  recipients: { wallet: PublicKey; amount: number }[]
+) => {
+  const { isAllowlistEnabled, isDenylistEnabled } =
+    await PROGRAM.account.tinySplAuthority.fetch(mintAccounts.tinySplAuthority);
+  const wallets = recipients.map(({ wallet }) => wallet);
+  const toAccountMetas = (pubkeys: PublicKey[]): AccountMeta[] =>
+    pubkeys.map((pubkey) => ({ pubkey, isSigner: false, isWritable: false }));
+
+  return PROGRAM.methods
+    .distribute(recipients.map(({ amount }) => new anchor.BN(amount)))
+    .accounts({
+      owner,
+      ownerDeniedWallet: getDeniedWallet(mintAccounts.collectionMint, owner),
+      distributionSession: getDistributionSession(
+        mintAccounts.collectionMint,
+        owner
+      ),
+      treeAuthority: getTreeAuthority(TREE_ID),
+      merkleTree: TREE_ID,
+      treeCreatorOrDelegate: TREE_CREATOR.publicKey,
+      ...mintAccounts,
+      bubblegumSigner,
+      logWrapper: SPL_NOOP_PROGRAM_ID,
+      compressionProgram: COMPRESSION_PROGRAM_ID,
+      tokenMetadataProgram: mplTokenMetadataProgramId,
+      systemProgram: SystemProgram.programId,
+      mplBubblegumProgram: BUBBLEGUM_PROGRAM_ID,
+    })
+    // recipients, then their allowed wallets and their denied wallets for
+    // mints that check them
+    .remainingAccounts([
+      ...toAccountMetas(wallets),
+      ...(isAllowlistEnabled
+        ? toAccountMetas(
+            wallets.map((wallet) =>
+              getAllowedWallet(mintAccounts.collectionMint, wallet)
+            )
+          )
+        : []),
+      ...(isDenylistEnabled
+        ? toAccountMetas(
+            wallets.map((wallet) =>
+              getDeniedWallet(mintAccounts.collectionMint, wallet)
+            )
+          )
+        : []),
+    ])
+    .instruction();
+};
+
+export const buildCloseDistributionSessionIx = (
+  mintAccounts: MintAccounts,
+  owner: PublicKey,
+  distributionSession = getDistributionSession(
+    mintAccounts.collectionMint,
+    owner
+  )
+) =>
+  PROGRAM.methods
+    .closeDistributionSession()
+    .accounts({
+      owner,
+      ...getWalletAccounts(mintAccounts, { owner }),
+      distributionSession,
+      treeAuthority: getTreeAuthority(TREE_ID),
+      merkleTree: TREE_ID,
+      treeCreatorOrDelegate: TREE_CREATOR.publicKey,
+      ...mintAccounts,
+      bubblegumSigner,
+      logWrapper: SPL_NOOP_PROGRAM_ID,
+      compressionProgram: COMPRESSION_PROGRAM_ID,
+      tokenMetadataProgram: mplTokenMetadataProgramId,
+      systemProgram: SystemProgram.programId,
+      mplBubblegumProgram: BUBBLEGUM_PROGRAM_ID,
+    })
+    .instruction();
@@ -1,76 +1,126 @@
 import * as anchor from "@coral-xyz/anchor";
-import { AccountMeta, ComputeBudgetProgram, PublicKey } from "@solana/web3.js";
+import { PublicKey } from "@solana/web3.js";
 import {
   CONNECTION,
   PROGRAM,
   SIGNER,
-  TINY_SPL_AUTHORITY_SEED,
   TOKEN_MINT_KEY,
   TREE_CREATOR,
   TREE_ID,
   WRONG_AUTHORITY,
 } from "../scripts/constants";
-import {
-  PROGRAM_ID as BUBBLEGUM_PROGRAM_ID,
-  MetadataArgs,
-  Creator,
-} from "@metaplex-foundation/mpl-bubblegum";
+import { PROGRAM_ID as BUBBLEGUM_PROGRAM_ID } from "@metaplex-foundation/mpl-bubblegum";
 import {
   PROGRAM_ID as COMPRESSION_PROGRAM_ID,
-  ConcurrentMerkleTreeAccount,
   SPL_NOOP_PROGRAM_ID,
 } from "@solana/spl-account-compression";
 import { assert, expect } from "chai";
 import { sendAndConfirmIxs } from "../scripts/sendAndConfirmIxs";
-import { BN } from "bn.js";
-
-const mplTokenMetadataProgramId = new PublicKey(
-  "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s"
-);
-const [treeAuthority] = PublicKey.findProgramAddressSync(
-  [TREE_ID.toBuffer()],
-  BUBBLEGUM_PROGRAM_ID
-);
+import {
+  bubblegumSigner,
+  buildCombineIx,
+  buildCompressIx,
+  buildCreateTwinMintIx,
+  buildCreateTwinTokenAccountIx,
+  buildDecompressIx,
+  buildLockCollectionMetadataIx,
+  buildMigrateLeafIx,
+  buildSetLeafFormatIx,
+  buildSetTransferHookIx,
+  buildSplitIx,
+  buildTransferMetadataAuthorityIx,
+  buildUpdateCollectionMetadataIx,
+  buildCloseDistributionSessionIx,
+  buildDistributeIx,
+  buildFundDistributionSessionIx,
+  buildOpenCombineSessionIx,
+  buildOpenDistributionSessionIx,
+  createCollection,
+  createOwner,
+  createTestMint,
+  denyWallet,
+  disallowWallet,
+  expectCustomError,
+  fetchCollectionMetadata,
+  getAssetAmount,
+  getCollectionAuthorityRecord,
+  getCombineSession,
+  getDistributionSession,
+  getLeaf,
+  getLeafFormat,
+  getMintAccounts,
+  getNewestAssets,
+  getTransferHook,
+  getTreeAuthority,
+  getTwinTokenAccount,
+  getWalletAccounts,
+  mintLeaves,
+  mplTokenMetadataProgramId,
+  sendAsOwner,
+  sendIxs,
+  token2022ProgramId,
+} from "./helpers";
+
+// only the tests need a second tree, which split outputs can spill over into.
+// like TREE_ADDRESS it has to be a bubblegum tree that TREE_CREATOR can mint to
+const SECOND_TREE_ID = new PublicKey(process.env.SECOND_TREE_ADDRESS!);
+const treeAuthority = getTreeAuthority(TREE_ID);
 const mint = TOKEN_MINT_KEY.publicKey;
-const [metadata] = PublicKey.findProgramAddressSync(
-  [
-    Buffer.from("metadata"),
-    mplTokenMetadataProgramId.toBuffer(),
-    mint.toBuffer(),
-  ],
-  mplTokenMetadataProgramId
-);
-const [masterEdition] = PublicKey.findProgramAddressSync(
-  [
-    Buffer.from("metadata"),
-    mplTokenMetadataProgramId.toBuffer(),
-    mint.toBuffer(),
-    Buffer.from("edition"),
-  ],
-  mplTokenMetadataProgramId
-);
-const [bubblegumSigner] = PublicKey.findProgramAddressSync(
-  [Buffer.from("collection_cpi")],
-  BUBBLEGUM_PROGRAM_ID
-);
-const [tinySplAuthority] = PublicKey.findProgramAddressSync(
-  [Buffer.from(TINY_SPL_AUTHORITY_SEED), mint.toBuffer()],
-  PROGRAM.programId
-);
+// the test mint never changes its leaf format, so every leaf is in version 0
+const mintAccounts = getMintAccounts(mint);
+const {
+  collectionMetadata: metadata,
+  editionAccount: masterEdition,
+  tinySplAuthority,
+  leafFormat,
+} = mintAccounts;
+
+// reads the metadata address out of the metadata pointer extension of a
+// token 2022 mint, extensions start after the 165 byte base account and its
+// account type byte as (type: u16, length: u16, value) entries
+const METADATA_POINTER_EXTENSION = 18;
+const getMetadataPointerAddress = (mintData: Buffer): PublicKey | null => {
+  let offset = 166;
+  while (offset + 4 <= mintData.length) {
+    const extensionType = mintData.readUInt16LE(offset);
+    const length = mintData.readUInt16LE(offset + 2);
+    if (extensionType === METADATA_POINTER_EXTENSION) {
+      // authority followed by the metadata address
+      return new PublicKey(
+        mintData.subarray(offset + 4 + 32, offset + 4 + 64)
+      );
+    }
+    offset += 4 + length;
+  }
+  return null;
+};
 
 const TOKENS_TO_MINT = 3;
+// compliance mints made by the tests use SIGNER as their compliance authority
+const ALLOWLIST_CONFIG = {
+  authority: SIGNER.publicKey,
+  isAllowlistEnabled: true,
+  isDenylistEnabled: false,
+};
+const DENYLIST_CONFIG = {
+  authority: SIGNER.publicKey,
+  isAllowlistEnabled: false,
+  isDenylistEnabled: true,
+};
+const LEAF_DELEGATE = anchor.web3.Keypair.generate().publicKey;
 
 describe("tiny-spl", () => {
   anchor.setProvider(anchor.AnchorProvider.env());
 
   it("should fail when invalid mint authority tries to mint", async () => {
     const ix = await PROGRAM.methods
-      .mintTo(new anchor.BN(Number.MAX_SAFE_INTEGER))
+      .mintTo(new anchor.BN(Number.MAX_SAFE_INTEGER), null)
       .accounts({
         bubblegumSigner,
         collectionMetadata: metadata,
         collectionMint: mint,
         tinySplAuthority,
+        leafFormat,
         compressionProgram: COMPRESSION_PROGRAM_ID,
         editionAccount: masterEdition,
         newLeafOwner: SIGNER.publicKey,
@@ -112,6 +162,7 @@ describe("tiny-spl", () => {
         collectionMetadata: metadata,
         collectionMint: mint,
         tinySplAuthority,
+        leafFormat,
         compressionProgram: COMPRESSION_PROGRAM_ID,
         editionAccount: masterEdition,
         newLeafOwner: SIGNER.publicKey,
@@ -137,413 +188,1406 @@ describe("tiny-spl", () => {
     assert(currentSupply.eq(prevSupply.add(MINT_COUNT)));
   });
 
+  // each account is swapped for one that looks right but is not derived from
+  // the collection mint or the tree
+  const [lookAlikeTreeAuthority] = PublicKey.findProgramAddressSync(
+    [anchor.web3.Keypair.generate().publicKey.toBuffer()],
+    BUBBLEGUM_PROGRAM_ID
+  );
+  const lookAlikeMintToAccounts = [
+    {
+      name: "collection metadata",
+      accounts: { collectionMetadata: masterEdition },
+      expectedErrorCode: 6031,
+    },
+    {
+      name: "edition account",
+      accounts: { editionAccount: metadata },
+      expectedErrorCode: 6032,
+    },
+    {
+      name: "bubblegum signer",
+      accounts: { bubblegumSigner: treeAuthority },
+      expectedErrorCode: 6033,
+    },
+    {
+      name: "tree authority",
+      accounts: { treeAuthority: lookAlikeTreeAuthority },
+      expectedErrorCode: 6012,
+    },
+    {
+      name: "merkle tree",
+      accounts: { merkleTree: anchor.web3.Keypair.generate().publicKey },
+      expectedErrorCode: 6034,
+    },
+  ];
+
+  for (const {
+    name,
+    accounts,
+    expectedErrorCode,
+  } of lookAlikeMintToAccounts) {
+    it(`should not allow minting with a look-alike ${name}`, async () => {
+      const ix = await PROGRAM.methods
+        .mintTo(new anchor.BN(1), null)
+        .accounts({
+          bubblegumSigner,
+          collectionMetadata: metadata,
+          collectionMint: mint,
+          tinySplAuthority,
+          leafFormat,
+          compressionProgram: COMPRESSION_PROGRAM_ID,
+          editionAccount: masterEdition,
+          newLeafOwner: SIGNER.publicKey,
+          logWrapper: SPL_NOOP_PROGRAM_ID,
+          merkleTree: TREE_ID,
+          mintAuthority: SIGNER.publicKey,
+          mplBubblegumProgram: BUBBLEGUM_PROGRAM_ID,
+          tokenMetadataProgram: mplTokenMetadataProgramId,
+          treeAuthority,
+          treeCreatorOrDelegate: TREE_CREATOR.publicKey,
+          ...accounts,
+        })
+        .instruction();
+
+      const result = await sendAndConfirmIxs(
+        [ix],
+        SIGNER.publicKey,
+        [SIGNER, TREE_CREATOR],
+        true
+      );
+
+      const errorCode = (result.value?.err as any).InstructionError[1].Custom;
+      expect(errorCode).to.equal(expectedErrorCode);
+    });
+  }
+
   it("should not allow owner to split token to invalid amounts", async () => {
-    const assets = await CONNECTION.getAssetsByOwner({
-      ownerAddress: SIGNER.publicKey.toBase58(),
-      limit: 1,
-      sortBy: {
-        sortBy: "created",
-        sortDirection: "desc",
-      },
+    const owner = await createOwner();
+    const [leaf] = await mintLeaves(mintAccounts, owner.publicKey, [
+      TOKENS_TO_MINT,
+    ]);
+
+    const result1 = await sendAsOwner(
+      [
+        await buildSplitIx({
+          mintAccounts,
+          leaf,
+          amounts: [2, 2], // invalid split amounts
+        }),
+      ],
+      owner
+    );
+    const result2 = await sendAsOwner(
+      [
+        await buildSplitIx({
+          mintAccounts,
+          leaf,
+          amounts: [0, 3], // invalid split amounts
+        }),
+      ],
+      owner
+    );
+
+    expectCustomError(result1, 6006);
+    expectCustomError(result2, 6006);
+  });
+
+  it("should allow token owner to split token", async () => {
+    const owner = await createOwner();
+    const [leaf] = await mintLeaves(mintAccounts, owner.publicKey, [
+      TOKENS_TO_MINT,
+    ]);
+
+    const result = await sendAsOwner(
+      [await buildSplitIx({ mintAccounts, leaf, amounts: [1, 2] })],
+      owner
+    );
+
+    expect(result.value.err).to.be.null;
+
+    const resultAssets = await getNewestAssets(owner.publicKey, 2, mint, [
+      leaf,
+    ]);
+    expect(resultAssets.map(getAssetAmount)).to.have.members([1, 2]);
+  });
+
+  // every output is minted through its own cpi, which only fits the default
+  // heap frame because each one hands its allocations back
+  it("should split a leaf into many outputs", async () => {
+    const outputCount = 20;
+    const owner = await createOwner();
+    const [leaf] = await mintLeaves(mintAccounts, owner.publicKey, [
+      outputCount,
+    ]);
+
+    const result = await sendAsOwner(
+      [
+        await buildSplitIx({
+          mintAccounts,
+          leaf,
+          amounts: Array(outputCount).fill(1),
+        }),
+      ],
+      owner
+    );
+
+    expect(result.value.err).to.be.null;
+
+    const resultAssets = await getNewestAssets(
+      owner.publicKey,
+      outputCount,
+      mint,
+      [leaf]
+    );
+    expect(resultAssets.map(getAssetAmount)).to.deep.equal(
+      Array(outputCount).fill(1)
+    );
+  });
+
+  it("should not allow token owner to combine the same token", async () => {
+    const owner = await createOwner();
+    const [leaf] = await mintLeaves(mintAccounts, owner.publicKey, [
+      TOKENS_TO_MINT,
+    ]);
+
+    const result = await sendAsOwner(
+      [await buildCombineIx({ mintAccounts, leaves: [leaf, leaf] })],
+      owner
+    );
+
+    expectCustomError(result, 6007);
+  });
+
+  it("should not allow combining with a look-alike tree", async () => {
+    const owner = await createOwner();
+    const [leaf] = await mintLeaves(mintAccounts, owner.publicKey, [
+      TOKENS_TO_MINT,
+    ]);
+
+    const lookAlikeTree = anchor.web3.Keypair.generate().publicKey;
+    const result = await sendAsOwner(
+      [
+        await buildCombineIx({
+          mintAccounts,
+          leaves: [{ ...leaf, merkleTree: lookAlikeTree, proofPath: [] }],
+        }),
+      ],
+      owner
+    );
+
+    expectCustomError(result, 6034);
+  });
+
+  it("should allow token owner to combine tokens", async () => {
+    const owner = await createOwner();
+    const leaves = await mintLeaves(mintAccounts, owner.publicKey, [1, 2]);
+
+    const result = await sendAsOwner(
+      [await buildCombineIx({ mintAccounts, leaves })],
+      owner
+    );
+
+    expect(result.value.err).to.be.null;
+
+    const [newAsset] = await getNewestAssets(
+      owner.publicKey,
+      1,
+      mint,
+      leaves
+    );
+    expect(getAssetAmount(newAsset)).to.equal(3);
+  });
+
+  it("should set an explicit delegate on split outputs", async () => {
+    const owner = await createOwner();
+    const [leaf] = await mintLeaves(mintAccounts, owner.publicKey, [
+      TOKENS_TO_MINT,
+    ]);
+
+    const result = await sendAsOwner(
+      [
+        await buildSplitIx({
+          mintAccounts,
+          leaf,
+          amounts: [TOKENS_TO_MINT],
+          accounts: { newLeafDelegate: LEAF_DELEGATE },
+        }),
+      ],
+      owner
+    );
+
+    expect(result.value.err).to.be.null;
+
+    const [newAsset] = await getNewestAssets(owner.publicKey, 1, mint, [
+      leaf,
+    ]);
+    expect(newAsset.ownership.delegate).to.equal(LEAF_DELEGATE.toBase58());
+  });
+
+  it("should preserve the source delegate on split outputs", async () => {
+    const owner = await createOwner();
+    const [leaf] = await mintLeaves(mintAccounts, owner.publicKey, [
+      TOKENS_TO_MINT,
+    ]);
+
+    // delegate the leaf first, so there is a delegate to preserve
+    const delegateResult = await sendAsOwner(
+      [
+        await buildSplitIx({
+          mintAccounts,
+          leaf,
+          amounts: [TOKENS_TO_MINT],
+          accounts: { newLeafDelegate: LEAF_DELEGATE },
+        }),
+      ],
+      owner
+    );
+    expect(delegateResult.value.err).to.be.null;
+
+    const [delegatedAsset] = await getNewestAssets(owner.publicKey, 1, mint, [
+      leaf,
+    ]);
+    const delegatedLeaf = await getLeaf(delegatedAsset);
+    expect(delegatedLeaf.delegate.toBase58()).to.equal(
+      LEAF_DELEGATE.toBase58()
+    );
+
+    const result = await sendAsOwner(
+      [
+        await buildSplitIx({
+          mintAccounts,
+          leaf: delegatedLeaf,
+          amounts: [TOKENS_TO_MINT],
+          preserveDelegate: true,
+        }),
+      ],
+      owner
+    );
+
+    expect(result.value.err).to.be.null;
+
+    const [newAsset] = await getNewestAssets(owner.publicKey, 1, mint, [
+      leaf,
+      delegatedLeaf,
+    ]);
+    expect(newAsset.ownership.delegate).to.equal(LEAF_DELEGATE.toBase58());
+  });
+
+  it("should reject an out-of-range destination tree index", async () => {
+    const owner = await createOwner();
+    const [leaf] = await mintLeaves(mintAccounts, owner.publicKey, [
+      TOKENS_TO_MINT,
+    ]);
+
+    const result = await sendAsOwner(
+      [
+        await buildSplitIx({
+          mintAccounts,
+          leaf,
+          amounts: [1, TOKENS_TO_MINT - 1],
+          extraTrees: [SECOND_TREE_ID],
+          treeIndexes: [0, 2], // only index 0 and 1 are in the table
+        }),
+      ],
+      owner
+    );
+
+    expectCustomError(result, 6014);
+  });
+
+  it("should allow split outputs to land in another tree", async () => {
+    const owner = await createOwner();
+    const [leaf] = await mintLeaves(mintAccounts, owner.publicKey, [
+      TOKENS_TO_MINT,
+    ]);
+
+    const result = await sendAsOwner(
+      [
+        await buildSplitIx({
+          mintAccounts,
+          leaf,
+          amounts: [1, TOKENS_TO_MINT - 1],
+          extraTrees: [SECOND_TREE_ID],
+          treeIndexes: [0, 1],
+        }),
+      ],
+      owner
+    );
+
+    expect(result.value.err).to.be.null;
+
+    const resultAssets = await getNewestAssets(owner.publicKey, 2, mint, [
+      leaf,
+    ]);
+    const resultTrees = resultAssets.map((asset) => asset.compression.tree);
+    expect(resultTrees).to.have.members([
+      TREE_ID.toBase58(),
+      SECOND_TREE_ID.toBase58(),
+    ]);
+  });
+
+  it("should create a collection mint under token 2022", async () => {
+    const token2022MintAccounts = await createTestMint({
+      splTokenProgram: token2022ProgramId,
     });
 
-    const newestAsset = assets.items[0];
-    const assetProof = await CONNECTION.getAssetProof(
-      new PublicKey(newestAsset.id)
-    );
-
-    const treeAccount = await ConcurrentMerkleTreeAccount.fromAccountAddress(
-      CONNECTION,
-      TREE_ID
-    );
-    const canopyDepth = treeAccount.getCanopyDepth();
-
-    // parse the list of proof addresses into a valid AccountMeta[]
-    const proofPath: AccountMeta[] = assetProof.proof
-      .map((node: string) => ({
-        pubkey: new PublicKey(node),
-        isSigner: false,
-        isWritable: false,
-      }))
-      .slice(0, assetProof.proof.length - (!!canopyDepth ? canopyDepth : 0));
-
-    const ixWithTooManySplits = await PROGRAM.methods
-      .split(
-        new anchor.BN(TOKENS_TO_MINT),
-        new PublicKey(newestAsset.id),
-        [...new PublicKey(assetProof.root.trim()).toBytes()],
-        new anchor.BN(newestAsset.compression.leaf_id),
-        newestAsset.compression.leaf_id,
-        [new anchor.BN(2), new anchor.BN(2)] // invalid split amounts
+    const mintAccount = await CONNECTION.getAccountInfo(
+      token2022MintAccounts.collectionMint,
+      "confirmed"
+    );
+    expect(mintAccount.owner.toBase58()).to.equal(
+      token2022ProgramId.toBase58()
+    );
+    expect(getMetadataPointerAddress(mintAccount.data)?.toBase58()).to.equal(
+      token2022MintAccounts.collectionMetadata.toBase58()
+    );
+  });
+
+  it("should mint and split leaves of a token 2022 collection", async () => {
+    const token2022MintAccounts = await createTestMint({
+      splTokenProgram: token2022ProgramId,
+    });
+    const owner = await createOwner();
+    const [leaf] = await mintLeaves(token2022MintAccounts, owner.publicKey, [
+      TOKENS_TO_MINT,
+    ]);
+    expect(leaf.amount).to.equal(TOKENS_TO_MINT);
+
+    const result = await sendAsOwner(
+      [
+        await buildSplitIx({
+          mintAccounts: token2022MintAccounts,
+          leaf,
+          amounts: [1, TOKENS_TO_MINT - 1],
+        }),
+      ],
+      owner
+    );
+
+    expect(result.value.err).to.be.null;
+
+    const resultAssets = await getNewestAssets(
+      owner.publicKey,
+      2,
+      token2022MintAccounts.collectionMint,
+      [leaf]
+    );
+    expect(resultAssets.map(getAssetAmount)).to.have.members([
+      1,
+      TOKENS_TO_MINT - 1,
+    ]);
+  });
+
+  it("should distribute a session and refund the rest on close", async () => {
+    const owner = await createOwner();
+    const recipient = anchor.web3.Keypair.generate().publicKey;
+    const leaves = await mintLeaves(mintAccounts, owner.publicKey, [1, 2]);
+
+    const fundResult = await sendAsOwner(
+      [
+        await buildOpenDistributionSessionIx(mintAccounts, owner.publicKey),
+        await buildFundDistributionSessionIx(mintAccounts, leaves),
+      ],
+      owner
+    );
+    expect(fundResult.value.err).to.be.null;
+
+    const distributeResult = await sendAsOwner(
+      [
+        await buildDistributeIx(mintAccounts, owner.publicKey, [
+          { wallet: recipient, amount: 2 },
+        ]),
+      ],
+      owner
+    );
+    expect(distributeResult.value.err).to.be.null;
+
+    const closeResult = await sendAsOwner(
+      [await buildCloseDistributionSessionIx(mintAccounts, owner.publicKey)],
+      owner
+    );
+    expect(closeResult.value.err).to.be.null;
+
+    const [recipientAsset] = await getNewestAssets(recipient, 1, mint);
+    expect(getAssetAmount(recipientAsset)).to.equal(2);
+    const [refund] = await getNewestAssets(owner.publicKey, 1, mint, leaves);
+    expect(getAssetAmount(refund)).to.equal(1);
+    expect(
+      await CONNECTION.getAccountInfo(
+        getDistributionSession(mint, owner.publicKey)
       )
+    ).to.be.null;
+  });
+
+  it("should only let the owner close a distribution session", async () => {
+    const owner = await createOwner();
+    const stranger = await createOwner();
+    const leaves = await mintLeaves(mintAccounts, owner.publicKey, [
+      TOKENS_TO_MINT,
+    ]);
+
+    const fundResult = await sendAsOwner(
+      [
+        await buildOpenDistributionSessionIx(mintAccounts, owner.publicKey),
+        await buildFundDistributionSessionIx(mintAccounts, leaves),
+      ],
+      owner
+    );
+    expect(fundResult.value.err).to.be.null;
+
+    // the session is derived from the wallet that signs as its owner
+    const result = await sendAsOwner(
+      [
+        await buildCloseDistributionSessionIx(
+          mintAccounts,
+          stranger.publicKey,
+          getDistributionSession(mint, owner.publicKey)
+        ),
+      ],
+      stranger
+    );
+
+    expectCustomError(result, 2006);
+  });
+
+  it("should not refund a distribution session to a denied owner", async () => {
+    const denylistMintAccounts = await createTestMint({
+      complianceConfig: DENYLIST_CONFIG,
+    });
+    const owner = await createOwner();
+    const leaves = await mintLeaves(denylistMintAccounts, owner.publicKey, [
+      TOKENS_TO_MINT,
+    ]);
+
+    const fundResult = await sendAsOwner(
+      [
+        await buildOpenDistributionSessionIx(
+          denylistMintAccounts,
+          owner.publicKey
+        ),
+        await buildFundDistributionSessionIx(denylistMintAccounts, leaves),
+      ],
+      owner
+    );
+    expect(fundResult.value.err).to.be.null;
+
+    await denyWallet(denylistMintAccounts, owner.publicKey);
+
+    const result = await sendAsOwner(
+      [
+        await buildCloseDistributionSessionIx(
+          denylistMintAccounts,
+          owner.publicKey
+        ),
+      ],
+      owner
+    );
+
+    expectCustomError(result, 6023);
+  });
+
+  it("should combine the leaves absorbed by a combine session", async () => {
+    const owner = await createOwner();
+    const leaves = await mintLeaves(mintAccounts, owner.publicKey, [1, 2, 3]);
+
+    const absorbResult = await sendAsOwner(
+      [
+        await buildOpenCombineSessionIx(mintAccounts, owner.publicKey),
+        await buildAbsorbIx(mintAccounts, leaves.slice(0, 2)),
+      ],
+      owner
+    );
+    expect(absorbResult.value.err).to.be.null;
+
+    const closeResult = await sendAsOwner(
+      [
+        await buildAbsorbIx(mintAccounts, leaves.slice(2)),
+        await buildCloseCombineSessionIx(mintAccounts, owner.publicKey),
+      ],
+      owner
+    );
+    expect(closeResult.value.err).to.be.null;
+
+    const [combined] = await getNewestAssets(owner.publicKey, 1, mint, leaves);
+    expect(getAssetAmount(combined)).to.equal(6);
+    expect(
+      await CONNECTION.getAccountInfo(getCombineSession(mint, owner.publicKey))
+    ).to.be.null;
+  });
+
+  it("should not let others close a combine session early", async () => {
+    const owner = await createOwner();
+    const stranger = await createOwner();
+    const leaves = await mintLeaves(mintAccounts, owner.publicKey, [
+      TOKENS_TO_MINT,
+    ]);
+
+    const absorbResult = await sendAsOwner(
+      [
+        await buildOpenCombineSessionIx(mintAccounts, owner.publicKey),
+        await buildAbsorbIx(mintAccounts, leaves),
+      ],
+      owner
+    );
+    expect(absorbResult.value.err).to.be.null;
+
+    const result = await sendAsOwner(
+      [
+        await buildCloseCombineSessionIx(
+          mintAccounts,
+          owner.publicKey,
+          stranger.publicKey
+        ),
+      ],
+      stranger
+    );
+
+    expectCustomError(result, 6025);
+  });
+
+  it("should not mint a combine session to a denied owner", async () => {
+    const denylistMintAccounts = await createTestMint({
+      complianceConfig: DENYLIST_CONFIG,
+    });
+    const owner = await createOwner();
+    const leaves = await mintLeaves(denylistMintAccounts, owner.publicKey, [
+      TOKENS_TO_MINT,
+    ]);
+
+    const absorbResult = await sendAsOwner(
+      [
+        await buildOpenCombineSessionIx(denylistMintAccounts, owner.publicKey),
+        await buildAbsorbIx(denylistMintAccounts, leaves),
+      ],
+      owner
+    );
+    expect(absorbResult.value.err).to.be.null;
+
+    await denyWallet(denylistMintAccounts, owner.publicKey);
+
+    const result = await sendAsOwner(
+      [
+        await buildCloseCombineSessionIx(
+          denylistMintAccounts,
+          owner.publicKey
+        ),
+      ],
+      owner
+    );
+
+    expectCustomError(result, 6023);
+  });
+
+  it("should not recover a denied owner's combine session early", async () => {
+    const denylistMintAccounts = await createTestMint({
+      complianceConfig: DENYLIST_CONFIG,
+    });
+    const owner = await createOwner();
+    const recipient = await createOwner();
+    const leaves = await mintLeaves(denylistMintAccounts, owner.publicKey, [
+      TOKENS_TO_MINT,
+    ]);
+
+    const absorbResult = await sendAsOwner(
+      [
+        await buildOpenCombineSessionIx(denylistMintAccounts, owner.publicKey),
+        await buildAbsorbIx(denylistMintAccounts, leaves),
+      ],
+      owner
+    );
+    expect(absorbResult.value.err).to.be.null;
+
+    await denyWallet(denylistMintAccounts, owner.publicKey);
+
+    // the mint authority can only pick another wallet once the session expires
+    const result = await sendIxs(
+      [
+        await buildCloseCombineSessionIx(
+          denylistMintAccounts,
+          owner.publicKey,
+          SIGNER.publicKey,
+          recipient.publicKey
+        ),
+      ],
+      [SIGNER]
+    );
+
+    expectCustomError(result, 6025);
+  });
+
+  it("should not mint a combine session to a disallowed owner", async () => {
+    const allowlistMintAccounts = await createTestMint({
+      complianceConfig: ALLOWLIST_CONFIG,
+    });
+    const owner = await createOwner();
+    await allowWallet(allowlistMintAccounts, owner.publicKey);
+    const leaves = await mintLeaves(allowlistMintAccounts, owner.publicKey, [
+      TOKENS_TO_MINT,
+    ]);
+
+    const absorbResult = await sendAsOwner(
+      [
+        await buildOpenCombineSessionIx(
+          allowlistMintAccounts,
+          owner.publicKey
+        ),
+        await buildAbsorbIx(allowlistMintAccounts, leaves),
+      ],
+      owner
+    );
+    expect(absorbResult.value.err).to.be.null;
+
+    await disallowWallet(allowlistMintAccounts, owner.publicKey);
+
+    const result = await sendAsOwner(
+      [
+        await buildCloseCombineSessionIx(
+          allowlistMintAccounts,
+          owner.publicKey
+        ),
+      ],
+      owner
+    );
+
+    expectCustomError(result, 6022);
+  });
+
+  const adoptCollection = async (collectionMint: PublicKey) => {
+    const mintAccounts = getMintAccounts(collectionMint);
+    const collectionAuthorityRecord = getCollectionAuthorityRecord(
+      collectionMint,
+      mintAccounts.tinySplAuthority
+    );
+    const ix = await PROGRAM.methods
+      .adoptCollection()
       .accounts({
-        leafOwner: SIGNER.publicKey,
-        leafDelegate: SIGNER.publicKey,
-        compressionProgram: COMPRESSION_PROGRAM_ID,
-        collectionMint: mint,
-        tinySplAuthority,
-        merkleTree: TREE_ID,
+        updateAuthority: SIGNER.publicKey,
+        collectionMint,
+        collectionMetadata: mintAccounts.collectionMetadata,
+        collectionAuthorityRecord,
+        tinySplAuthority: mintAccounts.tinySplAuthority,
+        leafFormat: mintAccounts.leafFormat,
         systemProgram: anchor.web3.SystemProgram.programId,
-        authority: SIGNER.publicKey,
-        bubblegumSigner,
-        collectionMetadata: metadata,
-        editionAccount: masterEdition,
-        logWrapper: SPL_NOOP_PROGRAM_ID,
-        mplBubblegumProgram: BUBBLEGUM_PROGRAM_ID,
-        newLeafOwner: SIGNER.publicKey,
         tokenMetadataProgram: mplTokenMetadataProgramId,
-        treeAuthority,
-        treeCreatorOrDelegate: TREE_CREATOR.publicKey,
       })
-      .remainingAccounts(proofPath)
       .instruction();
 
-    const modifyComputeUnits = ComputeBudgetProgram.setComputeUnitLimit({
-      units: 1_400_000,
+    return {
+      result: await sendIxs([ix], []),
+      mintAccounts: { ...mintAccounts, collectionAuthorityRecord },
+    };
+  };
+
+  it("should adopt a sized collection and keep its creators", async () => {
+    const secondCreator = anchor.web3.Keypair.generate().publicKey;
+    const collectionMint = await createCollection({
+      isSized: true,
+      creators: [
+        { address: SIGNER.publicKey, share: 60 },
+        { address: secondCreator, share: 40 },
+      ],
     });
 
-    const result1 = await sendAndConfirmIxs(
-      [modifyComputeUnits, ixWithTooManySplits],
-      SIGNER.publicKey,
-      [SIGNER, TREE_CREATOR],
-      true
+    const { result, mintAccounts: adoptedMintAccounts } =
+      await adoptCollection(collectionMint);
+    expect(result.value.err).to.be.null;
+
+    const adoptedLeafFormat = await PROGRAM.account.leafFormat.fetch(
+      adoptedMintAccounts.leafFormat,
+      "confirmed"
     );
+    expect(
+      adoptedLeafFormat.additionalCreators.map(({ address, share }) => [
+        address.toBase58(),
+        share,
+      ])
+    ).to.deep.equal([
+      [SIGNER.publicKey.toBase58(), 60],
+      [secondCreator.toBase58(), 40],
+    ]);
+    expect(adoptedLeafFormat.sellerFeeBasisPoints).to.equal(500);
 
-    const ixWithZero = await PROGRAM.methods
-      .split(
-        new anchor.BN(TOKENS_TO_MINT),
-        new PublicKey(newestAsset.id),
-        [...new PublicKey(assetProof.root.trim()).toBytes()],
-        new anchor.BN(newestAsset.compression.leaf_id),
-        newestAsset.compression.leaf_id,
-        [new anchor.BN(0), new anchor.BN(3)] // invalid split amounts
-      )
+    // the update authority governs the leaf format of the collection
+    const setLeafFormatIx = await PROGRAM.methods
+      .setLeafFormat(1, "ADPT", "https://example.com/{amount}.json", {
+        grouped: {},
+      })
       .accounts({
-        leafOwner: SIGNER.publicKey,
-        leafDelegate: SIGNER.publicKey,
-        compressionProgram: COMPRESSION_PROGRAM_ID,
-        collectionMint: mint,
-        tinySplAuthority,
-        merkleTree: TREE_ID,
+        metadataAuthority: SIGNER.publicKey,
+        collectionMint,
+        tinySplAuthority: adoptedMintAccounts.tinySplAuthority,
+        currentLeafFormat: adoptedMintAccounts.leafFormat,
+        leafFormat: getLeafFormat(collectionMint, 1),
         systemProgram: anchor.web3.SystemProgram.programId,
-        authority: SIGNER.publicKey,
-        bubblegumSigner,
-        collectionMetadata: metadata,
-        editionAccount: masterEdition,
-        logWrapper: SPL_NOOP_PROGRAM_ID,
-        mplBubblegumProgram: BUBBLEGUM_PROGRAM_ID,
-        newLeafOwner: SIGNER.publicKey,
-        tokenMetadataProgram: mplTokenMetadataProgramId,
-        treeAuthority,
-        treeCreatorOrDelegate: TREE_CREATOR.publicKey,
       })
-      .remainingAccounts(proofPath)
       .instruction();
+    const setLeafFormatResult = await sendIxs([setLeafFormatIx], []);
+    expect(setLeafFormatResult.value.err).to.be.null;
 
-    const result2 = await sendAndConfirmIxs(
-      [modifyComputeUnits, ixWithZero],
-      SIGNER.publicKey,
-      [SIGNER, TREE_CREATOR],
-      true
+    const owner = await createOwner();
+    const [leaf] = await mintLeaves(
+      {
+        ...adoptedMintAccounts,
+        leafFormat: getLeafFormat(collectionMint, 1),
+      },
+      owner.publicKey,
+      [TOKENS_TO_MINT]
     );
-
-    const errorCode1 = (result1.value?.err as any).InstructionError[1].Custom;
-    const errorCode2 = (result2.value?.err as any).InstructionError[1].Custom;
-    expect(errorCode1).to.equal(6006);
-    expect(errorCode2).to.equal(6006);
+    expect(leaf.asset.content.json_uri).to.equal(
+      `https://example.com/${TOKENS_TO_MINT}.json`
+    );
+    expect(
+      leaf.asset.creators.map(({ address }) => address.toString())
+    ).to.deep.equal([
+      adoptedMintAccounts.tinySplAuthority.toBase58(),
+      SIGNER.publicKey.toBase58(),
+      secondCreator.toBase58(),
+    ]);
   });
 
-  it("should allow token owner to split token", async () => {
-    const assets = await CONNECTION.getAssetsByOwner({
-      ownerAddress: SIGNER.publicKey.toBase58(),
-      limit: 1,
-      sortBy: {
-        sortBy: "created",
-        sortDirection: "desc",
-      },
+  it("should not adopt an unsized collection", async () => {
+    const collectionMint = await createCollection({
+      isSized: false,
+      creators: [{ address: SIGNER.publicKey, share: 100 }],
     });
 
-    const newestAsset = assets.items[0];
-    const assetProof = await CONNECTION.getAssetProof(
-      new PublicKey(newestAsset.id)
+    const { result } = await adoptCollection(collectionMint);
+
+    expectCustomError(result, 6040);
+  });
+
+  const V1_URI_TEMPLATE =
+    "https://example.com/v1?id={collection_mint}&amount={amount}";
+
+  // a fresh mint with a leaf of TOKENS_TO_MINT minted in version 0, after which
+  // the mint moves on to version 1
+  const setUpLeafFormats = async () => {
+    const v0MintAccounts = await createTestMint();
+    const owner = await createOwner();
+    const [v0Leaf] = await mintLeaves(v0MintAccounts, owner.publicKey, [
+      TOKENS_TO_MINT,
+    ]);
+
+    const result = await sendIxs(
+      [await buildSetLeafFormatIx(v0MintAccounts, 1, V1_URI_TEMPLATE)],
+      []
+    );
+    expect(result.value.err).to.be.null;
+
+    return {
+      owner,
+      v0Leaf,
+      v1MintAccounts: getMintAccounts(v0MintAccounts.collectionMint, 1),
+    };
+  };
+
+  it("should only let the metadata authority set a leaf format", async () => {
+    const testMintAccounts = await createTestMint();
+    const stranger = await createOwner();
+
+    const result = await sendIxs(
+      [
+        await buildSetLeafFormatIx(
+          testMintAccounts,
+          1,
+          V1_URI_TEMPLATE,
+          stranger.publicKey
+        ),
+      ],
+      [stranger]
+    );
+
+    expectCustomError(result, 2003);
+  });
+
+  it("should not skip a leaf format version", async () => {
+    const testMintAccounts = await createTestMint();
+
+    const result = await sendIxs(
+      [await buildSetLeafFormatIx(testMintAccounts, 2, V1_URI_TEMPLATE)],
+      []
     );
 
-    const treeAccount = await ConcurrentMerkleTreeAccount.fromAccountAddress(
-      CONNECTION,
-      TREE_ID
+    expectCustomError(result, 6015);
+  });
+
+  it("should not set a leaf format without the amount in its uri", async () => {
+    const testMintAccounts = await createTestMint();
+
+    const result = await sendIxs(
+      [
+        await buildSetLeafFormatIx(
+          testMintAccounts,
+          1,
+          "https://example.com/leaf.json"
+        ),
+      ],
+      []
     );
-    const canopyDepth = treeAccount.getCanopyDepth();
 
-    // parse the list of proof addresses into a valid AccountMeta[]
-    const proofPath: AccountMeta[] = assetProof.proof
-      .map((node: string) => ({
-        pubkey: new PublicKey(node),
-        isSigner: false,
-        isWritable: false,
-      }))
-      .slice(0, assetProof.proof.length - (!!canopyDepth ? canopyDepth : 0));
+    expectCustomError(result, 6016);
+  });
 
+  it("should not burn a compact leaf as a smaller amount", async () => {
+    const testMintAccounts = await createTestMint();
+    const owner = await createOwner();
+    // both amounts are named `1K TST`, only the uri tells them apart
+    const [leaf] = await mintLeaves(testMintAccounts, owner.publicKey, [1_050]);
+
+    const result = await sendAsOwner(
+      [
+        await buildSplitIx({
+          mintAccounts: testMintAccounts,
+          leaf,
+          amounts: [500, 500],
+          sourceAmount: 1_000,
+        }),
+      ],
+      owner
+    );
+
+    expect(result.value.err).to.not.be.null;
+  });
+
+  it("should not init the leaf format of a mint that has one", async () => {
+    const testMintAccounts = await createTestMint();
     const ix = await PROGRAM.methods
-      .split(
-        new anchor.BN(TOKENS_TO_MINT),
-        new PublicKey(newestAsset.id),
-        [...new PublicKey(assetProof.root.trim()).toBytes()],
-        new anchor.BN(newestAsset.compression.leaf_id),
-        newestAsset.compression.leaf_id,
-        [new anchor.BN(1), new anchor.BN(2)]
-      )
+      .initLeafFormat()
       .accounts({
-        leafOwner: SIGNER.publicKey,
-        leafDelegate: SIGNER.publicKey,
-        compressionProgram: COMPRESSION_PROGRAM_ID,
-        collectionMint: mint,
-        tinySplAuthority,
-        merkleTree: TREE_ID,
+        payer: SIGNER.publicKey,
+        collectionMint: testMintAccounts.collectionMint,
+        collectionMetadata: testMintAccounts.collectionMetadata,
+        tinySplAuthority: testMintAccounts.tinySplAuthority,
+        leafFormat: testMintAccounts.leafFormat,
         systemProgram: anchor.web3.SystemProgram.programId,
-        authority: SIGNER.publicKey,
-        bubblegumSigner,
-        collectionMetadata: metadata,
-        editionAccount: masterEdition,
-        logWrapper: SPL_NOOP_PROGRAM_ID,
-        mplBubblegumProgram: BUBBLEGUM_PROGRAM_ID,
-        newLeafOwner: SIGNER.publicKey,
-        tokenMetadataProgram: mplTokenMetadataProgramId,
-        treeAuthority,
-        treeCreatorOrDelegate: TREE_CREATOR.publicKey,
       })
-      .remainingAccounts(proofPath)
       .instruction();
 
-    const modifyComputeUnits = ComputeBudgetProgram.setComputeUnitLimit({
-      units: 1_400_000,
-    });
+    const result = await sendIxs([ix], []);
 
-    const result = await sendAndConfirmIxs(
-      [modifyComputeUnits, ix],
-      SIGNER.publicKey,
-      [SIGNER, TREE_CREATOR],
-      true
+    // the system program refuses to create an account that is already in use
+    expectCustomError(result, 0);
+  });
+
+  it("should migrate a leaf to the current leaf format", async () => {
+    const { owner, v0Leaf, v1MintAccounts } = await setUpLeafFormats();
+
+    const result = await sendAsOwner(
+      [await buildMigrateLeafIx(v1MintAccounts, v0Leaf)],
+      owner
+    );
+    expect(result.value.err).to.be.null;
+
+    const [v1Asset] = await getNewestAssets(
+      owner.publicKey,
+      1,
+      v1MintAccounts.collectionMint,
+      [v0Leaf]
+    );
+    expect(v1Asset.content.json_uri).to.equal(
+      V1_URI_TEMPLATE.replace(
+        "{collection_mint}",
+        v1MintAccounts.collectionMint.toBase58()
+      ).replace("{amount}", `${TOKENS_TO_MINT}`)
     );
 
+    const v1Leaf = await getLeaf(v1Asset);
+    const migrateAgainResult = await sendAsOwner(
+      [await buildMigrateLeafIx(v1MintAccounts, v1Leaf)],
+      owner
+    );
+    expectCustomError(migrateAgainResult, 6017);
+  });
+
+  it("should split a leaf minted in an older leaf format", async () => {
+    const { owner, v0Leaf, v1MintAccounts } = await setUpLeafFormats();
+
+    const result = await sendAsOwner(
+      [
+        await buildSplitIx({
+          mintAccounts: v1MintAccounts,
+          leaf: v0Leaf,
+          amounts: [1, 2],
+        }),
+      ],
+      owner
+    );
     expect(result.value.err).to.be.null;
 
-    await new Promise((resolve) => setTimeout(resolve, 1000));
+    const resultAssets = await getNewestAssets(
+      owner.publicKey,
+      2,
+      v1MintAccounts.collectionMint,
+      [v0Leaf]
+    );
+    expect(resultAssets.map(getAssetAmount)).to.have.members([1, 2]);
+  });
 
-    const resultAssets = await CONNECTION.getAssetsByOwner({
-      ownerAddress: SIGNER.publicKey.toBase58(),
-      limit: 2,
-      sortBy: {
-        sortBy: "created",
-        sortDirection: "desc",
-      },
-    });
+  it("should combine leaves minted in different leaf formats", async () => {
+    const { owner, v0Leaf, v1MintAccounts } = await setUpLeafFormats();
+    const [v1Leaf] = await mintLeaves(v1MintAccounts, owner.publicKey, [
+      TOKENS_TO_MINT,
+    ]);
 
-    const resultAssetsQuantity = resultAssets.items.map((asset) =>
-      new URL(asset.content.json_uri).searchParams.get("amount")
+    const result = await sendAsOwner(
+      [
+        await buildCombineIx({
+          mintAccounts: v1MintAccounts,
+          leaves: [v0Leaf, v1Leaf],
+        }),
+      ],
+      owner
     );
-    const totalNewAssetsAmount = resultAssetsQuantity.reduce(
-      (acc, curr) => (curr ? acc + parseInt(curr) : acc),
-      0
+    expect(result.value.err).to.be.null;
+
+    const [combinedAsset] = await getNewestAssets(
+      owner.publicKey,
+      1,
+      v1MintAccounts.collectionMint,
+      [v0Leaf, v1Leaf]
     );
-    expect(totalNewAssetsAmount).to.equal(TOKENS_TO_MINT);
+    expect(getAssetAmount(combinedAsset)).to.equal(TOKENS_TO_MINT * 2);
   });
 
-  it("should not allow token owner to combine the same token", async () => {
-    // upload metadata for the new token
-    const assets = await CONNECTION.getAssetsByOwner({
-      ownerAddress: SIGNER.publicKey.toBase58(),
-      limit: 1,
-      sortBy: {
-        sortBy: "created",
-        sortDirection: "desc",
+  it("should not combine with the leaf format of another mint", async () => {
+    const { owner, v0Leaf, v1MintAccounts } = await setUpLeafFormats();
+    const [v1Leaf] = await mintLeaves(v1MintAccounts, owner.publicKey, [
+      TOKENS_TO_MINT,
+    ]);
+
+    const result = await sendAsOwner(
+      [
+        await buildCombineIx({
+          mintAccounts: v1MintAccounts,
+          leaves: [{ ...v0Leaf, leafFormat }, v1Leaf],
+        }),
+      ],
+      owner
+    );
+
+    expectCustomError(result, 6042);
+  });
+
+  it("should let only the metadata authority rename a collection", async () => {
+    const testMintAccounts = await createTestMint();
+    const stranger = await createOwner();
+
+    const strangerResult = await sendIxs(
+      [
+        await buildUpdateCollectionMetadataIx(
+          testMintAccounts,
+          "Stranger",
+          stranger.publicKey
+        ),
+      ],
+      [stranger]
+    );
+    expectCustomError(strangerResult, 2003);
+
+    const result = await sendIxs(
+      [await buildUpdateCollectionMetadataIx(testMintAccounts, "Renamed")],
+      []
+    );
+    expect(result.value.err).to.be.null;
+
+    const collection = await fetchCollectionMetadata(
+      testMintAccounts.collectionMint
+    );
+    expect(collection.name).to.equal("Renamed");
+  });
+
+  it("should not update the collection once it is locked", async () => {
+    const testMintAccounts = await createTestMint();
+
+    const lockResult = await sendIxs(
+      [await buildLockCollectionMetadataIx(testMintAccounts)],
+      []
+    );
+    expect(lockResult.value.err).to.be.null;
+
+    const collection = await fetchCollectionMetadata(
+      testMintAccounts.collectionMint
+    );
+    expect(collection.isMutable).to.be.false;
+    const { metadataAuthority } = await PROGRAM.account.tinySplAuthority.fetch(
+      testMintAccounts.tinySplAuthority,
+      "confirmed"
+    );
+    expect(metadataAuthority).to.be.null;
+
+    // nobody is left to pass the metadata authority check
+    const result = await sendIxs(
+      [await buildUpdateCollectionMetadataIx(testMintAccounts, "Renamed")],
+      []
+    );
+    expectCustomError(result, 2003);
+  });
+
+  it("should hand the metadata authority over", async () => {
+    const testMintAccounts = await createTestMint();
+    const newMetadataAuthority = await createOwner();
+
+    const transferResult = await sendIxs(
+      [
+        await buildTransferMetadataAuthorityIx(
+          testMintAccounts,
+          newMetadataAuthority.publicKey
+        ),
+      ],
+      []
+    );
+    expect(transferResult.value.err).to.be.null;
+
+    const oldAuthorityResult = await sendIxs(
+      [await buildUpdateCollectionMetadataIx(testMintAccounts, "Renamed")],
+      []
+    );
+    expectCustomError(oldAuthorityResult, 2003);
+
+    const result = await sendIxs(
+      [
+        await buildUpdateCollectionMetadataIx(
+          testMintAccounts,
+          "Renamed",
+          newMetadataAuthority.publicKey
+        ),
+      ],
+      [newMetadataAuthority]
+    );
+    expect(result.value.err).to.be.null;
+  });
+
+  // a fresh mint with a twin mint and a leaf of TOKENS_TO_MINT
+  const setUpTwinMint = async () => {
+    const testMintAccounts = await createTestMint();
+    const createTwinMintResult = await sendIxs(
+      [await buildCreateTwinMintIx(testMintAccounts)],
+      []
+    );
+    expect(createTwinMintResult.value.err).to.be.null;
+
+    const owner = await createOwner();
+    const [leaf] = await mintLeaves(testMintAccounts, owner.publicKey, [
+      TOKENS_TO_MINT,
+    ]);
+
+    return { testMintAccounts, owner, leaf };
+  };
+
+  it("should decompress a leaf and compress it back", async () => {
+    const { testMintAccounts, owner, leaf } = await setUpTwinMint();
+    const twinTokenAccount = getTwinTokenAccount(
+      testMintAccounts.collectionMint,
+      owner.publicKey
+    );
+
+    const decompressResult = await sendAsOwner(
+      [
+        buildCreateTwinTokenAccountIx(testMintAccounts, owner.publicKey),
+        await buildDecompressIx(testMintAccounts, leaf),
+      ],
+      owner
+    );
+    expect(decompressResult.value.err).to.be.null;
+    const decompressedBalance = await CONNECTION.getTokenAccountBalance(
+      twinTokenAccount,
+      "confirmed"
+    );
+    expect(decompressedBalance.value.amount).to.equal(`${TOKENS_TO_MINT}`);
+
+    const compressResult = await sendAsOwner(
+      [await buildCompressIx(testMintAccounts, owner.publicKey, 2)],
+      owner
+    );
+    expect(compressResult.value.err).to.be.null;
+    const compressedBalance = await CONNECTION.getTokenAccountBalance(
+      twinTokenAccount,
+      "confirmed"
+    );
+    expect(compressedBalance.value.amount).to.equal(`${TOKENS_TO_MINT - 2}`);
+
+    const [compressedAsset] = await getNewestAssets(
+      owner.publicKey,
+      1,
+      testMintAccounts.collectionMint,
+      [leaf]
+    );
+    expect(getAssetAmount(compressedAsset)).to.equal(2);
+  });
+
+  it("should not compress a zero amount", async () => {
+    const { testMintAccounts, owner } = await setUpTwinMint();
+
+    const result = await sendAsOwner(
+      [
+        buildCreateTwinTokenAccountIx(testMintAccounts, owner.publicKey),
+        await buildCompressIx(testMintAccounts, owner.publicKey, 0),
+      ],
+      owner
+    );
+
+    expectCustomError(result, 6006);
+  });
+
+  it("should not create a twin mint for an allowlisted mint", async () => {
+    const testMintAccounts = await createTestMint({
+      complianceConfig: ALLOWLIST_CONFIG,
+    });
+
+    const result = await sendIxs(
+      [await buildCreateTwinMintIx(testMintAccounts)],
+      []
+    );
+
+    expectCustomError(result, 6019);
+  });
+
+  // tiny spl has no instruction behind the transfer hook discriminator, so
+  // registering it as its own hook rejects every movement
+  const setUpRejectingTransferHook = async () => {
+    const testMintAccounts = await createTestMint();
+    const result = await sendIxs(
+      [await buildSetTransferHookIx(testMintAccounts, PROGRAM.programId)],
+      []
+    );
+    expect(result.value.err).to.be.null;
+
+    return {
+      testMintAccounts,
+      transferHookAccounts: {
+        transferHook: getTransferHook(testMintAccounts.collectionMint),
+        transferHookProgram: PROGRAM.programId,
       },
+    };
+  };
+
+  it("should abort a split the transfer hook rejects", async () => {
+    const { testMintAccounts, transferHookAccounts } =
+      await setUpRejectingTransferHook();
+    const owner = await createOwner();
+    const [leaf] = await mintLeaves(testMintAccounts, owner.publicKey, [
+      TOKENS_TO_MINT,
+    ]);
+
+    const result = await sendAsOwner(
+      [
+        await buildSplitIx({
+          mintAccounts: testMintAccounts,
+          leaf,
+          amounts: [1, 2],
+          accounts: transferHookAccounts,
+        }),
+      ],
+      owner
+    );
+
+    // InstructionFallbackNotFound of the hook
+    expectCustomError(result, 101);
+  });
+
+  it("should abort a combine the transfer hook rejects", async () => {
+    const { testMintAccounts, transferHookAccounts } =
+      await setUpRejectingTransferHook();
+    const owner = await createOwner();
+    const leaves = await mintLeaves(testMintAccounts, owner.publicKey, [1, 2]);
+
+    const result = await sendAsOwner(
+      [
+        await buildCombineIx({
+          mintAccounts: testMintAccounts,
+          leaves,
+          accounts: transferHookAccounts,
+        }),
+      ],
+      owner
+    );
+
+    expectCustomError(result, 101);
+  });
+
+  it("should not split to a wallet that is not allowed", async () => {
+    const allowlistMintAccounts = await createTestMint({
+      complianceConfig: ALLOWLIST_CONFIG,
     });
+    const owner = await createOwner();
+    const receiver = anchor.web3.Keypair.generate().publicKey;
+    await allowWallet(allowlistMintAccounts, owner.publicKey);
+    const [leaf] = await mintLeaves(allowlistMintAccounts, owner.publicKey, [
+      TOKENS_TO_MINT,
+    ]);
 
-    const newestAsset = assets.items[0];
-    const assetProof = await CONNECTION.getAssetProof(
-      new PublicKey(newestAsset.id)
-    );
-
-    const treeAccount = await ConcurrentMerkleTreeAccount.fromAccountAddress(
-      CONNECTION,
-      TREE_ID
-    );
-    const canopyDepth = treeAccount.getCanopyDepth();
-
-    // parse the list of proof addresses into a valid AccountMeta[]
-    const proofPath: AccountMeta[] = assetProof.proof
-      .map((node: string) => ({
-        pubkey: new PublicKey(node),
-        isSigner: false,
-        isWritable: false,
-      }))
-      .slice(0, assetProof.proof.length - (!!canopyDepth ? canopyDepth : 0));
-
-    const amount = new URL(newestAsset.content.json_uri).searchParams.get(
-      "amount"
-    );
-    const combineIx = await PROGRAM.methods
-      .combine(
-        [new BN(amount), new BN(amount)],
-        [new PublicKey(newestAsset.id), new PublicKey(newestAsset.id)],
-        [
-          [...new PublicKey(assetProof.root.trim()).toBytes()],
-          [...new PublicKey(assetProof.root.trim()).toBytes()],
-        ],
-        [
-          new anchor.BN(newestAsset.compression.leaf_id),
-          new anchor.BN(newestAsset.compression.leaf_id),
-        ],
-        [newestAsset.compression.leaf_id, newestAsset.compression.leaf_id],
-        [proofPath.length, proofPath.length + proofPath.length]
-      )
-      .accounts({
-        authority: SIGNER.publicKey,
-        bubblegumSigner,
-        collectionMetadata: metadata,
-        collectionMint: mint,
-        compressionProgram: COMPRESSION_PROGRAM_ID,
-        editionAccount: masterEdition,
-        leafDelegate: SIGNER.publicKey,
-        leafOwner: SIGNER.publicKey,
-        logWrapper: SPL_NOOP_PROGRAM_ID,
-        merkleTree: TREE_ID,
-        mplBubblegumProgram: BUBBLEGUM_PROGRAM_ID,
-        newLeafOwner: SIGNER.publicKey,
-        systemProgram: anchor.web3.SystemProgram.programId,
-        tinySplAuthority,
-        tokenMetadataProgram: mplTokenMetadataProgramId,
-        treeAuthority,
-        treeCreatorOrDelegate: TREE_CREATOR.publicKey,
-      })
-      .remainingAccounts([...proofPath, ...proofPath])
-      .instruction();
+    const result = await sendAsOwner(
+      [
+        await buildSplitIx({
+          mintAccounts: allowlistMintAccounts,
+          leaf,
+          amounts: [1, 2],
+          accounts: {
+            newLeafOwner: receiver,
+            ...getWalletAccounts(allowlistMintAccounts, {
+              newLeafOwner: receiver,
+            }),
+          },
+        }),
+      ],
+      owner
+    );
+
+    expectCustomError(result, 6022);
+  });
 
-    const modifyComputeUnits = ComputeBudgetProgram.setComputeUnitLimit({
-      units: 1_400_000,
+  it("should not combine to a wallet that is not allowed", async () => {
+    const allowlistMintAccounts = await createTestMint({
+      complianceConfig: ALLOWLIST_CONFIG,
     });
+    const owner = await createOwner();
+    const receiver = anchor.web3.Keypair.generate().publicKey;
+    await allowWallet(allowlistMintAccounts, owner.publicKey);
+    const leaves = await mintLeaves(allowlistMintAccounts, owner.publicKey, [
+      1, 2,
+    ]);
 
-    const result = await sendAndConfirmIxs(
-      [modifyComputeUnits, combineIx],
-      SIGNER.publicKey,
-      [SIGNER, TREE_CREATOR],
-      true
+    const result = await sendAsOwner(
+      [
+        await buildCombineIx({
+          mintAccounts: allowlistMintAccounts,
+          leaves,
+          accounts: {
+            newLeafOwner: receiver,
+            ...getWalletAccounts(allowlistMintAccounts, {
+              newLeafOwner: receiver,
+            }),
+          },
+        }),
+      ],
+      owner
     );
 
-    const errorCode = (result.value?.err as any).InstructionError[1].Custom;
-    expect(errorCode).to.equal(6007);
+    expectCustomError(result, 6022);
   });
 
-  it("should allow token owner to combine tokens", async () => {
-    // upload metadata for the new token
-    const assets = await CONNECTION.getAssetsByOwner({
-      ownerAddress: SIGNER.publicKey.toBase58(),
-      limit: 2,
-      sortBy: {
-        sortBy: "created",
-        sortDirection: "desc",
-      },
+  it("should not let a denied wallet split", async () => {
+    const denylistMintAccounts = await createTestMint({
+      complianceConfig: DENYLIST_CONFIG,
     });
+    const owner = await createOwner();
+    const [leaf] = await mintLeaves(denylistMintAccounts, owner.publicKey, [
+      TOKENS_TO_MINT,
+    ]);
+    await denyWallet(denylistMintAccounts, owner.publicKey);
 
-    const [assetA, assetB] = assets.items;
-    const assetProofA = await CONNECTION.getAssetProof(
-      new PublicKey(assetA.id)
-    );
-    const assetProofB = await CONNECTION.getAssetProof(
-      new PublicKey(assetB.id)
-    );
-
-    const treeAccount = await ConcurrentMerkleTreeAccount.fromAccountAddress(
-      CONNECTION,
-      TREE_ID
-    );
-    const canopyDepth = treeAccount.getCanopyDepth();
-
-    // parse the list of proof addresses into a valid AccountMeta[]
-    const proofPathA: AccountMeta[] = assetProofA.proof
-      .map((node: string) => ({
-        pubkey: new PublicKey(node),
-        isSigner: false,
-        isWritable: false,
-      }))
-      .slice(0, assetProofA.proof.length - (!!canopyDepth ? canopyDepth : 0));
-
-    const proofPathB: AccountMeta[] = assetProofB.proof
-      .map((node: string) => ({
-        pubkey: new PublicKey(node),
-        isSigner: false,
-        isWritable: false,
-      }))
-      .slice(0, assetProofB.proof.length - (!!canopyDepth ? canopyDepth : 0));
-
-    const amountA = new URL(assetA.content.json_uri).searchParams.get("amount");
-    const amountB = new URL(assetB.content.json_uri).searchParams.get("amount");
-    const combineIx = await PROGRAM.methods
-      .combine(
-        [new BN(amountA), new BN(amountB)],
-        [new PublicKey(assetA.id), new PublicKey(assetB.id)],
-        [
-          [...new PublicKey(assetProofA.root.trim()).toBytes()],
-          [...new PublicKey(assetProofB.root.trim()).toBytes()],
-        ],
-        [
-          new anchor.BN(assetA.compression.leaf_id),
-          new anchor.BN(assetB.compression.leaf_id),
-        ],
-        [assetA.compression.leaf_id, assetB.compression.leaf_id],
-        [proofPathA.length, proofPathA.length + proofPathB.length]
-      )
-      .accounts({
-        authority: SIGNER.publicKey,
-        bubblegumSigner,
-        collectionMetadata: metadata,
-        collectionMint: mint,
-        compressionProgram: COMPRESSION_PROGRAM_ID,
-        editionAccount: masterEdition,
-        leafDelegate: SIGNER.publicKey,
-        leafOwner: SIGNER.publicKey,
-        logWrapper: SPL_NOOP_PROGRAM_ID,
-        merkleTree: TREE_ID,
-        mplBubblegumProgram: BUBBLEGUM_PROGRAM_ID,
-        newLeafOwner: SIGNER.publicKey,
-        systemProgram: anchor.web3.SystemProgram.programId,
-        tinySplAuthority,
-        tokenMetadataProgram: mplTokenMetadataProgramId,
-        treeAuthority,
-        treeCreatorOrDelegate: TREE_CREATOR.publicKey,
-      })
-      .remainingAccounts([...proofPathA, ...proofPathB])
-      .instruction();
+    const result = await sendAsOwner(
+      [
+        await buildSplitIx({
+          mintAccounts: denylistMintAccounts,
+          leaf,
+          amounts: [1, 2],
+        }),
+      ],
+      owner
+    );
+
+    expectCustomError(result, 6023);
+  });
 
-    const modifyComputeUnits = ComputeBudgetProgram.setComputeUnitLimit({
-      units: 1_400_000,
+  it("should not split to a denied wallet", async () => {
+    const denylistMintAccounts = await createTestMint({
+      complianceConfig: DENYLIST_CONFIG,
     });
+    const owner = await createOwner();
+    const receiver = anchor.web3.Keypair.generate().publicKey;
+    const [leaf] = await mintLeaves(denylistMintAccounts, owner.publicKey, [
+      TOKENS_TO_MINT,
+    ]);
+    await denyWallet(denylistMintAccounts, receiver);
 
-    const result = await sendAndConfirmIxs(
-      [modifyComputeUnits, combineIx],
-      SIGNER.publicKey,
-      [SIGNER, TREE_CREATOR],
-      true
+    const result = await sendAsOwner(
+      [
+        await buildSplitIx({
+          mintAccounts: denylistMintAccounts,
+          leaf,
+          amounts: [1, 2],
+          accounts: {
+            newLeafOwner: receiver,
+            ...getWalletAccounts(denylistMintAccounts, {
+              newLeafOwner: receiver,
+            }),
+          },
+        }),
+      ],
+      owner
     );
 
-    expect(result.value.err).to.be.null;
+    expectCustomError(result, 6023);
+  });
 
-    await new Promise((resolve) => setTimeout(resolve, 1000));
+  it("should not let a denied wallet combine", async () => {
+    const denylistMintAccounts = await createTestMint({
+      complianceConfig: DENYLIST_CONFIG,
+    });
+    const owner = await createOwner();
+    const leaves = await mintLeaves(denylistMintAccounts, owner.publicKey, [
+      1, 2,
+    ]);
+    await denyWallet(denylistMintAccounts, owner.publicKey);
 
-    const newestAsset = (
-      await CONNECTION.getAssetsByOwner({
-        ownerAddress: SIGNER.publicKey.toBase58(),
-        limit: 1,
-        sortBy: {
-          sortBy: "created",
-          sortDirection: "desc",
-        },
-      })
-    ).items[0];
-    const amount = new URL(newestAsset.content.json_uri).searchParams.get(
-      "amount"
+    const result = await sendAsOwner(
+      [
+        await buildCombineIx({
+          mintAccounts: denylistMintAccounts,
+          leaves,
+        }),
+      ],
+      owner
     );
 
-    expect(parseInt(amount)).to.equal(parseInt(amountA) + parseInt(amountB));
+    expectCustomError(result, 6023);
+  });
+
+  it("should not combine to a denied wallet", async () => {
+    const denylistMintAccounts = await createTestMint({
+      complianceConfig: DENYLIST_CONFIG,
+    });
+    const owner = await createOwner();
+    const receiver = anchor.web3.Keypair.generate().publicKey;
+    const leaves = await mintLeaves(denylistMintAccounts, owner.publicKey, [
+      1, 2,
+    ]);
+    await denyWallet(denylistMintAccounts, receiver);
+
+    const result = await sendAsOwner(
+      [
+        await buildCombineIx({
+          mintAccounts: denylistMintAccounts,
+          leaves,
+          accounts: {
+            newLeafOwner: receiver,
+            ...getWalletAccounts(denylistMintAccounts, {
+              newLeafOwner: receiver,
+            }),
+          },
+        }),
+      ],
+      owner
+    );
+
+    expectCustomError(result, 6023);
+  });
+
+  it("should not migrate an account in the current layout", async () => {
+    const testMintAccounts = await createTestMint();
+    const ix = await PROGRAM.methods
+      .migrateAccount()
+      .accounts({
+        payer: SIGNER.publicKey,
+        account: testMintAccounts.tinySplAuthority,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .instruction();
+
+    const result = await sendIxs([ix], []);
+
+    expectCustomError(result, 6036);
   });
 });